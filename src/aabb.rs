@@ -0,0 +1,144 @@
+use crate::{
+  newtypes::point::Point,
+  ray::Ray,
+  vec3::{Axis, Vec3},
+};
+
+const MINIMUM_THICKNESS: f32 = 0.0001;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Aabb {
+  minimum: Point,
+  maximum: Point,
+}
+
+impl Aabb {
+  pub fn new(corner_1: Point, corner_2: Point) -> Self {
+    Self {
+      minimum: corner_1.as_vec3().minimum(corner_2.as_vec3()).into(),
+      maximum: corner_1.as_vec3().maximum(corner_2.as_vec3()).into(),
+    }
+  }
+  pub fn new_from_points(points: &[Point]) -> Option<Self> {
+    let (first, rest) = points.split_first()?;
+    Some(rest.iter().fold(Self::new(*first, *first), |bounding_box, point| {
+      bounding_box.surrounding(&Self::new(*point, *point))
+    }))
+  }
+  pub const fn minimum(&self) -> Point {
+    self.minimum
+  }
+  pub const fn maximum(&self) -> Point {
+    self.maximum
+  }
+  pub fn surrounding(&self, other: &Self) -> Self {
+    Self {
+      minimum: self.minimum.as_vec3().minimum(other.minimum().as_vec3()).into(),
+      maximum: self.maximum.as_vec3().maximum(other.maximum().as_vec3()).into(),
+    }
+  }
+  /// The box shared by both; it is empty (minimum above maximum) when they do not overlap.
+  pub fn overlap(&self, other: &Self) -> Self {
+    Self {
+      minimum: self.minimum.as_vec3().maximum(other.minimum().as_vec3()).into(),
+      maximum: self.maximum.as_vec3().minimum(other.maximum().as_vec3()).into(),
+    }
+  }
+  /// Widens any axis thinner than `MINIMUM_THICKNESS` so flat shapes still have a volume to hit.
+  pub fn padded(&self) -> Self {
+    let mut minimum = [0.0; 3];
+    let mut maximum = [0.0; 3];
+    for (index, axis) in Axis::ALL.into_iter().enumerate() {
+      let low = self.minimum.as_vec3().axis(axis);
+      let high = self.maximum.as_vec3().axis(axis);
+      if high - low < MINIMUM_THICKNESS {
+        minimum[index] = low - MINIMUM_THICKNESS / 2.0;
+        maximum[index] = high + MINIMUM_THICKNESS / 2.0;
+      } else {
+        minimum[index] = low;
+        maximum[index] = high;
+      }
+    }
+    Self {
+      minimum: Point::from_f32_array_const(minimum),
+      maximum: Point::from_f32_array_const(maximum),
+    }
+  }
+  pub fn centroid(&self) -> Point {
+    Point::from_vec3_const((self.minimum.as_vec3() + self.maximum.as_vec3()) / 2.0)
+  }
+  pub fn extent(&self) -> Vec3 {
+    self.maximum.as_vec3() - self.minimum.as_vec3()
+  }
+  pub fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> bool {
+    self.hit_interval(ray, minimum_distance, maximum_distance).is_some()
+  }
+  /// The part of `minimum_distance..maximum_distance` during which the ray is inside the box.
+  pub fn hit_interval(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<(f32, f32)> {
+    let mut minimum_distance = minimum_distance;
+    let mut maximum_distance = maximum_distance;
+    for axis in Axis::ALL {
+      let inverse_direction = 1.0 / ray.direction().as_vec3().axis(axis);
+      let origin = ray.origin().as_vec3().axis(axis);
+      let mut near = (self.minimum.as_vec3().axis(axis) - origin) * inverse_direction;
+      let mut far = (self.maximum.as_vec3().axis(axis) - origin) * inverse_direction;
+      if inverse_direction < 0.0 {
+        std::mem::swap(&mut near, &mut far);
+      }
+      minimum_distance = minimum_distance.max(near);
+      maximum_distance = maximum_distance.min(far);
+      if maximum_distance <= minimum_distance {
+        return None;
+      }
+    }
+    Some((minimum_distance, maximum_distance))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+  };
+
+  use super::Aabb;
+
+  #[test]
+  fn new_orders_corners() {
+    let bounding_box = Aabb::new(
+      Point::from_f32_array_const([1.0, -1.0, 2.0]),
+      Point::from_f32_array_const([-1.0, 1.0, 0.0]),
+    );
+    assert_eq!(bounding_box.minimum(), Point::from_f32_array_const([-1.0, -1.0, 0.0]));
+    assert_eq!(bounding_box.maximum(), Point::from_f32_array_const([1.0, 1.0, 2.0]));
+  }
+  #[test]
+  fn ray_hits_and_misses_box() {
+    let bounding_box = Aabb::new(
+      Point::from_f32_array_const([-1.0, -1.0, -3.0]),
+      Point::from_f32_array_const([1.0, 1.0, -2.0]),
+    );
+    let hitting_ray = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    assert!(bounding_box.hit(&hitting_ray, 0.0, f32::INFINITY));
+    assert!(!bounding_box.hit(&hitting_ray, 0.0, 1.0));
+    let missing_ray = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+    );
+    assert!(!bounding_box.hit(&missing_ray, 0.0, f32::INFINITY));
+  }
+  #[test]
+  fn padded_gives_flat_box_thickness() {
+    let bounding_box = Aabb::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Point::from_f32_array_const([1.0, 1.0, 0.0]),
+    )
+    .padded();
+    assert!(bounding_box.maximum().z() > bounding_box.minimum().z());
+    assert!(bounding_box.maximum().x().eq(&1.0));
+  }
+}
@@ -0,0 +1,192 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  ray::Ray,
+  vec3::Axis,
+};
+
+#[derive(Debug, Clone)]
+enum Node {
+  Leaf(Arc<dyn Hittable>),
+  Branch {
+    left: Box<Node>,
+    right: Box<Node>,
+    bounding_box: Aabb,
+  },
+}
+
+impl Node {
+  fn build(mut objects: Vec<(Aabb, Arc<dyn Hittable>)>) -> Self {
+    if objects.len() == 1 {
+      let (_, object) = objects.pop().expect("There is exactly one object");
+      return Self::Leaf(object);
+    }
+    let centroid_bounds = objects
+      .iter()
+      .map(|(bounding_box, _)| Aabb::new(bounding_box.centroid(), bounding_box.centroid()))
+      .reduce(|bounds, centroid| bounds.surrounding(&centroid))
+      .expect("A branch has at least two objects");
+    let extent = centroid_bounds.extent();
+    let split_axis = Axis::ALL
+      .into_iter()
+      .max_by(|left, right| {
+        extent
+          .axis(*left)
+          .partial_cmp(&extent.axis(*right))
+          .unwrap_or(Ordering::Equal)
+      })
+      .expect("There are three axes");
+    objects.sort_by(|(left, _), (right, _)| {
+      left
+        .centroid()
+        .as_vec3()
+        .axis(split_axis)
+        .partial_cmp(&right.centroid().as_vec3().axis(split_axis))
+        .unwrap_or(Ordering::Equal)
+    });
+    let right_objects = objects.split_off(objects.len() / 2);
+    let left = Self::build(objects);
+    let right = Self::build(right_objects);
+    let bounding_box = left.bounding_box().surrounding(&right.bounding_box());
+    Self::Branch {
+      left: Box::new(left),
+      right: Box::new(right),
+      bounding_box,
+    }
+  }
+  fn bounding_box(&self) -> Aabb {
+    match self {
+      Self::Leaf(object) => object.bounding_box().expect("Only bounded objects are stored in the tree"),
+      Self::Branch { bounding_box, .. } => *bounding_box,
+    }
+  }
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    match self {
+      Self::Leaf(object) => object.hit(ray, minimum_distance, maximum_distance),
+      Self::Branch {
+        left,
+        right,
+        bounding_box,
+      } => {
+        if !bounding_box.hit(ray, minimum_distance, maximum_distance) {
+          return None;
+        }
+        let left_record = left.hit(ray, minimum_distance, maximum_distance);
+        let closest_so_far = left_record.as_ref().map_or(maximum_distance, HitRecord::distance);
+        right.hit(ray, minimum_distance, closest_so_far).or(left_record)
+      }
+    }
+  }
+}
+
+/// A bounding volume hierarchy split at the median centroid of the longest axis.
+/// Objects without a bounding box, such as planes, are tested separately on every ray.
+#[derive(Debug, Default, Clone)]
+pub struct Bvh {
+  root: Option<Node>,
+  unbounded: Vec<Arc<dyn Hittable>>,
+}
+
+impl Bvh {
+  pub fn new(objects: Vec<Arc<dyn Hittable>>) -> Self {
+    let mut bounded = Vec::with_capacity(objects.len());
+    let mut unbounded = Vec::new();
+    for object in objects {
+      match object.bounding_box() {
+        Some(bounding_box) => bounded.push((bounding_box, object)),
+        None => unbounded.push(object),
+      }
+    }
+    Self {
+      root: if bounded.is_empty() {
+        None
+      } else {
+        Some(Node::build(bounded))
+      },
+      unbounded,
+    }
+  }
+}
+
+impl Hittable for Bvh {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let mut closest_record = self
+      .root
+      .as_ref()
+      .and_then(|root| root.hit(ray, minimum_distance, maximum_distance));
+    for object in &self.unbounded {
+      let closest_so_far = closest_record.as_ref().map_or(maximum_distance, HitRecord::distance);
+      if let Some(record) = object.hit(ray, minimum_distance, closest_so_far) {
+        closest_record = Some(record);
+      }
+    }
+    closest_record
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    if !self.unbounded.is_empty() {
+      return None;
+    }
+    self.root.as_ref().map(Node::bounding_box)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    hittable_list::HittableList,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    random::Random,
+    ray::Ray,
+    shapes::{plane::Plane, sphere::Sphere},
+    vec3::Axis,
+  };
+
+  use super::Bvh;
+
+  #[test]
+  fn matches_linear_search() {
+    let mut random = Random::new(3);
+    let mut list = HittableList::new();
+    let mut objects: Vec<Arc<dyn Hittable>> = Vec::new();
+    for _ in 0..50 {
+      let sphere: Arc<dyn Hittable> = Arc::new(Sphere::new_const(
+        Point::from_f32_array_const([
+          random.range_f32(-10.0, 10.0),
+          random.range_f32(-10.0, 10.0),
+          random.range_f32(-10.0, 10.0),
+        ]),
+        Distance::try_from_const(random.range_f32(0.1, 1.0)).unwrap(),
+      ));
+      list.add(sphere.clone());
+      objects.push(sphere);
+    }
+    let plane: Arc<dyn Hittable> = Arc::new(Plane::new_axis_aligned(Axis::Y, -20.0));
+    list.add(plane.clone());
+    objects.push(plane);
+    let bvh = Bvh::new(objects);
+    assert!(bvh.bounding_box().is_none());
+    for _ in 0..200 {
+      let ray = Ray::new(Point::from_f32_array_const([0.0, 0.0, 0.0]), random.unit_direction());
+      let expected = list.hit(&ray, 0.001, f32::INFINITY).map(|record| record.distance());
+      let actual = bvh.hit(&ray, 0.001, f32::INFINITY).map(|record| record.distance());
+      assert_eq!(expected.is_some(), actual.is_some());
+      if let (Some(expected), Some(actual)) = (expected, actual) {
+        assert!((expected - actual).abs() < 1e-6);
+      }
+    }
+  }
+  #[test]
+  fn empty_hierarchy_hits_nothing() {
+    let bvh = Bvh::new(Vec::new());
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    assert!(bvh.hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+}
@@ -1,6 +1,9 @@
 use crate::{
+  error::LocalError,
   image::Image,
   newtypes::{dimension::Dimension, direction::Direction, distance::Distance, point::Point},
+  random::Random,
+  ray::{Ray, RayDifferentials},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -15,12 +18,29 @@ impl Default for WidthOrHeight {
   }
 }
 
+/// How rays leave the camera: from its origin through the viewport, or in parallel from across the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Projection {
+  Perspective,
+  /// Every ray points straight ahead, so sizes do not shrink with distance.
+  Orthographic,
+}
+
+impl Default for Projection {
+  fn default() -> Self {
+    Self::Perspective
+  }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Camera {
   image: Image,
   view_port_height_or_width: WidthOrHeight,
   focal_length: Distance,
   origin: Point,
+  shutter_open: f32,
+  shutter_close: f32,
+  projection: Projection,
 }
 
 impl Camera {
@@ -35,6 +55,9 @@ impl Camera {
       view_port_height_or_width: WidthOrHeight::Width(viewport_width),
       focal_length,
       origin,
+      shutter_open: 0.0,
+      shutter_close: 0.0,
+      projection: Projection::Perspective,
     }
   }
   pub const fn new_from_viewport_height(
@@ -48,6 +71,9 @@ impl Camera {
       view_port_height_or_width: WidthOrHeight::Height(viewport_height),
       focal_length,
       origin,
+      shutter_open: 0.0,
+      shutter_close: 0.0,
+      projection: Projection::Perspective,
     }
   }
   pub fn viewport_width(&self) -> Dimension {
@@ -68,6 +94,29 @@ impl Camera {
   pub const fn focal_length(&self) -> Distance {
     self.focal_length
   }
+  /// Spreads ray times over `[open, close]` so moving objects blur across the exposure.
+  #[allow(dead_code)]
+  pub const fn with_shutter(self, open: f32, close: f32) -> Self {
+    Self {
+      shutter_open: open,
+      shutter_close: close,
+      ..self
+    }
+  }
+  /// Orthographic cameras cover the viewport at any distance, so its size sets how much of the scene is framed.
+  #[allow(dead_code)]
+  pub const fn with_projection(self, projection: Projection) -> Self {
+    Self { projection, ..self }
+  }
+  pub const fn projection(&self) -> Projection {
+    self.projection
+  }
+  pub const fn shutter_open(&self) -> f32 {
+    self.shutter_open
+  }
+  pub const fn shutter_close(&self) -> f32 {
+    self.shutter_close
+  }
   pub fn horizontal(&self) -> Direction {
     Direction::from_f32_array_const([f32::from(self.viewport_width()), 0.0, 0.0])
   }
@@ -80,4 +129,123 @@ impl Camera {
       - (self.vertical() / 2.0).into()
       - Point::from_f32_array_const([0.0, 0.0, self.focal_length().as_f32()])
   }
+  fn direction_to(&self, u: f32, v: f32) -> Direction {
+    (self.lower_left_corner() + (u * self.horizontal()).into() + (v * self.vertical()).into() - self.origin()).into()
+  }
+  /// Where an orthographic ray through `(u, v)` starts, on the plane through the origin facing the viewport.
+  fn origin_at(&self, u: f32, v: f32) -> Point {
+    self.origin() + ((u - 0.5) * self.horizontal()).into() + ((v - 0.5) * self.vertical()).into()
+  }
+  /// A ray through `(u, v)` on the viewport, carrying differentials toward the next pixel in each direction. Fails
+  /// when the image's size cannot be worked out.
+  pub fn get_ray(&self, u: f32, v: f32, random: &mut Random) -> Result<Ray, LocalError> {
+    let origin = self.origin();
+    let time = if self.shutter_close() > self.shutter_open() {
+      random.range_f32(self.shutter_open(), self.shutter_close())
+    } else {
+      self.shutter_open()
+    };
+    let pixel_width = 1.0 / f32::from(self.image.width()?);
+    let pixel_height = 1.0 / f32::from(self.image.height()?);
+    if self.projection() == Projection::Orthographic {
+      let forward = Direction::from_f32_array_const([0.0, 0.0, -self.focal_length().as_f32()]);
+      return Ok(
+        Ray::new_at_time(self.origin_at(u, v), forward, time).with_differentials(RayDifferentials::new(
+          self.origin_at(u + pixel_width, v),
+          forward,
+          self.origin_at(u, v + pixel_height),
+          forward,
+        )),
+      );
+    }
+    Ok(
+      Ray::new_at_time(origin, self.direction_to(u, v), time).with_differentials(RayDifferentials::new(
+        origin,
+        self.direction_to(u + pixel_width, v),
+        origin,
+        self.direction_to(u, v + pixel_height),
+      )),
+    )
+  }
+  /// A ray through a random point of the pixel at `column`, `row` of the full image, counted from the top left. Crop
+  /// windows render their pixels with these, so they see exactly what the full frame would.
+  pub fn get_pixel_ray(&self, column: usize, row: usize, random: &mut Random) -> Result<Ray, LocalError> {
+    let (width, height) = (f32::from(self.image.width()?), f32::from(self.image.height()?));
+    #[allow(clippy::cast_precision_loss)]
+    let (column, row) = (column as f32, row as f32);
+    let u = (column + random.next_f32()) / width;
+    let v = (height - row - random.next_f32()) / height;
+    self.get_ray(u, v, random)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    image::{AspectRatios, Image},
+    newtypes::{dimension::Dimension, distance::Distance, point::Point},
+    random::Random,
+  };
+
+  use super::{Camera, Projection};
+
+  fn camera() -> Camera {
+    Camera::new_from_viewport_height(
+      Image::new_from_width(AspectRatios::SixteenByNine, Dimension::from_const(16)),
+      Dimension::from_const(2),
+      Distance::try_from_const(1.0).unwrap(),
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+    )
+  }
+
+  #[test]
+  fn ray_times_stay_inside_the_shutter() {
+    let mut random = Random::new(7);
+    assert!(camera().get_ray(0.5, 0.5, &mut random).unwrap().time().eq(&0.0));
+    let camera = camera().with_shutter(0.25, 0.75);
+    for _ in 0..100 {
+      let time = camera.get_ray(0.5, 0.5, &mut random).unwrap().time();
+      assert!((0.25..0.75).contains(&time));
+    }
+  }
+  #[test]
+  fn pixel_rays_pass_through_their_pixel_of_the_full_frame() {
+    let camera = camera();
+    let mut random = Random::new(8);
+    let (width, height) = (f32::from(camera.viewport_width()), f32::from(camera.viewport_height()));
+    for _ in 0..100 {
+      let direction = camera.get_pixel_ray(15, 0, &mut random).unwrap().direction();
+      let (x, y) = (direction.x() / -direction.z(), direction.y() / -direction.z());
+      assert!(x > width / 2.0 * (14.0 / 16.0) - 1e-5 && x < width / 2.0 + 1e-5);
+      assert!(y > height / 2.0 * (7.0 / 9.0) - 1e-5 && y < height / 2.0 + 1e-5);
+    }
+  }
+  #[test]
+  fn rays_carry_one_pixel_differentials() {
+    let camera = camera();
+    let ray = camera.get_ray(0.5, 0.5, &mut Random::new(1)).unwrap();
+    let differentials = ray.differentials().expect("Camera rays have differentials");
+    let step = differentials.x_direction() - ray.direction();
+    assert!((step.x() - f32::from(camera.viewport_width()) / 16.0).abs() < 1e-5);
+    assert!(step.y().abs() < 1e-6);
+    let step = differentials.y_direction() - ray.direction();
+    assert!((step.y() - f32::from(camera.viewport_height()) / f32::from(camera.image.height().unwrap())).abs() < 1e-5);
+  }
+  #[test]
+  fn orthographic_rays_run_parallel_from_across_the_viewport() {
+    let camera = camera().with_projection(Projection::Orthographic);
+    let mut random = Random::new(9);
+    let corner = camera.get_ray(0.0, 0.0, &mut random).unwrap();
+    let center = camera.get_ray(0.5, 0.5, &mut random).unwrap();
+    assert_eq!(corner.direction(), center.direction());
+    assert!(center.direction().x().abs() < 1e-6 && center.direction().y().abs() < 1e-6);
+    assert!(center.origin().x().abs() < 1e-6 && center.origin().y().abs() < 1e-6);
+    assert!((corner.origin().x() + f32::from(camera.viewport_width()) / 2.0).abs() < 1e-5);
+    assert!((corner.origin().y() + f32::from(camera.viewport_height()) / 2.0).abs() < 1e-5);
+    assert!(corner.origin().z().abs() < 1e-6);
+    let differentials = center.differentials().expect("Camera rays have differentials");
+    assert_eq!(differentials.x_direction(), center.direction());
+    let step = differentials.x_origin().x() - center.origin().x();
+    assert!((step - f32::from(camera.viewport_width()) / 16.0).abs() < 1e-5);
+  }
 }
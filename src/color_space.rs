@@ -0,0 +1,232 @@
+use crate::vec3::Vec3;
+
+type Matrix = [[f32; 3]; 3];
+
+/// Linear Rec.709 primaries to CIE XYZ, both with a D65 white point.
+const REC709_TO_XYZ: Matrix = [
+  [0.412_390_8, 0.357_584_3, 0.180_480_8],
+  [0.212_639, 0.715_168_7, 0.072_192_3],
+  [0.019_330_8, 0.119_194_8, 0.950_532_2],
+];
+const XYZ_TO_REC709: Matrix = [
+  [3.240_97, -1.537_383_2, -0.498_610_8],
+  [-0.969_243_6, 1.875_967_5, 0.041_555_1],
+  [0.055_630_1, -0.203_977, 1.056_971_5],
+];
+/// Linear Display P3 primaries to CIE XYZ, both with a D65 white point.
+const DISPLAY_P3_TO_XYZ: Matrix = [
+  [0.486_570_9, 0.265_667_7, 0.198_217_3],
+  [0.228_974_6, 0.691_738_5, 0.079_286_9],
+  [0.0, 0.045_113_4, 1.043_944_4],
+];
+const XYZ_TO_DISPLAY_P3: Matrix = [
+  [2.493_497, -0.931_383_6, -0.402_710_8],
+  [-0.829_489, 1.762_664_1, 0.023_624_7],
+  [0.035_845_8, -0.076_172_4, 0.956_884_5],
+];
+/// `ACEScg` (AP1) primaries to CIE XYZ, Bradford adapted from the ACES white point to D65 so every space shares one
+/// white.
+const ACESCG_TO_XYZ: Matrix = [
+  [0.652_237_5, 0.128_236_1, 0.169_982_2],
+  [0.267_672_2, 0.674_34, 0.057_987_8],
+  [-0.005_381_8, 0.001_369_1, 1.093_070_5],
+];
+const XYZ_TO_ACESCG: Matrix = [
+  [1.660_585_3, -0.315_295_6, -0.241_509_3],
+  [-0.659_926_1, 1.608_391_5, 0.017_298_6],
+  [0.009_002_6, -0.003_566_9, 0.913_643_3],
+];
+
+fn apply(matrix: &Matrix, color: Vec3) -> Vec3 {
+  let row = |row: [f32; 3]| row[0] * color.x() + row[1] * color.y() + row[2] * color.z();
+  Vec3::new([row(matrix[0]), row(matrix[1]), row(matrix[2])])
+}
+
+/// The `sRGB` transfer curve from linear light to encoded values, mirrored for negative values.
+fn encode_channel(linear: f32) -> f32 {
+  let magnitude = linear.abs();
+  let encoded = if magnitude <= 0.003_130_8 {
+    12.92 * magnitude
+  } else {
+    1.055 * magnitude.powf(1.0 / 2.4) - 0.055
+  };
+  encoded.copysign(linear)
+}
+
+fn decode_channel(encoded: f32) -> f32 {
+  let magnitude = encoded.abs();
+  let linear = if magnitude <= 0.040_45 {
+    magnitude / 12.92
+  } else {
+    ((magnitude + 0.055) / 1.055).powf(2.4)
+  };
+  linear.copysign(encoded)
+}
+
+/// The RGB color spaces colors can be given, rendered and written in. Rendering happens in a linear working space;
+/// encoded spaces only appear at the boundaries, in image files and on displays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSpace {
+  /// Rec.709 primaries with the sRGB transfer curve, as in most 8-bit images.
+  Srgb,
+  /// Rec.709 primaries without a transfer curve, the default working space.
+  LinearRec709,
+  /// The ACES AP1 primaries, a wide linear working space.
+  AcesCg,
+  /// DCI-P3 primaries with a D65 white point and the sRGB transfer curve.
+  DisplayP3,
+}
+
+impl Default for ColorSpace {
+  fn default() -> Self {
+    Self::LinearRec709
+  }
+}
+
+impl ColorSpace {
+  pub const fn is_linear(self) -> bool {
+    matches!(self, Self::LinearRec709 | Self::AcesCg)
+  }
+  /// Removes the transfer curve, leaving linear light with this space's primaries.
+  pub fn decode(self, color: Vec3) -> Vec3 {
+    if self.is_linear() {
+      return color;
+    }
+    Vec3::new([
+      decode_channel(color.x()),
+      decode_channel(color.y()),
+      decode_channel(color.z()),
+    ])
+  }
+  /// Applies the transfer curve to linear light with this space's primaries.
+  pub fn encode(self, linear: Vec3) -> Vec3 {
+    if self.is_linear() {
+      return linear;
+    }
+    Vec3::new([
+      encode_channel(linear.x()),
+      encode_channel(linear.y()),
+      encode_channel(linear.z()),
+    ])
+  }
+  const fn to_xyz_matrix(self) -> &'static Matrix {
+    match self {
+      Self::Srgb | Self::LinearRec709 => &REC709_TO_XYZ,
+      Self::AcesCg => &ACESCG_TO_XYZ,
+      Self::DisplayP3 => &DISPLAY_P3_TO_XYZ,
+    }
+  }
+  const fn inverse_matrix(self) -> &'static Matrix {
+    match self {
+      Self::Srgb | Self::LinearRec709 => &XYZ_TO_REC709,
+      Self::AcesCg => &XYZ_TO_ACESCG,
+      Self::DisplayP3 => &XYZ_TO_DISPLAY_P3,
+    }
+  }
+  /// The CIE XYZ coordinates, relative to D65, of a color given in this space.
+  pub fn to_xyz(self, color: Vec3) -> Vec3 {
+    apply(self.to_xyz_matrix(), self.decode(color))
+  }
+  /// The color in this space with the given CIE XYZ coordinates relative to D65.
+  pub fn color_from_xyz(self, xyz: Vec3) -> Vec3 {
+    self.encode(apply(self.inverse_matrix(), xyz))
+  }
+  /// Re-expresses `color` from this space in `target`. Colors outside the target's gamut get negative channels.
+  pub fn convert(self, color: Vec3, target: Self) -> Vec3 {
+    if self == target {
+      return color;
+    }
+    if self.to_xyz_matrix() == target.to_xyz_matrix() {
+      return target.encode(self.decode(color));
+    }
+    target.color_from_xyz(self.to_xyz(color))
+  }
+}
+
+/// A color together with the space its channels are measured in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TaggedColor {
+  channels: Vec3,
+  color_space: ColorSpace,
+}
+
+impl TaggedColor {
+  pub const fn new(channels: Vec3, color_space: ColorSpace) -> Self {
+    Self { channels, color_space }
+  }
+  pub const fn channels(&self) -> Vec3 {
+    self.channels
+  }
+  pub const fn color_space(&self) -> ColorSpace {
+    self.color_space
+  }
+  pub fn converted_to(self, color_space: ColorSpace) -> Self {
+    Self::new(self.color_space.convert(self.channels, color_space), color_space)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::vec3::Vec3;
+
+  use super::{ColorSpace, TaggedColor};
+
+  const SPACES: [ColorSpace; 4] = [
+    ColorSpace::Srgb,
+    ColorSpace::LinearRec709,
+    ColorSpace::AcesCg,
+    ColorSpace::DisplayP3,
+  ];
+
+  fn close(first: Vec3, second: Vec3, tolerance: f32) -> bool {
+    (first - second).length().as_f32() < tolerance
+  }
+
+  #[test]
+  fn white_is_shared_by_every_space() {
+    let d65 = Vec3::new([0.950_456, 1.0, 1.089_058]);
+    for space in SPACES {
+      let white = space.to_xyz(Vec3::new([1.0, 1.0, 1.0]));
+      assert!(close(white, d65, 1e-3), "{:?} white is {}", space, white);
+    }
+  }
+  #[test]
+  fn conversions_round_trip() {
+    let color = Vec3::new([0.8, 0.3, 0.05]);
+    for from in SPACES {
+      for to in SPACES {
+        let there = TaggedColor::new(color, from).converted_to(to);
+        let back = there.converted_to(from);
+        assert_eq!(there.color_space(), to);
+        assert!(
+          close(back.channels(), color, 1e-4),
+          "{:?} to {:?} gave {}",
+          from,
+          to,
+          back.channels()
+        );
+      }
+    }
+  }
+  #[test]
+  fn wide_gamuts_hold_rec709_red_inside() {
+    let red = Vec3::new([1.0, 0.0, 0.0]);
+    let in_aces = ColorSpace::LinearRec709.convert(red, ColorSpace::AcesCg);
+    assert!(close(in_aces, Vec3::new([0.613_097, 0.070_194, 0.020_616]), 1e-4));
+    let in_p3 = ColorSpace::LinearRec709.convert(red, ColorSpace::DisplayP3);
+    assert!(in_p3.x() < 1.0 && in_p3.y() > 0.0 && in_p3.z() > 0.0);
+    let p3_red = ColorSpace::DisplayP3.convert(red, ColorSpace::LinearRec709);
+    assert!(p3_red.x() > 1.0 && p3_red.y() < 0.0);
+  }
+  #[test]
+  fn srgb_curve_brightens_mid_grey() {
+    let grey = ColorSpace::Srgb.encode(Vec3::new([0.214_041, 0.0, 1.0]));
+    assert!(close(grey, Vec3::new([0.5, 0.0, 1.0]), 1e-4));
+    let encoded = Vec3::new([0.02, 0.5, 0.9]);
+    assert!(close(
+      ColorSpace::Srgb.encode(ColorSpace::Srgb.decode(encoded)),
+      encoded,
+      1e-5
+    ));
+  }
+}
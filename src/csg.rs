@@ -0,0 +1,209 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  ray::Ray,
+};
+
+const CROSSING_EPSILON: f32 = 1e-4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Operation {
+  Union,
+  Intersection,
+  Difference,
+}
+
+impl Operation {
+  const fn is_inside(self, inside_left: bool, inside_right: bool) -> bool {
+    match self {
+      Self::Union => inside_left || inside_right,
+      Self::Intersection => inside_left && inside_right,
+      Self::Difference => inside_left && !inside_right,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Side {
+  Left,
+  Right,
+}
+
+/// Every surface crossing of `object` along the whole line of `ray`, in order, and whether the line starts inside it.
+fn crossings(object: &dyn Hittable, ray: &Ray) -> (bool, Vec<HitRecord>) {
+  let mut records = Vec::new();
+  let mut minimum_distance = f32::NEG_INFINITY;
+  while let Some(record) = object.hit(ray, minimum_distance, f32::INFINITY) {
+    minimum_distance = record.distance() + CROSSING_EPSILON * record.distance().abs().max(1.0);
+    records.push(record);
+  }
+  let starts_inside = records.first().map_or(false, |record| !record.front_face());
+  (starts_inside, records)
+}
+
+/// A boolean combination of two closed hittables, found by walking the entry and exit points of both along the ray.
+#[derive(Debug, Clone)]
+pub struct Csg {
+  left: Arc<dyn Hittable>,
+  right: Arc<dyn Hittable>,
+  operation: Operation,
+}
+
+impl Csg {
+  pub fn new(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>, operation: Operation) -> Self {
+    Self { left, right, operation }
+  }
+  pub fn union(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Self {
+    Self::new(left, right, Operation::Union)
+  }
+  pub fn intersection(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Self {
+    Self::new(left, right, Operation::Intersection)
+  }
+  pub fn difference(left: Arc<dyn Hittable>, right: Arc<dyn Hittable>) -> Self {
+    Self::new(left, right, Operation::Difference)
+  }
+  pub const fn operation(&self) -> Operation {
+    self.operation
+  }
+}
+
+impl Hittable for Csg {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    if let Some(bounding_box) = self.bounding_box() {
+      if !bounding_box.hit(ray, minimum_distance, maximum_distance) {
+        return None;
+      }
+    }
+    let (mut inside_left, left_records) = crossings(self.left.as_ref(), ray);
+    let (mut inside_right, right_records) = crossings(self.right.as_ref(), ray);
+    let mut events: Vec<(Side, HitRecord)> = left_records
+      .into_iter()
+      .map(|record| (Side::Left, record))
+      .chain(right_records.into_iter().map(|record| (Side::Right, record)))
+      .collect();
+    events.sort_by(|(_, left), (_, right)| left.distance().partial_cmp(&right.distance()).unwrap_or(Ordering::Equal));
+    let mut inside = self.operation.is_inside(inside_left, inside_right);
+    for (side, record) in events {
+      match side {
+        Side::Left => inside_left = record.front_face(),
+        Side::Right => inside_right = record.front_face(),
+      }
+      let now_inside = self.operation.is_inside(inside_left, inside_right);
+      if now_inside == inside {
+        continue;
+      }
+      inside = now_inside;
+      if record.distance() < minimum_distance {
+        continue;
+      }
+      if record.distance() > maximum_distance {
+        return None;
+      }
+      if side == Side::Right && self.operation == Operation::Difference {
+        let flipped_normal = -record.outward_normal();
+        let flipped_shading_normal = -record.outward_shading_normal();
+        let (tangent, bitangent) = (record.tangent(), record.bitangent());
+        return Some(
+          record
+            .reoriented(ray, flipped_normal)
+            .with_shading_frame(flipped_shading_normal, tangent, bitangent),
+        );
+      }
+      return Some(record);
+    }
+    None
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let left = self.left.bounding_box();
+    let right = self.right.bounding_box();
+    match self.operation {
+      Operation::Union => Some(left?.surrounding(&right?)),
+      Operation::Intersection => match (left, right) {
+        (Some(left), Some(right)) => Some(left.overlap(&right)),
+        (Some(bounding_box), None) | (None, Some(bounding_box)) => Some(bounding_box),
+        (None, None) => None,
+      },
+      Operation::Difference => left,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+    shapes::sphere::Sphere,
+  };
+
+  use super::Csg;
+
+  fn sphere_at(x: f32) -> Arc<Sphere> {
+    Arc::new(Sphere::new_const(
+      Point::from_f32_array_const([x, 0.0, 0.0]),
+      Distance::try_from_const(1.0).unwrap(),
+    ))
+  }
+
+  fn along_x() -> Ray {
+    Ray::new(
+      Point::from_f32_array_const([-5.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    )
+  }
+
+  fn all_distances(object: &dyn Hittable, ray: &Ray) -> Vec<(f32, f32)> {
+    let mut minimum_distance = 0.0;
+    let mut hits = Vec::new();
+    while let Some(record) = object.hit(ray, minimum_distance, f32::INFINITY) {
+      hits.push((record.distance(), record.outward_normal().x()));
+      minimum_distance = record.distance() + 1e-3;
+    }
+    hits
+  }
+
+  fn assert_hits(actual: &[(f32, f32)], expected: &[(f32, f32)]) {
+    assert_eq!(actual.len(), expected.len(), "{:?} != {:?}", actual, expected);
+    for (actual, expected) in actual.iter().zip(expected) {
+      assert!((actual.0 - expected.0).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+      assert!((actual.1 - expected.1).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+    }
+  }
+
+  #[test]
+  fn union_keeps_outer_surfaces() {
+    let union = Csg::union(sphere_at(-0.5), sphere_at(0.5));
+    assert_hits(&all_distances(&union, &along_x()), &[(3.5, -1.0), (6.5, 1.0)]);
+  }
+  #[test]
+  fn intersection_makes_a_lens() {
+    let lens = Csg::intersection(sphere_at(-0.5), sphere_at(0.5));
+    assert_hits(&all_distances(&lens, &along_x()), &[(4.5, -1.0), (5.5, 1.0)]);
+    let bounding_box = lens.bounding_box().expect("Both spheres are bounded");
+    assert!((bounding_box.minimum().x() + 0.5).abs() < 1e-5);
+    assert!((bounding_box.maximum().x() - 0.5).abs() < 1e-5);
+  }
+  #[test]
+  fn difference_flips_normals_of_subtracted_surface() {
+    let bitten = Csg::difference(sphere_at(-0.5), sphere_at(0.5));
+    assert_hits(&all_distances(&bitten, &along_x()), &[(3.5, -1.0), (4.5, 1.0)]);
+    let record = bitten
+      .hit(&along_x(), 4.0, f32::INFINITY)
+      .expect("The ray leaves the bitten sphere");
+    assert!(!record.front_face());
+  }
+  #[test]
+  fn nested_operations_work_from_inside() {
+    let drilled = Csg::difference(Arc::new(Csg::union(sphere_at(-0.5), sphere_at(0.5))), sphere_at(0.0));
+    let from_center = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    assert_hits(&all_distances(&drilled, &from_center), &[(1.0, -1.0), (1.5, 1.0)]);
+  }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::direction::Direction,
+  orthonormal_basis::OrthonormalBasis,
+  ray::Ray,
+  textures::Texture,
+};
+
+/// The step in texture coordinates used to take finite differences of a bump map.
+const BUMP_DELTA: f32 = 1.0 / 1024.0;
+
+/// Surface detail that changes only the shading normal, never the geometry.
+#[derive(Debug, Clone)]
+pub enum Detail {
+  /// A tangent-space normal map with channels in `[0, 1]`, where `(0.5, 0.5, 1)` is the unperturbed normal.
+  /// `strength` scales the tangential components.
+  NormalMap { texture: Arc<dyn Texture>, strength: f32 },
+  /// A height map that displaces the surface along its normal; `scale` is the height in world units of a texture value
+  /// of one. The tilt follows the shape's surface derivatives, so the same map leans less on a larger surface.
+  BumpMap { height: Arc<dyn Texture>, scale: f32 },
+}
+
+/// Wraps an object so its hits carry a shading normal perturbed by `detail`.
+#[derive(Debug, Clone)]
+pub struct DetailMapped {
+  object: Arc<dyn Hittable>,
+  detail: Detail,
+}
+
+impl DetailMapped {
+  pub fn new(object: Arc<dyn Hittable>, detail: Detail) -> Self {
+    Self { object, detail }
+  }
+  pub fn object(&self) -> &Arc<dyn Hittable> {
+    &self.object
+  }
+  pub const fn detail(&self) -> &Detail {
+    &self.detail
+  }
+}
+
+/// The tangent frame of a record, built around the shading normal when the shape did not supply one.
+fn tangent_frame(record: &HitRecord) -> (Direction, Direction) {
+  if record.tangent().near_zero() || record.bitangent().near_zero() {
+    let basis = OrthonormalBasis::new_from_w(record.outward_shading_normal());
+    (basis.u(), basis.v())
+  } else {
+    (record.tangent(), record.bitangent())
+  }
+}
+
+/// Applies `detail` to the shading normal of `record`, which the record then guards against facing into the surface.
+pub fn apply_detail(detail: &Detail, record: HitRecord) -> HitRecord {
+  let (tangent, bitangent) = tangent_frame(&record);
+  let normal = record.outward_shading_normal();
+  let uv = [record.u(), record.v()];
+  let point = record.point();
+  let perturbed = match detail {
+    Detail::NormalMap { texture, strength } => {
+      let encoded = texture.value(uv, point);
+      let x = (2.0 * encoded.x() - 1.0) * strength;
+      let y = (2.0 * encoded.y() - 1.0) * strength;
+      let z = 2.0 * encoded.z() - 1.0;
+      tangent * x + bitangent * y + normal * z
+    }
+    Detail::BumpMap { height, scale } => {
+      let [along_u, along_v] = record.surface_derivatives();
+      let (along_u, along_v) = if along_u.near_zero() || along_v.near_zero() {
+        (tangent, bitangent)
+      } else {
+        (along_u, along_v)
+      };
+      let height_at = |uv: [f32; 2], offset: Direction| {
+        let value = height.value(uv, point + offset.into());
+        (value.x() + value.y() + value.z()) / 3.0
+      };
+      let base = height_at(uv, Direction::default());
+      let slope_u = (height_at([uv[0] + BUMP_DELTA, uv[1]], along_u * BUMP_DELTA) - base) / BUMP_DELTA;
+      let slope_v = (height_at([uv[0], uv[1] + BUMP_DELTA], along_v * BUMP_DELTA) - base) / BUMP_DELTA;
+      let bumped = (along_u + normal * (scale * slope_u)).cross(along_v + normal * (scale * slope_v));
+      if bumped.dot(normal) < 0.0 {
+        -bumped
+      } else {
+        bumped
+      }
+    }
+  };
+  record.with_shading_frame(perturbed, tangent, bitangent)
+}
+
+impl Hittable for DetailMapped {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let record = self.object.hit(ray, minimum_distance, maximum_distance)?;
+    Some(apply_detail(&self.detail, record))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.object.bounding_box()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    mesh::Mesh,
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+    textures::{solid_color::SolidColor, Texture},
+    vec3::Vec3,
+  };
+
+  use super::{Detail, DetailMapped};
+
+  #[derive(Debug)]
+  struct RampInU;
+
+  impl Texture for RampInU {
+    fn value(&self, uv: [f32; 2], _point: Point) -> Vec3 {
+      Vec3::new([uv[0]; 3])
+    }
+  }
+
+  /// A `size` by `size` square in the xy plane whose texture coordinates run from zero to one.
+  fn quad(size: f32) -> Arc<dyn Hittable> {
+    let text = format!(
+      "v 0 0 0\nv {size} 0 0\nv {size} {size} 0\nv 0 {size} 0\nvt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\nf 1/1 2/2 3/3 4/4\n",
+      size = size
+    );
+    Arc::new(Mesh::parse_obj(&text).unwrap().into_bvh())
+  }
+  fn shading_normal(detail: Detail) -> (Direction, Direction) {
+    shading_normal_on(1.0, detail)
+  }
+  fn shading_normal_on(size: f32, detail: Detail) -> (Direction, Direction) {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.4 * size, 0.6 * size, 1.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = DetailMapped::new(quad(size), detail)
+      .hit(&ray, 0.0, f32::INFINITY)
+      .expect("The ray points at the quad");
+    (record.normal(), record.shading_normal())
+  }
+
+  #[test]
+  fn flat_normal_map_keeps_the_normal() {
+    let (_, normal) = shading_normal(Detail::NormalMap {
+      texture: Arc::new(SolidColor::new(Vec3::new([0.5, 0.5, 1.0]))),
+      strength: 1.0,
+    });
+    assert!((normal.z() - 1.0).abs() < 1e-5);
+  }
+  #[test]
+  fn normal_map_tilts_along_the_tangent() {
+    let (_, normal) = shading_normal(Detail::NormalMap {
+      texture: Arc::new(SolidColor::new(Vec3::new([1.0, 0.5, 1.0]))),
+      strength: 1.0,
+    });
+    let expected = Direction::from_f32_array_const([1.0, 0.0, 1.0]).unit_direction();
+    assert!((normal - expected).length().as_f32() < 1e-5);
+  }
+  #[test]
+  fn bump_map_leans_away_from_rising_height() {
+    let (_, normal) = shading_normal(Detail::BumpMap {
+      height: Arc::new(RampInU),
+      scale: 1.0,
+    });
+    let expected = Direction::from_f32_array_const([-1.0, 0.0, 1.0]).unit_direction();
+    assert!((normal - expected).length().as_f32() < 1e-3);
+  }
+  #[test]
+  fn bump_map_slope_shrinks_on_larger_surfaces() {
+    for (size, tilt) in [(1.0_f32, 1.0_f32), (10.0, 0.1)] {
+      let (_, normal) = shading_normal_on(
+        size,
+        Detail::BumpMap {
+          height: Arc::new(RampInU),
+          scale: 1.0,
+        },
+      );
+      let expected = Direction::from_f32_array_const([-tilt, 0.0, 1.0]).unit_direction();
+      assert!(
+        (normal - expected).length().as_f32() < 1e-3,
+        "{:?} on a quad of size {}",
+        normal,
+        size
+      );
+    }
+  }
+  #[test]
+  fn maps_pointing_into_the_surface_are_guarded() {
+    let (geometric, normal) = shading_normal(Detail::NormalMap {
+      texture: Arc::new(SolidColor::new(Vec3::new([1.0, 0.5, 0.0]))),
+      strength: 1.0,
+    });
+    assert!(normal.dot(geometric) > 0.0);
+  }
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::{
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  newtypes::{distance::Distance, point::Point, radiance::Radiance},
+  phase_function::PhaseFunction,
+  ray::Ray,
+  shapes::sphere::Sphere,
+  vec3::Vec3,
+  volumes::constant_medium::ConstantMedium,
+};
+
+/// A uniform haze filling a ball around the world origin, so rays that leave the ball still reach the sky.
+#[derive(Debug, Clone)]
+pub struct Fog {
+  medium: ConstantMedium,
+}
+
+impl Fog {
+  pub fn new(density: f32, albedo: Vec3, phase_function: PhaseFunction, radius: Distance) -> Result<Self, LocalError> {
+    let boundary = Arc::new(Sphere::new_const(Point::from_f32_array_const([0.0, 0.0, 0.0]), radius));
+    Ok(Self {
+      medium: ConstantMedium::new(boundary, density, albedo, phase_function)?,
+    })
+  }
+  pub const fn medium(&self) -> &ConstantMedium {
+    &self.medium
+  }
+  /// A scattering event in the fog before `maximum_distance`, if there is one.
+  pub fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    self.medium.hit(ray, minimum_distance, maximum_distance)
+  }
+}
+
+/// What a ray sees when it escapes the scene: a vertical sky gradient, optionally seen through fog.
+#[derive(Debug, Clone)]
+pub struct Environment {
+  horizon: Vec3,
+  zenith: Vec3,
+  fog: Option<Fog>,
+}
+
+impl Default for Environment {
+  fn default() -> Self {
+    Self::new(Vec3::new([1.0, 1.0, 1.0]), Vec3::new([0.5, 0.7, 1.0]))
+  }
+}
+
+impl Environment {
+  pub const fn new(horizon: Vec3, zenith: Vec3) -> Self {
+    Self {
+      horizon,
+      zenith,
+      fog: None,
+    }
+  }
+  pub fn with_fog(self, fog: Fog) -> Self {
+    Self { fog: Some(fog), ..self }
+  }
+  pub const fn fog(&self) -> Option<&Fog> {
+    self.fog.as_ref()
+  }
+  pub fn background(&self, ray: &Ray) -> Radiance {
+    let amount = 0.5 * (ray.direction().unit_direction().y() + 1.0);
+    Radiance::from(self.horizon * (1.0 - amount) + self.zenith * amount)
+  }
+}
@@ -16,3 +16,25 @@ impl Error for DistanceCannotBeNegative {}
 unsafe impl Send for DistanceCannotBeNegative {}
 #[allow(unsafe_code)]
 unsafe impl Sync for DistanceCannotBeNegative {}
+
+#[derive(Debug)]
+pub struct NodeNameAlreadyUsed(pub String);
+
+impl Display for NodeNameAlreadyUsed {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "A sibling node is already named {}", self.0)
+  }
+}
+
+impl Error for NodeNameAlreadyUsed {}
+
+#[derive(Debug)]
+pub struct NodeNameIsNotValid(pub String);
+
+impl Display for NodeNameIsNotValid {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Node names must be non-empty and cannot contain '/': {:?}", self.0)
+  }
+}
+
+impl Error for NodeNameIsNotValid {}
@@ -0,0 +1,236 @@
+use std::io::Write;
+
+use crate::{
+  color_space::{ColorSpace, TaggedColor},
+  error::LocalError,
+  image::{CropWindow, Image},
+  netpbm,
+  newtypes::{color::Rgb, dimension::Dimension, radiance::Radiance},
+  tone_mapping::ToneMapping,
+};
+
+/// What a cropped render writes: just the window, or the full frame with every pixel outside it set to a constant.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum CropOutput {
+  Cropped,
+  FullFrame(Rgb),
+}
+
+impl Default for CropOutput {
+  fn default() -> Self {
+    Self::Cropped
+  }
+}
+
+/// Accumulates unclamped radiance samples per pixel of a crop window, stored row by row from the top. Pixels are
+/// addressed by their position in the full image. Nothing is clipped until the image is developed for an output.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct Framebuffer {
+  window: CropWindow,
+  working_space: ColorSpace,
+  totals: Vec<Radiance>,
+  counts: Vec<u32>,
+}
+
+impl Framebuffer {
+  pub fn new(width: usize, height: usize) -> Result<Self, LocalError> {
+    if width == 0 || height == 0 {
+      return Err("A framebuffer needs at least one pixel".into());
+    }
+    let image = Image::new(Dimension::from(width), Dimension::from(height))?;
+    Ok(Self::new_cropped(CropWindow::new_full(image)?))
+  }
+  /// A framebuffer holding only the pixels inside `window`.
+  pub fn new_cropped(window: CropWindow) -> Self {
+    let pixel_count = window.width() * window.height();
+    Self {
+      window,
+      working_space: ColorSpace::default(),
+      totals: vec![Radiance::default(); pixel_count],
+      counts: vec![0; pixel_count],
+    }
+  }
+  /// The linear space the samples are rendered in.
+  pub fn with_working_space(self, working_space: ColorSpace) -> Self {
+    Self { working_space, ..self }
+  }
+  pub const fn window(&self) -> CropWindow {
+    self.window
+  }
+  pub const fn working_space(&self) -> ColorSpace {
+    self.working_space
+  }
+  fn index(&self, column: usize, row: usize) -> Option<usize> {
+    if !self.window.contains(column, row) {
+      return None;
+    }
+    Some((row - self.window.top()) * self.window.width() + column - self.window.left())
+  }
+  /// Adds a sample to a pixel, unless it is outside the window or not a finite number, so one broken path cannot
+  /// poison the whole pixel. Returns whether the sample was kept.
+  pub fn add_sample(&mut self, column: usize, row: usize, radiance: Radiance) -> bool {
+    let index = match self.index(column, row) {
+      Some(index) if radiance.is_finite() => index,
+      _ => return false,
+    };
+    self.totals[index] += radiance;
+    self.counts[index] += 1;
+    true
+  }
+  /// The mean radiance of a pixel's samples, black if it has none or lies outside the window.
+  pub fn pixel(&self, column: usize, row: usize) -> Radiance {
+    let index = match self.index(column, row) {
+      Some(index) => index,
+      None => return Radiance::default(),
+    };
+    let count = self.counts[index];
+    if count == 0 {
+      return Radiance::default();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let count = count as f32;
+    self.totals[index] / count
+  }
+  fn developed_pixel(&self, column: usize, row: usize, tone_mapping: ToneMapping, output: ColorSpace) -> Rgb {
+    let radiance = self
+      .working_space
+      .convert(self.pixel(column, row).as_vec3(), ColorSpace::LinearRec709);
+    let display = TaggedColor::new(tone_mapping.apply(Radiance::from(radiance)), ColorSpace::LinearRec709);
+    Rgb::new_from_color(display, output)
+  }
+  /// Tone maps every pixel in the window and encodes it in `output`, row by row from the top.
+  pub fn develop(&self, tone_mapping: ToneMapping, output: ColorSpace) -> Vec<Rgb> {
+    self
+      .window
+      .pixels()
+      .map(|(column, row)| self.developed_pixel(column, row, tone_mapping, output))
+      .collect()
+  }
+  /// Develops the full image, filling the pixels outside the window with `fill`.
+  pub fn develop_full_frame(&self, tone_mapping: ToneMapping, output: ColorSpace, fill: Rgb) -> Vec<Rgb> {
+    let window = self.window;
+    (0..window.full_height())
+      .flat_map(|row| (0..window.full_width()).map(move |column| (column, row)))
+      .map(|(column, row)| {
+        if window.contains(column, row) {
+          self.developed_pixel(column, row, tone_mapping, output)
+        } else {
+          fill
+        }
+      })
+      .collect()
+  }
+  /// Writes a developed pixmap, noting where the window sits in the full image in a header comment.
+  pub fn write_pixmap(
+    &self,
+    writer: &mut impl Write,
+    tone_mapping: ToneMapping,
+    output: ColorSpace,
+    crop_output: CropOutput,
+  ) -> Result<(), LocalError> {
+    let window = self.window;
+    let comment = format!(
+      "crop window {} {} {} {} of {} {}",
+      window.left(),
+      window.top(),
+      window.width(),
+      window.height(),
+      window.full_width(),
+      window.full_height()
+    );
+    match crop_output {
+      CropOutput::Cropped => netpbm::write_pixmap(
+        writer,
+        window.width(),
+        window.height(),
+        &[comment],
+        &self.develop(tone_mapping, output),
+      ),
+      CropOutput::FullFrame(fill) => netpbm::write_pixmap(
+        writer,
+        window.full_width(),
+        window.full_height(),
+        &[comment],
+        &self.develop_full_frame(tone_mapping, output, fill),
+      ),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    color_space::ColorSpace,
+    image::{CropWindow, Image},
+    newtypes::{color::Rgb, dimension::Dimension, radiance::Radiance},
+    tone_mapping::ToneMapping,
+  };
+
+  use super::{CropOutput, Framebuffer};
+
+  #[test]
+  fn keeps_radiance_beyond_display_range() {
+    assert!(Framebuffer::new(0, 4).is_err());
+    let mut framebuffer = Framebuffer::new(2, 1).unwrap();
+    assert!(framebuffer.add_sample(0, 0, Radiance::new([40.0, 2.0, 0.0])));
+    assert!(framebuffer.add_sample(0, 0, Radiance::new([20.0, 0.0, 0.0])));
+    assert_eq!(framebuffer.pixel(0, 0), Radiance::new([30.0, 1.0, 0.0]));
+    assert_eq!(framebuffer.pixel(1, 0), Radiance::default());
+  }
+  #[test]
+  fn drops_samples_that_are_not_numbers() {
+    let mut framebuffer = Framebuffer::new(1, 1).unwrap();
+    assert!(framebuffer.add_sample(0, 0, Radiance::new([2.0, 2.0, 2.0])));
+    assert!(!framebuffer.add_sample(0, 0, Radiance::new([f32::NAN, 0.0, 0.0])));
+    assert!(!framebuffer.add_sample(0, 0, Radiance::new([0.0, f32::INFINITY, 0.0])));
+    assert_eq!(framebuffer.pixel(0, 0), Radiance::new([2.0, 2.0, 2.0]));
+  }
+  #[test]
+  fn develops_each_output_with_its_own_operator() {
+    let mut framebuffer = Framebuffer::new(1, 1).unwrap();
+    framebuffer.add_sample(0, 0, Radiance::new([3.0, 3.0, 3.0]));
+    let clipped = framebuffer.develop(ToneMapping::Clip, ColorSpace::Srgb);
+    let filmic = framebuffer.develop(ToneMapping::AcesFitted, ColorSpace::Srgb);
+    assert_eq!(clipped.len(), 1);
+    assert_eq!(clipped[0].red_part(), 255);
+    assert!(filmic[0].red_part() < 255 && filmic[0].red_part() > 200);
+  }
+  #[test]
+  fn crops_keep_full_frame_coordinates() {
+    let image = Image::new(Dimension::from(4), Dimension::from(3)).unwrap();
+    let mut framebuffer = Framebuffer::new_cropped(CropWindow::new(image, 1, 1, 2, 1).unwrap());
+    assert!(!framebuffer.add_sample(0, 0, Radiance::new([1.0, 1.0, 1.0])));
+    assert!(framebuffer.add_sample(2, 1, Radiance::new([1.0, 1.0, 1.0])));
+    assert_eq!(framebuffer.pixel(2, 1), Radiance::new([1.0, 1.0, 1.0]));
+    assert_eq!(framebuffer.pixel(0, 0), Radiance::default());
+    let cropped = framebuffer.develop(ToneMapping::Clip, ColorSpace::Srgb);
+    assert_eq!(cropped.iter().map(Rgb::red_part).collect::<Vec<_>>(), vec![0, 255]);
+  }
+  #[test]
+  fn writes_cropped_or_filled_pixmaps_with_their_offsets() {
+    let image = Image::new(Dimension::from(3), Dimension::from(2)).unwrap();
+    let mut framebuffer = Framebuffer::new_cropped(CropWindow::new(image, 2, 1, 1, 1).unwrap());
+    framebuffer.add_sample(2, 1, Radiance::new([1.0, 1.0, 1.0]));
+    let mut cropped = Vec::new();
+    framebuffer
+      .write_pixmap(&mut cropped, ToneMapping::Clip, ColorSpace::Srgb, CropOutput::Cropped)
+      .unwrap();
+    assert_eq!(
+      String::from_utf8(cropped).unwrap(),
+      "P3\n# crop window 2 1 1 1 of 3 2\n1 1\n255\n255 255 255\n"
+    );
+    let mut whole_frame = Vec::new();
+    let outside = CropOutput::FullFrame(Rgb::new([0.0, 0.0, 128.0]).unwrap());
+    framebuffer
+      .write_pixmap(&mut whole_frame, ToneMapping::Clip, ColorSpace::Srgb, outside)
+      .unwrap();
+    let whole_frame = String::from_utf8(whole_frame).unwrap();
+    let mut lines = whole_frame.lines();
+    assert_eq!(
+      lines.by_ref().take(4).collect::<Vec<_>>(),
+      vec!["P3", "# crop window 2 1 1 1 of 3 2", "3 2", "255"]
+    );
+    assert!(lines.by_ref().take(5).all(|line| line == "0 0 128"));
+    assert_eq!(lines.collect::<Vec<_>>(), vec!["255 255 255"]);
+  }
+}
@@ -0,0 +1,235 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+  aabb::Aabb,
+  materials::Material,
+  newtypes::{direction::Direction, point::Point},
+  ray::{Ray, RayDifferentials},
+  textures::TextureFootprint,
+};
+
+const SINGULAR_EPSILON: f32 = 1e-12;
+
+/// The smallest cosine allowed between the shading and geometric normals, so shading never faces into the surface.
+const MINIMUM_SHADING_COSINE: f32 = 0.01;
+
+/// Tilts `shading` just far enough toward `geometric` that it stays in the same hemisphere.
+fn guarded_shading_normal(geometric: Direction, shading: Direction) -> Direction {
+  let shading = shading.unit_direction();
+  let cosine = shading.dot(geometric);
+  if cosine.is_nan() {
+    return geometric;
+  }
+  if cosine >= MINIMUM_SHADING_COSINE {
+    return shading;
+  }
+  (shading + geometric * (MINIMUM_SHADING_COSINE - cosine)).unit_direction()
+}
+
+/// Removes the part of `direction` along `normal` and normalises the rest, or gives zero if nothing is left.
+fn orthogonalised(direction: Direction, normal: Direction) -> Direction {
+  let projected = direction - normal * direction.dot(normal);
+  if projected.near_zero() {
+    Direction::default()
+  } else {
+    projected.unit_direction()
+  }
+}
+
+/// `normal` is the true geometric normal; `shading_normal` may be smoothed or perturbed for lighting, and both face
+/// against the incoming ray. `tangent` and `bitangent` are zero unless the shape supplies a surface frame, and
+/// `surface_derivatives` (how the point moves with `u` and `v`) are zero unless the shape supplies them.
+#[derive(Debug, Default, Clone)]
+pub struct HitRecord {
+  point: Point,
+  normal: Direction,
+  shading_normal: Direction,
+  tangent: Direction,
+  bitangent: Direction,
+  surface_derivatives: [Direction; 2],
+  texture_footprint: TextureFootprint,
+  distance: f32,
+  u: f32,
+  v: f32,
+  front_face: bool,
+  material: Option<Arc<dyn Material>>,
+}
+
+impl HitRecord {
+  /// Builds a record whose normal always faces against the incoming ray; `outward_normal` must be unit length.
+  pub fn new(ray: &Ray, distance: f32, outward_normal: Direction, surface_coordinates: [f32; 2]) -> Self {
+    let front_face = ray.direction().dot(outward_normal) < 0.0;
+    let normal = if front_face { outward_normal } else { -outward_normal };
+    Self {
+      point: ray.at(distance),
+      normal,
+      shading_normal: normal,
+      tangent: Direction::default(),
+      bitangent: Direction::default(),
+      surface_derivatives: [Direction::default(); 2],
+      texture_footprint: TextureFootprint::default(),
+      distance,
+      u: surface_coordinates[0],
+      v: surface_coordinates[1],
+      front_face,
+      material: None,
+    }
+  }
+  pub const fn point(&self) -> Point {
+    self.point
+  }
+  pub const fn normal(&self) -> Direction {
+    self.normal
+  }
+  pub const fn shading_normal(&self) -> Direction {
+    self.shading_normal
+  }
+  pub const fn tangent(&self) -> Direction {
+    self.tangent
+  }
+  pub const fn bitangent(&self) -> Direction {
+    self.bitangent
+  }
+  pub const fn surface_derivatives(&self) -> [Direction; 2] {
+    self.surface_derivatives
+  }
+  pub const fn texture_footprint(&self) -> TextureFootprint {
+    self.texture_footprint
+  }
+  pub const fn distance(&self) -> f32 {
+    self.distance
+  }
+  pub const fn u(&self) -> f32 {
+    self.u
+  }
+  pub const fn v(&self) -> f32 {
+    self.v
+  }
+  pub const fn front_face(&self) -> bool {
+    self.front_face
+  }
+  pub fn material(&self) -> Option<&Arc<dyn Material>> {
+    self.material.as_ref()
+  }
+  /// Attaches `material` unless a more specific one was already attached closer to the geometry.
+  pub fn with_default_material(mut self, material: &Arc<dyn Material>) -> Self {
+    if self.material.is_none() {
+      self.material = Some(material.clone());
+    }
+    self
+  }
+  pub fn outward_normal(&self) -> Direction {
+    if self.front_face {
+      self.normal
+    } else {
+      -self.normal
+    }
+  }
+  pub fn outward_shading_normal(&self) -> Direction {
+    if self.front_face {
+      self.shading_normal
+    } else {
+      -self.shading_normal
+    }
+  }
+  /// Replaces the shading frame. The normal is given facing outward like the geometric one, is kept above the
+  /// surface, and the tangents are made perpendicular to it.
+  pub fn with_shading_frame(self, outward_shading_normal: Direction, tangent: Direction, bitangent: Direction) -> Self {
+    let facing = if self.front_face {
+      outward_shading_normal
+    } else {
+      -outward_shading_normal
+    };
+    let shading_normal = guarded_shading_normal(self.normal, facing);
+    let tangent = orthogonalised(tangent, shading_normal);
+    let bitangent = orthogonalised(bitangent - tangent * bitangent.dot(tangent), shading_normal);
+    Self {
+      shading_normal,
+      tangent,
+      bitangent,
+      ..self
+    }
+  }
+  pub fn with_surface_derivatives(self, along_u: Direction, along_v: Direction) -> Self {
+    Self {
+      surface_derivatives: [along_u, along_v],
+      ..self
+    }
+  }
+  /// Where the two auxiliary rays of `differentials` cross the plane tangent to the surface at this hit.
+  pub fn tangent_plane_offsets(&self, differentials: &RayDifferentials) -> Option<(Point, Point)> {
+    let plane_offset = self.normal.dot(self.point.into());
+    let cross_plane = |origin: Point, direction: Direction| {
+      let denominator = self.normal.dot(direction);
+      if denominator.abs() < SINGULAR_EPSILON {
+        return None;
+      }
+      let distance = (plane_offset - self.normal.dot(origin.into())) / denominator;
+      Some(origin + (direction * distance).into())
+    };
+    Some((
+      cross_plane(differentials.x_origin(), differentials.x_direction())?,
+      cross_plane(differentials.y_origin(), differentials.y_direction())?,
+    ))
+  }
+  /// Measures how far `u` and `v` change across the pixel footprint described by `differentials`, by expressing the
+  /// offsets on the tangent plane in terms of the surface derivatives. Leaves a point sample when either is missing.
+  pub fn with_footprint_from(self, differentials: &RayDifferentials) -> Self {
+    let [along_u, along_v] = self.surface_derivatives;
+    let (uu, uv, vv) = (along_u.dot(along_u), along_u.dot(along_v), along_v.dot(along_v));
+    let determinant = uu * vv - uv * uv;
+    if determinant.abs() < SINGULAR_EPSILON {
+      return self;
+    }
+    let (x_point, y_point) = match self.tangent_plane_offsets(differentials) {
+      Some(points) => points,
+      None => return self,
+    };
+    let solve = |offset: Direction| {
+      let (offset_u, offset_v) = (along_u.dot(offset), along_v.dot(offset));
+      [
+        (vv * offset_u - uv * offset_v) / determinant,
+        (uu * offset_v - uv * offset_u) / determinant,
+      ]
+    };
+    let along_x = solve((x_point - self.point).into());
+    let along_y = solve((y_point - self.point).into());
+    if along_x.iter().chain(&along_y).any(|value| !value.is_finite()) {
+      return self;
+    }
+    Self {
+      texture_footprint: TextureFootprint::new(along_x, along_y),
+      ..self
+    }
+  }
+  /// Mirrors a scattered `direction` that a tilted shading normal sent below the geometric surface back above it.
+  pub fn kept_above_surface(&self, direction: Direction) -> Direction {
+    let cosine = direction.dot(self.normal);
+    if cosine < 0.0 {
+      direction - self.normal * (2.0 * cosine)
+    } else {
+      direction
+    }
+  }
+  /// Moves the record onto `ray`, keeping the distance and surface coordinates found in another space.
+  /// The shading frame is reset to the new geometric normal; callers carry it over with `with_shading_frame`.
+  pub fn reoriented(self, ray: &Ray, outward_normal: Direction) -> Self {
+    let front_face = ray.direction().dot(outward_normal) < 0.0;
+    let normal = if front_face { outward_normal } else { -outward_normal };
+    Self {
+      point: ray.at(self.distance),
+      normal,
+      shading_normal: normal,
+      tangent: Direction::default(),
+      bitangent: Direction::default(),
+      front_face,
+      ..self
+    }
+  }
+}
+
+pub trait Hittable: Debug + Send + Sync {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord>;
+  /// Returns `None` for shapes without finite extent, such as infinite planes.
+  fn bounding_box(&self) -> Option<Aabb>;
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  ray::Ray,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct HittableList {
+  objects: Vec<Arc<dyn Hittable>>,
+}
+
+impl HittableList {
+  pub fn new() -> Self {
+    Self { objects: Vec::new() }
+  }
+  pub fn add(&mut self, object: Arc<dyn Hittable>) {
+    self.objects.push(object);
+  }
+  pub fn objects(&self) -> &[Arc<dyn Hittable>] {
+    &self.objects
+  }
+  pub fn is_empty(&self) -> bool {
+    self.objects.is_empty()
+  }
+  pub fn len(&self) -> usize {
+    self.objects.len()
+  }
+}
+
+impl Hittable for HittableList {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let mut closest_so_far = maximum_distance;
+    let mut closest_record = None;
+    for object in &self.objects {
+      if let Some(record) = object.hit(ray, minimum_distance, closest_so_far) {
+        closest_so_far = record.distance();
+        closest_record = Some(record);
+      }
+    }
+    closest_record
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let (first, rest) = self.objects.split_first()?;
+    rest.iter().try_fold(first.bounding_box()?, |bounding_box, object| {
+      Some(bounding_box.surrounding(&object.bounding_box()?))
+    })
+  }
+}
@@ -1,8 +1,21 @@
-use crate::newtypes::dimension::Dimension;
+use std::num::NonZeroU32;
 
+use crate::{error::LocalError, newtypes::dimension::Dimension};
+
+/// The shape of a picture as the ratio of its width to its height.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum AspectRatios {
   SixteenByNine,
+  OneByOne,
+  FourByThree,
+  ThreeByTwo,
+  TwentyOneByNine,
+  /// The 2.39:1 anamorphic widescreen of most feature films.
+  Scope,
+  Custom {
+    width: NonZeroU32,
+    height: NonZeroU32,
+  },
 }
 
 impl Default for AspectRatios {
@@ -11,19 +24,70 @@ impl Default for AspectRatios {
   }
 }
 
+const fn greatest_common_divisor(first: u128, second: u128) -> u128 {
+  let (mut first, mut second) = (first, second);
+  while second != 0 {
+    let remainder = first % second;
+    first = second;
+    second = remainder;
+  }
+  first
+}
+
+/// `numerator / denominator` rounded to the nearest integer, with halves rounding up. Positive results never round
+/// down to zero, so every image keeps at least one row and column.
+fn divide_rounding(numerator: u128, denominator: u128) -> Result<Dimension, LocalError> {
+  let (quotient, remainder) = (numerator / denominator, numerator % denominator);
+  let rounded = if remainder >= denominator - remainder {
+    quotient + 1
+  } else {
+    quotient
+  };
+  let rounded = if numerator > 0 { rounded.max(1) } else { rounded };
+  match usize::try_from(rounded) {
+    Ok(rounded) => Ok(Dimension::from(rounded)),
+    Err(_) => Err(format!("An image side of {} pixels does not fit in a usize", rounded).into()),
+  }
+}
+
+fn as_u128(dimension: Dimension) -> u128 {
+  u128::try_from(usize::from(dimension)).expect("Image dimensions fit in a u128")
+}
+
 impl AspectRatios {
-  pub const fn value(self) -> f32 {
+  /// A ratio of `width` to `height`, reduced to lowest terms.
+  pub fn new_custom(width: u32, height: u32) -> Result<Self, LocalError> {
+    let divisor = greatest_common_divisor(u128::from(width), u128::from(height));
+    let reduce = |part: u32| u32::try_from(u128::from(part) / divisor).ok().and_then(NonZeroU32::new);
+    match (reduce(width), reduce(height)) {
+      (Some(width), Some(height)) => Ok(Self::Custom { width, height }),
+      _ => Err("An aspect ratio needs a positive width and height".into()),
+    }
+  }
+  /// The ratio as whole numbers of width and height.
+  pub const fn parts(self) -> (u32, u32) {
     match self {
-      Self::SixteenByNine => 16.0 / 9.0,
+      Self::SixteenByNine => (16, 9),
+      Self::OneByOne => (1, 1),
+      Self::FourByThree => (4, 3),
+      Self::ThreeByTwo => (3, 2),
+      Self::TwentyOneByNine => (21, 9),
+      Self::Scope => (239, 100),
+      Self::Custom { width, height } => (width.get(), height.get()),
     }
   }
+  #[allow(clippy::cast_precision_loss)]
+  pub const fn value(self) -> f32 {
+    let (width, height) = self.parts();
+    width as f32 / height as f32
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum WidthOrHeight {
-  #[allow(dead_code)]
   Height(Dimension),
   Width(Dimension),
+  Both(Dimension, Dimension),
 }
 
 impl Default for WidthOrHeight {
@@ -32,38 +96,267 @@ impl Default for WidthOrHeight {
   }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// The size of a rendered picture in pixels. Give one side and an aspect ratio to derive the other, rounding to the
+/// nearest pixel with halves rounding up, or give both sides exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Image {
   aspect_ratio: AspectRatios,
   width_or_height: WidthOrHeight,
+  pixel_aspect_ratio: AspectRatios,
+}
+
+impl Default for Image {
+  fn default() -> Self {
+    Self::new_from_width(AspectRatios::default(), Dimension::from(0))
+  }
 }
 
 impl Image {
-  pub const fn _new_from_height(aspect_ratio: AspectRatios, height: Dimension) -> Self {
+  pub const fn new_from_height(aspect_ratio: AspectRatios, height: Dimension) -> Self {
     Self {
       aspect_ratio,
       width_or_height: WidthOrHeight::Height(height),
+      pixel_aspect_ratio: AspectRatios::OneByOne,
     }
   }
   pub const fn new_from_width(aspect_ratio: AspectRatios, width: Dimension) -> Self {
     Self {
       aspect_ratio,
       width_or_height: WidthOrHeight::Width(width),
+      pixel_aspect_ratio: AspectRatios::OneByOne,
+    }
+  }
+  /// An image of exactly `width` by `height` square pixels, whatever ratio that makes.
+  pub fn new(width: Dimension, height: Dimension) -> Result<Self, LocalError> {
+    let aspect_ratio = match (u32::try_from(usize::from(width)), u32::try_from(usize::from(height))) {
+      (Ok(width), Ok(height)) => AspectRatios::new_custom(width, height)?,
+      _ => return Err("An image's width and height must fit in a u32".into()),
+    };
+    Ok(Self {
+      aspect_ratio,
+      width_or_height: WidthOrHeight::Both(width, height),
+      pixel_aspect_ratio: AspectRatios::OneByOne,
+    })
+  }
+  /// Pixels that are displayed stretched, as in anamorphic formats. The aspect ratio stays that of the displayed
+  /// picture, so derived sides account for the stretch; exact images change their displayed shape instead.
+  pub fn with_pixel_aspect_ratio(self, pixel_aspect_ratio: AspectRatios) -> Self {
+    let aspect_ratio = match self.width_or_height {
+      WidthOrHeight::Both(width, height) => {
+        let (pixel_width, pixel_height) = pixel_aspect_ratio.parts();
+        let displayed_width = as_u128(width) * u128::from(pixel_width);
+        let displayed_height = as_u128(height) * u128::from(pixel_height);
+        let divisor = greatest_common_divisor(displayed_width, displayed_height);
+        match (
+          u32::try_from(displayed_width / divisor),
+          u32::try_from(displayed_height / divisor),
+        ) {
+          (Ok(width), Ok(height)) => AspectRatios::new_custom(width, height).unwrap_or(self.aspect_ratio),
+          _ => self.aspect_ratio,
+        }
+      }
+      WidthOrHeight::Height(_) | WidthOrHeight::Width(_) => self.aspect_ratio,
+    };
+    Self {
+      aspect_ratio,
+      pixel_aspect_ratio,
+      ..self
     }
   }
+  /// The shape of the displayed picture.
   pub const fn aspect_ratio(&self) -> AspectRatios {
     self.aspect_ratio
   }
-  pub fn height(&self) -> Dimension {
+  pub const fn pixel_aspect_ratio(&self) -> AspectRatios {
+    self.pixel_aspect_ratio
+  }
+  /// Fails when a derived height does not fit in a `usize`.
+  pub fn height(&self) -> Result<Dimension, LocalError> {
+    let (ratio_width, ratio_height) = self.aspect_ratio.parts();
+    let (pixel_width, pixel_height) = self.pixel_aspect_ratio.parts();
     match self.width_or_height {
-      WidthOrHeight::Height(height) => height,
-      WidthOrHeight::Width(width) => (f32::from(width) / self.aspect_ratio.value()).into(),
+      WidthOrHeight::Height(height) | WidthOrHeight::Both(_, height) => Ok(height),
+      WidthOrHeight::Width(width) => divide_rounding(
+        as_u128(width) * u128::from(pixel_width) * u128::from(ratio_height),
+        u128::from(pixel_height) * u128::from(ratio_width),
+      ),
     }
   }
-  pub fn _width(&self) -> Dimension {
+  /// Fails when a derived width does not fit in a `usize`.
+  pub fn width(&self) -> Result<Dimension, LocalError> {
+    let (ratio_width, ratio_height) = self.aspect_ratio.parts();
+    let (pixel_width, pixel_height) = self.pixel_aspect_ratio.parts();
     match self.width_or_height {
-      WidthOrHeight::Height(height) => (f32::from(height) * self.aspect_ratio.value()).into(),
-      WidthOrHeight::Width(width) => width,
+      WidthOrHeight::Height(height) => divide_rounding(
+        as_u128(height) * u128::from(pixel_height) * u128::from(ratio_width),
+        u128::from(pixel_width) * u128::from(ratio_height),
+      ),
+      WidthOrHeight::Width(width) | WidthOrHeight::Both(width, _) => Ok(width),
     }
   }
 }
+
+/// A rectangle of pixels within an image, measured from its top left corner, to render on its own while the camera
+/// still frames the whole image.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CropWindow {
+  full_width: usize,
+  full_height: usize,
+  left: usize,
+  top: usize,
+  width: usize,
+  height: usize,
+}
+
+impl CropWindow {
+  pub fn new(image: Image, left: usize, top: usize, width: usize, height: usize) -> Result<Self, LocalError> {
+    let (full_width, full_height) = (usize::from(image.width()?), usize::from(image.height()?));
+    if width == 0 || height == 0 {
+      return Err("A crop window needs at least one pixel".into());
+    }
+    let outside = |start: usize, length: usize, full: usize| start.checked_add(length).map_or(true, |end| end > full);
+    if outside(left, width, full_width) || outside(top, height, full_height) {
+      return Err(
+        format!(
+          "A {}x{} crop window at {}, {} does not fit in a {}x{} image",
+          width, height, left, top, full_width, full_height
+        )
+        .into(),
+      );
+    }
+    Ok(Self {
+      full_width,
+      full_height,
+      left,
+      top,
+      width,
+      height,
+    })
+  }
+  /// The window covering all of `image`.
+  pub fn new_full(image: Image) -> Result<Self, LocalError> {
+    let (width, height) = (usize::from(image.width()?), usize::from(image.height()?));
+    Ok(Self {
+      full_width: width,
+      full_height: height,
+      left: 0,
+      top: 0,
+      width,
+      height,
+    })
+  }
+  pub const fn full_width(&self) -> usize {
+    self.full_width
+  }
+  pub const fn full_height(&self) -> usize {
+    self.full_height
+  }
+  pub const fn left(&self) -> usize {
+    self.left
+  }
+  pub const fn top(&self) -> usize {
+    self.top
+  }
+  pub const fn width(&self) -> usize {
+    self.width
+  }
+  pub const fn height(&self) -> usize {
+    self.height
+  }
+  pub const fn is_full(&self) -> bool {
+    self.width == self.full_width && self.height == self.full_height
+  }
+  /// Whether the pixel at `column`, `row` of the full image lies inside the window.
+  pub const fn contains(&self, column: usize, row: usize) -> bool {
+    column >= self.left && column < self.left + self.width && row >= self.top && row < self.top + self.height
+  }
+  /// The full-image coordinates of every pixel in the window, row by row from the top.
+  pub fn pixels(self) -> impl Iterator<Item = (usize, usize)> {
+    (self.top..self.top + self.height)
+      .flat_map(move |row| (self.left..self.left + self.width).map(move |column| (column, row)))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::newtypes::dimension::Dimension;
+
+  use super::{AspectRatios, CropWindow, Image};
+
+  fn size(image: Image) -> (usize, usize) {
+    (usize::from(image.width().unwrap()), usize::from(image.height().unwrap()))
+  }
+
+  #[test]
+  fn presets_derive_the_other_side() {
+    let from_width = |ratio, width| size(Image::new_from_width(ratio, Dimension::from(width)));
+    assert_eq!(from_width(AspectRatios::SixteenByNine, 256), (256, 144));
+    assert_eq!(from_width(AspectRatios::OneByOne, 300), (300, 300));
+    assert_eq!(from_width(AspectRatios::FourByThree, 640), (640, 480));
+    assert_eq!(from_width(AspectRatios::ThreeByTwo, 600), (600, 400));
+    assert_eq!(from_width(AspectRatios::TwentyOneByNine, 2560), (2560, 1097));
+    assert_eq!(from_width(AspectRatios::Scope, 2048), (2048, 857));
+    let from_height = |ratio, height| size(Image::new_from_height(ratio, Dimension::from(height)));
+    assert_eq!(from_height(AspectRatios::SixteenByNine, 1080), (1920, 1080));
+    assert_eq!(from_height(AspectRatios::Scope, 100), (239, 100));
+  }
+  #[test]
+  fn rounding_is_to_nearest_with_halves_up() {
+    let ratio = AspectRatios::new_custom(2, 1).unwrap();
+    let heights: Vec<usize> = (1..=5)
+      .map(|width| usize::from(Image::new_from_width(ratio, Dimension::from(width)).height().unwrap()))
+      .collect();
+    assert_eq!(heights, vec![1, 1, 2, 2, 3]);
+    // The exact height is 562.5, which converting a float quotient would truncate to 562.
+    let image = Image::new_from_width(AspectRatios::SixteenByNine, Dimension::from(1000));
+    assert_eq!(usize::from(image.height().unwrap()), 563);
+    let thin = Image::new_from_width(AspectRatios::new_custom(1000, 1).unwrap(), Dimension::from(10));
+    assert_eq!(usize::from(thin.height().unwrap()), 1);
+  }
+  #[test]
+  fn custom_ratios_reduce_and_reject_zero() {
+    assert_eq!(AspectRatios::new_custom(1920, 1080).unwrap().parts(), (16, 9));
+    assert!(AspectRatios::new_custom(0, 9).is_err());
+    let image = Image::new(Dimension::from(1000), Dimension::from(600)).unwrap();
+    assert_eq!(size(image), (1000, 600));
+    assert_eq!(image.aspect_ratio().parts(), (5, 3));
+    assert!(Image::new(Dimension::from(0), Dimension::from(10)).is_err());
+  }
+  #[test]
+  fn stretched_pixels_change_the_pixel_count() {
+    let anamorphic = AspectRatios::new_custom(2, 1).unwrap();
+    let image = Image::new_from_width(AspectRatios::Scope, Dimension::from(1024)).with_pixel_aspect_ratio(anamorphic);
+    assert_eq!(size(image), (1024, 857));
+    let image = Image::new_from_height(AspectRatios::Scope, Dimension::from(857)).with_pixel_aspect_ratio(anamorphic);
+    assert_eq!(size(image), (1024, 857));
+    let exact = Image::new(Dimension::from(960), Dimension::from(540))
+      .unwrap()
+      .with_pixel_aspect_ratio(AspectRatios::new_custom(4, 3).unwrap());
+    assert_eq!(size(exact), (960, 540));
+    assert_eq!(exact.aspect_ratio().parts(), (64, 27));
+  }
+  #[test]
+  fn sides_too_large_for_a_usize_are_errors() {
+    let tall = AspectRatios::new_custom(1, u32::MAX).unwrap();
+    let image = Image::new_from_width(tall, Dimension::from(usize::MAX));
+    assert!(image.height().is_err());
+    assert!(CropWindow::new_full(image).is_err());
+    let image = Image::new_from_height(AspectRatios::new_custom(u32::MAX, 1).unwrap(), Dimension::from(usize::MAX));
+    assert!(image.width().is_err());
+    assert_eq!(Image::default().pixel_aspect_ratio(), AspectRatios::OneByOne);
+  }
+  #[test]
+  fn crop_windows_must_fit_inside_the_image() {
+    let image = Image::new(Dimension::from(8), Dimension::from(6)).unwrap();
+    assert!(CropWindow::new(image, 0, 0, 0, 2).is_err());
+    assert!(CropWindow::new(image, 5, 0, 4, 2).is_err());
+    assert!(CropWindow::new(image, 0, 5, 2, 2).is_err());
+    assert!(CropWindow::new(image, usize::MAX, 0, 1, 1).is_err());
+    assert!(CropWindow::new(image, 0, usize::MAX, 1, 1).is_err());
+    assert!(CropWindow::new_full(image).unwrap().is_full());
+    let crop = CropWindow::new(image, 6, 4, 2, 2).unwrap();
+    assert!(!crop.is_full());
+    assert!(crop.contains(7, 5) && !crop.contains(5, 5) && !crop.contains(7, 3));
+    assert_eq!(crop.pixels().collect::<Vec<_>>(), vec![(6, 4), (7, 4), (6, 5), (7, 5)]);
+  }
+}
@@ -0,0 +1,275 @@
+use crate::error::LocalError;
+
+const MAXIMUM_CODE_LENGTH: usize = 15;
+const LENGTH_BASES: [u16; 29] = [
+  3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+  0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [u16; 30] = [
+  1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193,
+  12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+  0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+const END_OF_BLOCK: u16 = 256;
+
+/// Reads a DEFLATE stream least significant bit first.
+struct BitReader<'a> {
+  bytes: &'a [u8],
+  bit_position: usize,
+}
+
+impl<'a> BitReader<'a> {
+  fn bits(&mut self, count: u8) -> Result<u32, LocalError> {
+    let mut value = 0;
+    for index in 0..count {
+      let byte = self
+        .bytes
+        .get(self.bit_position / 8)
+        .ok_or("The compressed data ends early")?;
+      value |= u32::from((byte >> (self.bit_position % 8)) & 1) << index;
+      self.bit_position += 1;
+    }
+    Ok(value)
+  }
+  fn align_to_byte(&mut self) {
+    self.bit_position = (self.bit_position + 7) / 8 * 8;
+  }
+  fn byte_position(&self) -> usize {
+    self.bit_position / 8
+  }
+}
+
+/// A canonical Huffman code, decoded one bit at a time from the code lengths alone.
+struct Huffman {
+  counts: [u16; MAXIMUM_CODE_LENGTH + 1],
+  symbols: Vec<u16>,
+}
+
+impl Huffman {
+  #[allow(clippy::cast_possible_truncation)]
+  fn new(lengths: &[u8]) -> Self {
+    let mut counts = [0; MAXIMUM_CODE_LENGTH + 1];
+    for length in lengths {
+      counts[usize::from(*length)] += 1;
+    }
+    counts[0] = 0;
+    let mut symbols = Vec::with_capacity(lengths.len());
+    for length in 1..=MAXIMUM_CODE_LENGTH {
+      symbols.extend(
+        lengths
+          .iter()
+          .enumerate()
+          .filter(|(_, symbol_length)| usize::from(**symbol_length) == length)
+          .map(|(symbol, _)| symbol as u16),
+      );
+    }
+    Self { counts, symbols }
+  }
+  fn decode(&self, reader: &mut BitReader<'_>) -> Result<u16, LocalError> {
+    let mut code: i32 = 0;
+    let mut first: i32 = 0;
+    let mut index: i32 = 0;
+    for count in self.counts.iter().skip(1) {
+      code |= i32::try_from(reader.bits(1)?)?;
+      let count = i32::from(*count);
+      if code - first < count {
+        return Ok(self.symbols[usize::try_from(index + code - first)?]);
+      }
+      index += count;
+      first = (first + count) << 1;
+      code <<= 1;
+    }
+    Err("The compressed data has an invalid Huffman code".into())
+  }
+}
+
+fn fixed_codes() -> (Huffman, Huffman) {
+  let mut lengths = [0; 288];
+  for (symbol, length) in lengths.iter_mut().enumerate() {
+    *length = match symbol {
+      0..=143 | 280..=287 => 8,
+      144..=255 => 9,
+      _ => 7,
+    };
+  }
+  (Huffman::new(&lengths), Huffman::new(&[5; 30]))
+}
+
+fn dynamic_codes(reader: &mut BitReader<'_>) -> Result<(Huffman, Huffman), LocalError> {
+  let literal_count = usize::try_from(reader.bits(5)?)? + 257;
+  let distance_count = usize::try_from(reader.bits(5)?)? + 1;
+  let code_length_count = usize::try_from(reader.bits(4)?)? + 4;
+  let mut code_length_lengths = [0; 19];
+  for position in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+    code_length_lengths[*position] = u8::try_from(reader.bits(3)?)?;
+  }
+  let code_length_code = Huffman::new(&code_length_lengths);
+  let mut lengths = Vec::with_capacity(literal_count + distance_count);
+  while lengths.len() < literal_count + distance_count {
+    let (value, repeat) = match code_length_code.decode(reader)? {
+      symbol @ 0..=15 => (u8::try_from(symbol)?, 1),
+      16 => (
+        *lengths.last().ok_or("A repeated code length has nothing to repeat")?,
+        3 + reader.bits(2)?,
+      ),
+      17 => (0, 3 + reader.bits(3)?),
+      _ => (0, 11 + reader.bits(7)?),
+    };
+    lengths.extend(std::iter::repeat(value).take(usize::try_from(repeat)?));
+  }
+  if lengths.len() != literal_count + distance_count {
+    return Err("The compressed data repeats code lengths past the end of the table".into());
+  }
+  Ok((
+    Huffman::new(&lengths[..literal_count]),
+    Huffman::new(&lengths[literal_count..]),
+  ))
+}
+
+fn inflate_block(
+  reader: &mut BitReader<'_>,
+  output: &mut Vec<u8>,
+  literals: &Huffman,
+  distances: &Huffman,
+) -> Result<(), LocalError> {
+  loop {
+    let symbol = literals.decode(reader)?;
+    match symbol {
+      0..=255 => output.push(u8::try_from(symbol)?),
+      END_OF_BLOCK => return Ok(()),
+      _ => {
+        let index = usize::from(symbol - 257);
+        let base = *LENGTH_BASES
+          .get(index)
+          .ok_or("The compressed data has an invalid length code")?;
+        let length = usize::from(base) + usize::try_from(reader.bits(LENGTH_EXTRA_BITS[index])?)?;
+        let index = usize::from(distances.decode(reader)?);
+        let base = *DISTANCE_BASES
+          .get(index)
+          .ok_or("The compressed data has an invalid distance code")?;
+        let distance = usize::from(base) + usize::try_from(reader.bits(DISTANCE_EXTRA_BITS[index])?)?;
+        if distance > output.len() {
+          return Err("The compressed data refers back past its start".into());
+        }
+        let start = output.len() - distance;
+        for offset in 0..length {
+          output.push(output[start + offset]);
+        }
+      }
+    }
+  }
+}
+
+/// Decompresses a raw DEFLATE stream (RFC 1951).
+pub fn inflate(bytes: &[u8]) -> Result<Vec<u8>, LocalError> {
+  let mut reader = BitReader { bytes, bit_position: 0 };
+  let mut output = Vec::new();
+  loop {
+    let is_final = reader.bits(1)? == 1;
+    match reader.bits(2)? {
+      0 => {
+        reader.align_to_byte();
+        let start = reader.byte_position();
+        let stored_header = bytes.get(start..start + 4).ok_or("The compressed data ends early")?;
+        let length = usize::from(u16::from_le_bytes([stored_header[0], stored_header[1]]));
+        if u16::from_le_bytes([stored_header[0], stored_header[1]])
+          != !u16::from_le_bytes([stored_header[2], stored_header[3]])
+        {
+          return Err("A stored block's length does not match its complement".into());
+        }
+        output.extend_from_slice(
+          bytes
+            .get(start + 4..start + 4 + length)
+            .ok_or("The compressed data ends early")?,
+        );
+        reader.bit_position = (start + 4 + length) * 8;
+      }
+      1 => {
+        let (literals, distances) = fixed_codes();
+        inflate_block(&mut reader, &mut output, &literals, &distances)?;
+      }
+      2 => {
+        let (literals, distances) = dynamic_codes(&mut reader)?;
+        inflate_block(&mut reader, &mut output, &literals, &distances)?;
+      }
+      _ => return Err("The compressed data uses a reserved block type".into()),
+    }
+    if is_final {
+      return Ok(output);
+    }
+  }
+}
+
+/// Decompresses a zlib stream (RFC 1950), checking its header and Adler-32 checksum.
+pub fn zlib_decompress(bytes: &[u8]) -> Result<Vec<u8>, LocalError> {
+  if bytes.len() < 6 {
+    return Err("The zlib stream is too short".into());
+  }
+  let (method, flags) = (bytes[0], bytes[1]);
+  if method & 0x0f != 8 || (u16::from(method) << 8 | u16::from(flags)) % 31 != 0 {
+    return Err("The zlib header is not valid".into());
+  }
+  if flags & 0x20 != 0 {
+    return Err("Preset zlib dictionaries are not supported".into());
+  }
+  let output = inflate(&bytes[2..])?;
+  let trailer = &bytes[bytes.len() - 4..];
+  let expected = u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+  if adler32(&output) != expected {
+    return Err("The zlib checksum does not match the data".into());
+  }
+  Ok(output)
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+  const MODULUS: u32 = 65_521;
+  let (low, high) = bytes.iter().fold((1_u32, 0_u32), |(low, high), byte| {
+    let low = (low + u32::from(*byte)) % MODULUS;
+    (low, (high + low) % MODULUS)
+  });
+  high << 16 | low
+}
+
+#[cfg(test)]
+mod test {
+  use super::{inflate, zlib_decompress};
+
+  #[test]
+  fn inflates_stored_blocks() {
+    let bytes = [0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'];
+    assert_eq!(inflate(&bytes).expect("This is a valid stored block"), b"abc");
+    assert!(inflate(&[0x01, 0x03, 0x00, 0xfc, 0xfe, b'a', b'b', b'c']).is_err());
+  }
+  #[test]
+  fn inflates_fixed_huffman_blocks() {
+    let bytes = [120, 156, 203, 72, 205, 201, 201, 87, 200, 64, 39, 1, 104, 3, 8, 177];
+    assert_eq!(
+      zlib_decompress(&bytes).expect("This is a valid zlib stream"),
+      b"hello hello hello hello"
+    );
+  }
+  #[test]
+  fn inflates_dynamic_huffman_blocks() {
+    let bytes = [
+      120, 218, 61, 79, 137, 17, 4, 49, 8, 106, 133, 214, 0, 237, 191, 133, 3, 179, 115, 102, 242, 41, 2, 146, 226, 206,
+      154, 92, 218, 28, 49, 137, 132, 179, 192, 217, 254, 146, 116, 211, 130, 65, 105, 225, 7, 150, 56, 32, 97, 109, 18, 10,
+      6, 215, 10, 169, 45, 200, 55, 72, 5, 176, 114, 218, 167, 213, 71, 63, 165, 99, 25, 114, 148, 116, 180, 155, 167, 195,
+      82, 161, 10, 222, 158, 136, 97, 202, 116, 21, 126, 93, 254, 88, 10, 84, 145, 49, 36, 212, 196, 195, 28, 184, 190, 190,
+      144, 98, 74, 213, 172, 202, 77, 119, 87, 43, 250, 79, 237, 55, 57, 207, 168, 157, 81, 191, 66, 252, 69, 255, 7, 246,
+      92, 109, 223,
+    ];
+    let output = zlib_decompress(&bytes).expect("The checksum confirms the whole stream");
+    assert_eq!(output.len(), 300);
+    assert!(output.starts_with(b"aabaedecaaeacc"));
+  }
+  #[test]
+  fn rejects_corrupt_checksums() {
+    let bytes = [120, 156, 203, 72, 205, 201, 201, 87, 200, 64, 39, 1, 104, 3, 8, 178];
+    assert!(zlib_decompress(&bytes).is_err());
+  }
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  materials::Material,
+  ray::Ray,
+  transform::{AnimatedTransform, Transform},
+};
+
+/// Carries a record found in object space, including its shading frame, back onto the world `ray`.
+fn transformed_record(transform: &Transform, ray: &Ray, record: HitRecord) -> HitRecord {
+  let outward_normal = transform.apply_to_normal(record.outward_normal());
+  let outward_shading_normal = transform.apply_to_normal(record.outward_shading_normal());
+  let tangent = transform.apply_to_direction(record.tangent());
+  let bitangent = transform.apply_to_direction(record.bitangent());
+  let [along_u, along_v] = record
+    .surface_derivatives()
+    .map(|derivative| transform.apply_to_direction(derivative));
+  record
+    .reoriented(ray, outward_normal)
+    .with_shading_frame(outward_shading_normal, tangent, bitangent)
+    .with_surface_derivatives(along_u, along_v)
+}
+
+/// Places a shared object in the world through a transform, without copying the object.
+#[derive(Debug, Clone)]
+pub struct Instance {
+  object: Arc<dyn Hittable>,
+  transform: Transform,
+  material: Option<Arc<dyn Material>>,
+  bounding_box: Option<Aabb>,
+}
+
+impl Instance {
+  pub fn new(object: Arc<dyn Hittable>, transform: Transform) -> Self {
+    let bounding_box = object
+      .bounding_box()
+      .map(|bounding_box| transform.apply_to_bounding_box(&bounding_box));
+    Self {
+      object,
+      transform,
+      material: None,
+      bounding_box,
+    }
+  }
+  /// Gives hits on the object `material` wherever the object itself does not carry one.
+  pub fn new_with_material(object: Arc<dyn Hittable>, transform: Transform, material: Arc<dyn Material>) -> Self {
+    Self {
+      material: Some(material),
+      ..Self::new(object, transform)
+    }
+  }
+  pub fn object(&self) -> &Arc<dyn Hittable> {
+    &self.object
+  }
+  pub const fn transform(&self) -> Transform {
+    self.transform
+  }
+  pub fn material(&self) -> Option<&Arc<dyn Material>> {
+    self.material.as_ref()
+  }
+}
+
+impl Hittable for Instance {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let object_ray = self.transform.inverse().apply_to_ray(ray);
+    let record = self.object.hit(&object_ray, minimum_distance, maximum_distance)?;
+    let record = transformed_record(&self.transform, ray, record);
+    Some(match &self.material {
+      Some(material) => record.with_default_material(material),
+      None => record,
+    })
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.bounding_box
+  }
+}
+
+/// An instance whose transform follows an animation, evaluated at the time carried by each ray.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone)]
+pub struct AnimatedInstance {
+  object: Arc<dyn Hittable>,
+  animation: AnimatedTransform,
+  material: Option<Arc<dyn Material>>,
+  bounding_box: Option<Aabb>,
+}
+
+impl AnimatedInstance {
+  pub fn new(object: Arc<dyn Hittable>, animation: AnimatedTransform) -> Self {
+    let bounding_box = object
+      .bounding_box()
+      .map(|bounding_box| animation.apply_to_bounding_box(&bounding_box));
+    Self {
+      object,
+      animation,
+      material: None,
+      bounding_box,
+    }
+  }
+  pub fn new_with_material(object: Arc<dyn Hittable>, animation: AnimatedTransform, material: Arc<dyn Material>) -> Self {
+    Self {
+      material: Some(material),
+      ..Self::new(object, animation)
+    }
+  }
+  pub fn object(&self) -> &Arc<dyn Hittable> {
+    &self.object
+  }
+  pub const fn animation(&self) -> AnimatedTransform {
+    self.animation
+  }
+  pub fn material(&self) -> Option<&Arc<dyn Material>> {
+    self.material.as_ref()
+  }
+}
+
+impl Hittable for AnimatedInstance {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let transform = self.animation.at(ray.time());
+    let object_ray = transform.inverse().apply_to_ray(ray);
+    let record = self.object.hit(&object_ray, minimum_distance, maximum_distance)?;
+    let record = transformed_record(&transform, ray, record);
+    Some(match &self.material {
+      Some(material) => record.with_default_material(material),
+      None => record,
+    })
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.bounding_box
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+    shapes::sphere::Sphere,
+    transform::{AnimatedTransform, Pose, Transform},
+    vec3::Vec3,
+  };
+
+  use super::{AnimatedInstance, Instance};
+
+  fn unit_sphere() -> Arc<Sphere> {
+    Arc::new(Sphere::new_const(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Distance::try_from_const(1.0).unwrap(),
+    ))
+  }
+
+  #[test]
+  fn instances_share_one_object() {
+    let sphere = unit_sphere();
+    let left = Instance::new(
+      sphere.clone(),
+      Transform::translation(Direction::from_f32_array_const([-3.0, 0.0, 0.0])),
+    );
+    let right = Instance::new(
+      sphere.clone(),
+      Transform::translation(Direction::from_f32_array_const([3.0, 0.0, 0.0])),
+    );
+    assert_eq!(Arc::strong_count(&sphere), 3);
+    let ray = Ray::new(
+      Point::from_f32_array_const([-3.0, 0.0, 5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = left.hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the left sphere");
+    assert!((record.distance() - 4.0).abs() < 1e-5);
+    assert!((record.point().x() + 3.0).abs() < 1e-5);
+    assert!(right.hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+  #[test]
+  fn scaled_instance_has_world_space_normals_and_bounds() {
+    let instance = Instance::new(unit_sphere(), Transform::scale(Vec3::new([2.0, 1.0, 1.0])).unwrap());
+    let ray = Ray::new(
+      Point::from_f32_array_const([5.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([-1.0, 0.0, 0.0]),
+    );
+    let record = instance
+      .hit(&ray, 0.0, f32::INFINITY)
+      .expect("The ray points at the ellipsoid");
+    assert!((record.distance() - 3.0).abs() < 1e-5);
+    assert!((record.normal().x() - 1.0).abs() < 1e-5);
+    let bounding_box = instance.bounding_box().expect("A sphere is bounded");
+    assert!((bounding_box.maximum().x() - 2.0).abs() < 1e-5);
+    assert!((bounding_box.maximum().y() - 1.0).abs() < 1e-5);
+  }
+  #[test]
+  fn animated_instance_moves_with_ray_time() {
+    let end = Pose::new(
+      Direction::from_f32_array_const([4.0, 0.0, 0.0]),
+      Pose::default().rotation(),
+      Vec3::new([1.0, 1.0, 1.0]),
+    );
+    let animation = AnimatedTransform::new(Pose::default(), end, 0.0, 1.0).unwrap();
+    let instance = AnimatedInstance::new(unit_sphere(), animation);
+    let origin = Point::from_f32_array_const([4.0, 0.0, 5.0]);
+    let direction = Direction::from_f32_array_const([0.0, 0.0, -1.0]);
+    assert!(instance
+      .hit(&Ray::new_at_time(origin, direction, 0.0), 0.0, f32::INFINITY)
+      .is_none());
+    assert!(instance
+      .hit(&Ray::new_at_time(origin, direction, 1.0), 0.0, f32::INFINITY)
+      .is_some());
+    let bounding_box = instance.bounding_box().expect("A sphere is bounded");
+    assert!(bounding_box.minimum().x() <= -1.0);
+    assert!(bounding_box.maximum().x() >= 5.0);
+  }
+}
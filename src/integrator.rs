@@ -0,0 +1,278 @@
+use crate::{
+  color_space::ColorSpace,
+  environment::Environment,
+  hittable::{HitRecord, Hittable},
+  materials::Specular,
+  newtypes::{direction::Direction, radiance::Radiance},
+  random::Random,
+  ray::{Ray, RayDifferentials},
+  spectrum::{sample_wavelength, spectral_sample_to_rgb, upsample_rgb},
+};
+
+/// Keeps scattered rays from hitting the surface they start on because of rounding.
+const SURFACE_EPSILON: f32 = 0.001;
+
+/// Follows the auxiliary rays of `differentials` through a specular bounce at `record`, treating the surface as flat
+/// around the hit. Returns `None` when either auxiliary ray misses the tangent plane or cannot refract.
+fn specular_differentials(
+  differentials: &RayDifferentials,
+  record: &HitRecord,
+  specular: Specular,
+) -> Option<RayDifferentials> {
+  let (x_origin, y_origin) = record.tangent_plane_offsets(differentials)?;
+  let normal = record.shading_normal();
+  let bend = |direction: Direction| {
+    let unit_direction = direction.unit_direction();
+    match specular {
+      Specular::Reflection => Some(unit_direction.reflect(normal)),
+      Specular::Transmission(eta_ratio) => {
+        let cos_theta = (-unit_direction).dot(normal).min(1.0);
+        if eta_ratio * eta_ratio * (1.0 - cos_theta * cos_theta) > 1.0 {
+          return None;
+        }
+        Some(unit_direction.refract(normal, eta_ratio))
+      }
+    }
+  };
+  Some(RayDifferentials::new(
+    x_origin,
+    bend(differentials.x_direction())?,
+    y_origin,
+    bend(differentials.y_direction())?,
+  ))
+}
+
+/// Estimates the light arriving along `ray` by following one scattering path for at most `maximum_depth` bounces.
+/// Ray differentials size texture footprints and survive specular bounces; diffuse bounces drop them.
+pub fn ray_color(
+  ray: &Ray,
+  world: &dyn Hittable,
+  environment: &Environment,
+  maximum_depth: u32,
+  random: &mut Random,
+) -> Radiance {
+  trace(ray, world, environment, maximum_depth, random, |color| color)
+}
+
+/// Estimates the same light as [`ray_color`] by following a single wavelength along the path. Colors of materials,
+/// lights and the sky, given in `working_space`, are upsampled to spectra, and the result is converted through CIE XYZ
+/// back to `working_space`. Dispersive materials bend each wavelength differently.
+pub fn spectral_ray_color(
+  ray: &Ray,
+  world: &dyn Hittable,
+  environment: &Environment,
+  working_space: ColorSpace,
+  maximum_depth: u32,
+  random: &mut Random,
+) -> Radiance {
+  let (wavelength, pdf) = sample_wavelength(random);
+  let ray = ray.with_wavelength(wavelength);
+  let radiance = trace(&ray, world, environment, maximum_depth, random, |color| {
+    let color = working_space.convert(color.as_vec3(), ColorSpace::LinearRec709);
+    Radiance::new([upsample_rgb(color, wavelength); 3])
+  });
+  Radiance::from(spectral_sample_to_rgb(radiance.red(), wavelength, pdf, working_space))
+}
+
+/// The path tracing loop, with every color passed through `to_radiance` before it is used.
+fn trace(
+  ray: &Ray,
+  world: &dyn Hittable,
+  environment: &Environment,
+  maximum_depth: u32,
+  random: &mut Random,
+  to_radiance: impl Fn(Radiance) -> Radiance,
+) -> Radiance {
+  let mut ray = *ray;
+  let mut throughput = Radiance::new([1.0, 1.0, 1.0]);
+  let mut radiance = Radiance::default();
+  for _ in 0..maximum_depth {
+    let surface = world.hit(&ray, SURFACE_EPSILON, f32::INFINITY);
+    let surface_distance = surface.as_ref().map_or(f32::INFINITY, HitRecord::distance);
+    let record = environment
+      .fog()
+      .and_then(|fog| fog.hit(&ray, SURFACE_EPSILON, surface_distance))
+      .or(surface);
+    let record = match (record, ray.differentials()) {
+      (Some(record), Some(differentials)) => record.with_footprint_from(&differentials),
+      (Some(record), None) => record,
+      (None, _) => return radiance + throughput * to_radiance(environment.background(&ray)),
+    };
+    let material = match record.material() {
+      Some(material) => material,
+      None => return radiance,
+    };
+    radiance += throughput * to_radiance(material.emitted(&ray, &record));
+    let scatter = match material.scatter(&ray, &record, random) {
+      Some(scatter) => scatter,
+      None => return radiance,
+    };
+    throughput *= to_radiance(scatter.attenuation());
+    let followed = ray
+      .differentials()
+      .zip(scatter.specular())
+      .and_then(|(differentials, specular)| specular_differentials(&differentials, &record, specular));
+    let next = match followed {
+      Some(differentials) => scatter.ray().with_differentials(differentials),
+      None => scatter.ray(),
+    };
+    ray = match ray.wavelength() {
+      Some(wavelength) => next.with_wavelength(wavelength),
+      None => next,
+    };
+  }
+  radiance
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    color_space::ColorSpace,
+    environment::{Environment, Fog},
+    hittable::Hittable,
+    hittable_list::HittableList,
+    materials::Specular,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    phase_function::PhaseFunction,
+    random::Random,
+    ray::{Ray, RayDifferentials},
+    shapes::quad::Quad,
+    vec3::Vec3,
+  };
+
+  use super::{ray_color, spectral_ray_color, specular_differentials};
+
+  fn ray() -> Ray {
+    Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    )
+  }
+
+  #[test]
+  fn empty_world_shows_the_background() {
+    let environment = Environment::default();
+    let color = ray_color(&ray(), &HittableList::new(), &environment, 8, &mut Random::new(1));
+    assert_eq!(color, environment.background(&ray()));
+  }
+  #[test]
+  fn white_fog_under_a_white_sky_conserves_energy() {
+    let white = Vec3::new([1.0, 1.0, 1.0]);
+    let phase = PhaseFunction::new_henyey_greenstein(0.4).unwrap();
+    let fog = Fog::new(0.5, white, phase, Distance::try_from_const(4.0).unwrap()).unwrap();
+    let environment = Environment::new(white, white).with_fog(fog);
+    let mut random = Random::new(2);
+    for sample in 0..200_u16 {
+      let direction = random.unit_direction();
+      let ray = Ray::new_at_time(ray().origin(), direction, f32::from(sample));
+      let color = ray_color(&ray, &HittableList::new(), &environment, 1000, &mut random);
+      assert_eq!(color.as_vec3(), white);
+    }
+  }
+  #[test]
+  fn spectral_paths_agree_with_rgb_on_grey_scenes() {
+    let sky = Vec3::new([0.8, 0.8, 0.8]);
+    let fog = Fog::new(
+      1.0,
+      Vec3::new([0.5, 0.5, 0.5]),
+      PhaseFunction::Isotropic,
+      Distance::try_from_const(4.0).unwrap(),
+    )
+    .unwrap();
+    let environment = Environment::new(sky, sky).with_fog(fog);
+    let mut random = Random::new(4);
+    let samples: u16 = 20_000;
+    let (rgb, spectral) = (0..samples).fold((Vec3::default(), Vec3::default()), |(rgb, spectral), sample| {
+      let ray = Ray::new_at_time(ray().origin(), ray().direction(), f32::from(sample));
+      (
+        rgb + ray_color(&ray, &HittableList::new(), &environment, 1000, &mut random).as_vec3(),
+        spectral
+          + spectral_ray_color(
+            &ray,
+            &HittableList::new(),
+            &environment,
+            ColorSpace::LinearRec709,
+            1000,
+            &mut random,
+          )
+          .as_vec3(),
+      )
+    });
+    let difference = (rgb - spectral) / f32::from(samples);
+    assert!(
+      difference.length().as_f32() < 0.03,
+      "The spectral estimate was off by {}",
+      difference
+    );
+  }
+  #[test]
+  fn spectral_paths_render_the_same_light_in_any_working_space() {
+    let sky = Vec3::new([0.5, 0.2, 0.1]);
+    let sample = |working_space: ColorSpace, seed: u64| {
+      let sky = ColorSpace::LinearRec709.convert(sky, working_space);
+      let environment = Environment::new(sky, sky);
+      let world = HittableList::new();
+      spectral_ray_color(&ray(), &world, &environment, working_space, 10, &mut Random::new(seed)).as_vec3()
+    };
+    for seed in 0..20 {
+      let expected = ColorSpace::LinearRec709.convert(sample(ColorSpace::LinearRec709, seed), ColorSpace::AcesCg);
+      let difference = sample(ColorSpace::AcesCg, seed) - expected;
+      assert!(difference.length().as_f32() < 1e-3, "Seed {} was off by {}", seed, difference);
+    }
+  }
+  #[test]
+  fn grey_fog_darkens_the_sky() {
+    let white = Vec3::new([1.0, 1.0, 1.0]);
+    let fog = Fog::new(
+      2.0,
+      Vec3::new([0.5, 0.5, 0.5]),
+      PhaseFunction::Isotropic,
+      Distance::try_from_const(4.0).unwrap(),
+    )
+    .unwrap();
+    let environment = Environment::new(white, white).with_fog(fog);
+    let mut random = Random::new(3);
+    let samples: u16 = 500;
+    let total: f32 = (0..samples)
+      .map(|sample| {
+        let ray = Ray::new_at_time(ray().origin(), ray().direction(), f32::from(sample));
+        ray_color(&ray, &HittableList::new(), &environment, 1000, &mut random).red()
+      })
+      .sum();
+    assert!(total / f32::from(samples) < 0.9);
+  }
+  #[test]
+  fn mirrors_reflect_the_footprint() {
+    let mirror = Quad::new(
+      Point::from_f32_array_const([-1.0, -1.0, -1.0]),
+      Direction::from_f32_array_const([2.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 2.0, 0.0]),
+    );
+    let differentials = RayDifferentials::new(
+      Point::default(),
+      Direction::from_f32_array_const([0.01, 0.0, -1.0]),
+      Point::default(),
+      Direction::from_f32_array_const([0.0, 0.01, -1.0]),
+    );
+    let record = mirror.hit(&ray(), 0.0, f32::INFINITY).unwrap();
+    let reflected = specular_differentials(&differentials, &record, Specular::Reflection).unwrap();
+    assert!(
+      Direction::from(reflected.x_origin() - Point::from_f32_array_const([0.01, 0.0, -1.0]))
+        .length()
+        .as_f32()
+        < 1e-6
+    );
+    let expected = Direction::from_f32_array_const([0.01, 0.0, 1.0]).unit_direction();
+    assert!((reflected.x_direction() - expected).length().as_f32() < 1e-6);
+    let refracted = specular_differentials(&differentials, &record, Specular::Transmission(1.0)).unwrap();
+    let expected = Direction::from_f32_array_const([0.0, 0.01, -1.0]).unit_direction();
+    assert!((refracted.y_direction() - expected).length().as_f32() < 1e-6);
+    let grazing = RayDifferentials::new(
+      Point::default(),
+      Direction::from_f32_array_const([1.0, 0.0, -0.1]),
+      Point::default(),
+      Direction::from_f32_array_const([0.0, 0.01, -1.0]),
+    );
+    assert!(specular_differentials(&grazing, &record, Specular::Transmission(1.5)).is_none());
+  }
+}
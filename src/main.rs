@@ -66,61 +66,133 @@
 #![warn(clippy::pedantic)]
 #![feature(const_fn_floating_point_arithmetic, const_fn_trait_bound, step_trait)]
 
+mod aabb;
+mod bvh;
 mod camera;
+#[allow(dead_code)]
+mod color_space;
+#[allow(dead_code)]
+mod csg;
+#[allow(dead_code)]
+mod detail_map;
+#[allow(dead_code)]
+mod environment;
 mod error;
+#[allow(dead_code)]
+mod framebuffer;
+mod hittable;
+#[allow(dead_code)]
+mod hittable_list;
+#[allow(dead_code)]
 mod image;
+mod inflate;
+#[allow(dead_code)]
+mod instance;
+#[allow(dead_code)]
+mod integrator;
+#[allow(dead_code)]
+mod materials;
+#[allow(dead_code)]
+mod mesh;
+#[allow(dead_code)]
+mod netpbm;
 mod newtypes;
+mod orthonormal_basis;
+#[allow(dead_code)]
+mod phase_function;
+mod png;
+mod polynomial;
+#[allow(dead_code)]
+mod quaternion;
+#[allow(dead_code)]
+mod random;
 mod ray;
-mod sphere;
+#[allow(dead_code)]
+mod rgb_image;
+#[allow(dead_code)]
+mod scene_graph;
+#[allow(dead_code)]
+mod sdf;
+#[allow(dead_code)]
+mod shapes;
+mod spectrum;
+#[cfg(test)]
+mod test_support;
+#[allow(dead_code)]
+mod textures;
+#[allow(dead_code)]
+mod tone_mapping;
+#[allow(dead_code)]
+mod transform;
 mod vec3;
+#[allow(dead_code)]
+mod volumes;
 
-use std::io::Write;
+use std::{io::Write, sync::Arc};
 
 use newtypes::dimension::Dimension;
 
 use crate::{
   camera::Camera,
-  image::{AspectRatios, Image},
-  newtypes::{direction::Direction, distance::Distance, point::Point},
-  ray::Ray,
-  sphere::Sphere,
+  color_space::ColorSpace,
+  environment::Environment,
+  error::LocalError,
+  framebuffer::{CropOutput, Framebuffer},
+  hittable_list::HittableList,
+  image::{AspectRatios, CropWindow, Image},
+  instance::Instance,
+  integrator::ray_color,
+  materials::{lambertian::Lambertian, Material},
+  newtypes::{distance::Distance, point::Point},
+  random::Random,
+  shapes::sphere::Sphere,
+  tone_mapping::ToneMapping,
+  transform::Transform,
+  vec3::Vec3,
 };
 
 const IMAGE_WIDTH: Dimension = Dimension::from_const(256);
+const SAMPLES_PER_PIXEL: u16 = 16;
+const MAXIMUM_DEPTH: u32 = 16;
+/// How the image written to standard output is tone mapped and encoded.
+const OUTPUT: (ToneMapping, ColorSpace) = (ToneMapping::AcesFitted, ColorSpace::Srgb);
 
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-fn main() {
-  const IMAGE: Image = Image::new_from_width(AspectRatios::SixteenByNine, IMAGE_WIDTH);
+fn diffuse_sphere(center: [f32; 3], radius: f32, albedo: [f32; 3]) -> Instance {
   let sphere = Sphere::new_const(
-    Point::from_f32_array_const([0.0, 0.0, -1.0]),
-    Distance::try_from_const(0.5).unwrap(),
+    Point::from_f32_array_const(center),
+    Distance::try_from_const(radius).expect("Sphere radii are positive and valid"),
   );
+  let material: Arc<dyn Material> = Arc::new(Lambertian::new(Vec3::new(albedo)));
+  Instance::new_with_material(Arc::new(sphere), Transform::identity(), material)
+}
+
+fn main() -> Result<(), LocalError> {
+  const IMAGE: Image = Image::new_from_width(AspectRatios::SixteenByNine, IMAGE_WIDTH);
+  let mut world = HittableList::new();
+  world.add(Arc::new(diffuse_sphere([0.0, 0.0, -1.0], 0.5, [0.7, 0.3, 0.3])));
+  world.add(Arc::new(diffuse_sphere([0.0, -100.5, -1.0], 100.0, [0.8, 0.8, 0.0])));
+  let environment = Environment::new(Vec3::new([1.0, 1.0, 1.0]), Vec3::new([0.5, 0.7, 1.0]));
   let camera: Camera = Camera::new_from_viewport_height(
     IMAGE,
     Dimension::from_const(2),
     Distance::try_from_const(1.0).expect("This is positive and valid"),
     Point::from_f32_array_const([0.0, 0.0, 0.0]),
   );
-  let origin: Point = camera.origin();
-  let horizontal: Direction = camera.horizontal();
-  let vertical: Direction = camera.vertical();
-  let lower_left_corner: Point = camera.lower_left_corner();
-  let image_height = IMAGE.height();
-  print!("P3\n{} {}\n255\n", IMAGE_WIDTH, image_height);
-  for y_dimension in (Dimension::from(0)..image_height).rev() {
-    eprintln!("Scanlines remaining: {}", y_dimension);
-    std::io::stderr().flush().expect("Standard error should flush normally");
-    let v = f32::from(y_dimension) / f32::from(image_height);
-    for x_dimension in Dimension::from(0)..IMAGE_WIDTH {
-      let u = f32::from(x_dimension) / f32::from(IMAGE_WIDTH);
-      let ray = Ray::new(
-        origin,
-        (lower_left_corner + (u * horizontal).into() + (v * vertical).into() - origin).into(),
-      );
-      let color = ray.find_color(&sphere);
-
-      println!("{}", color);
+  let mut random = Random::new(0);
+  let mut framebuffer = Framebuffer::new_cropped(CropWindow::new_full(IMAGE)?);
+  let image_height = usize::from(IMAGE.height()?);
+  for (column, row) in framebuffer.window().pixels() {
+    if column == 0 {
+      eprintln!("Scanlines remaining: {}", image_height - row);
+      std::io::stderr().flush().expect("Standard error should flush normally");
+    }
+    for _ in 0..SAMPLES_PER_PIXEL {
+      let ray = camera.get_pixel_ray(column, row, &mut random)?;
+      framebuffer.add_sample(column, row, ray_color(&ray, &world, &environment, MAXIMUM_DEPTH, &mut random));
     }
   }
+  let (tone_mapping, output) = OUTPUT;
+  framebuffer.write_pixmap(&mut std::io::stdout().lock(), tone_mapping, output, CropOutput::Cropped)?;
   eprintln!("Done");
+  Ok(())
 }
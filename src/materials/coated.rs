@@ -0,0 +1,213 @@
+use std::sync::Arc;
+
+use crate::{
+  error::LocalError,
+  hittable::HitRecord,
+  materials::{dielectric::fresnel, microfacet::Ggx, Material, Scatter, Specular},
+  newtypes::{percent::Strict as StrictPercent, radiance::Radiance},
+  orthonormal_basis::OrthonormalBasis,
+  random::Random,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+/// A clear dielectric layer over any base material, like lacquer or car paint. Light either reflects off the coating's
+/// GGX surface or crosses it, is absorbed on the way down to the base and back up, and loses what the coating reflects
+/// back inside as it leaves. Refraction inside the thin layer is not traced, so the base sees the original ray.
+#[derive(Debug, Clone)]
+pub struct Coated {
+  base: Arc<dyn Material>,
+  index_of_refraction: f32,
+  roughness: StrictPercent,
+  distribution: Ggx,
+  thickness: f32,
+  absorption: Vec3,
+}
+
+impl Coated {
+  /// `absorption` is the coating's absorption coefficient per unit length in each channel, so light crossing it
+  /// straight down and up again keeps `exp(-2 * thickness * absorption)`.
+  pub fn new(
+    base: Arc<dyn Material>,
+    index_of_refraction: f32,
+    roughness: StrictPercent,
+    thickness: f32,
+    absorption: Vec3,
+  ) -> Result<Self, LocalError> {
+    if !(index_of_refraction.is_finite() && index_of_refraction >= 1.0) {
+      return Err("A coating's index of refraction must be finite and at least 1.0".into());
+    }
+    if !(thickness.is_finite() && thickness >= 0.0) {
+      return Err("A coating's thickness must be finite and not negative".into());
+    }
+    if ![absorption.x(), absorption.y(), absorption.z()]
+      .iter()
+      .all(|coefficient| coefficient.is_finite() && *coefficient >= 0.0)
+    {
+      return Err("A coating's absorption must be finite and not negative".into());
+    }
+    Ok(Self {
+      base,
+      index_of_refraction,
+      roughness,
+      distribution: Ggx::new_from_roughness(roughness),
+      thickness,
+      absorption,
+    })
+  }
+  pub fn base(&self) -> &Arc<dyn Material> {
+    &self.base
+  }
+  pub const fn index_of_refraction(&self) -> f32 {
+    self.index_of_refraction
+  }
+  pub const fn roughness(&self) -> StrictPercent {
+    self.roughness
+  }
+  pub const fn thickness(&self) -> f32 {
+    self.thickness
+  }
+  pub const fn absorption(&self) -> Vec3 {
+    self.absorption
+  }
+  /// The cosine to the normal inside the coating of light crossing its top at `cos_outside`.
+  fn cos_inside(&self, cos_outside: f32) -> f32 {
+    let sin_squared = (1.0 - cos_outside * cos_outside) / (self.index_of_refraction * self.index_of_refraction);
+    (1.0 - sin_squared).max(0.0).sqrt()
+  }
+  /// The fraction of light surviving a crossing of the coating at `cos_outside` to the normal.
+  fn transmittance(&self, cos_outside: f32) -> Vec3 {
+    let path_length = self.thickness / self.cos_inside(cos_outside);
+    let optical_depth = self.absorption * path_length;
+    Vec3::new([
+      (-optical_depth.x()).exp(),
+      (-optical_depth.y()).exp(),
+      (-optical_depth.z()).exp(),
+    ])
+  }
+}
+
+impl Material for Coated {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let basis = OrthonormalBasis::new_from_w(record.shading_normal());
+    let outgoing = basis.world_to_local(-ray.direction().unit_direction());
+    if outgoing.z() <= 0.0 {
+      return None;
+    }
+    let microfacet_normal = self.distribution.sample_visible_normal(outgoing, random);
+    let eta_ratio = 1.0 / self.index_of_refraction;
+    if random.next_f32() < fresnel(outgoing.dot(microfacet_normal), eta_ratio) {
+      let incoming = -outgoing.reflect(microfacet_normal);
+      let direction = basis.local_to_world(incoming);
+      if incoming.z() <= 0.0 || direction.dot(record.normal()) <= 0.0 {
+        return None;
+      }
+      let weight = self.distribution.masking_shadowing(outgoing, incoming) / self.distribution.masking(outgoing);
+      let scattered = Ray::new_at_time(record.point(), direction, ray.time());
+      let attenuation = Radiance::new([weight, weight, weight]);
+      if self.roughness.as_f32() > 0.0 {
+        return Some(Scatter::new(attenuation, scattered));
+      }
+      return Some(Scatter::new_specular(attenuation, scattered, Specular::Reflection));
+    }
+    let base_scatter = self.base.scatter(ray, record, random)?;
+    let cos_leaving = base_scatter.ray().direction().unit_direction().dot(record.shading_normal());
+    if cos_leaving <= 0.0 {
+      return None;
+    }
+    let escaping = 1.0 - fresnel(self.cos_inside(cos_leaving), self.index_of_refraction);
+    let attenuation = base_scatter.attenuation()
+      * Radiance::from(self.transmittance(outgoing.z()) * self.transmittance(cos_leaving))
+      * escaping;
+    Some(match base_scatter.specular() {
+      Some(specular) => Scatter::new_specular(attenuation, base_scatter.ray(), specular),
+      None => Scatter::new(attenuation, base_scatter.ray()),
+    })
+  }
+  fn emitted(&self, ray: &Ray, record: &HitRecord) -> Radiance {
+    let cos_viewer = -ray.direction().unit_direction().dot(record.shading_normal());
+    if cos_viewer <= 0.0 {
+      return Radiance::default();
+    }
+    let escaping = 1.0 - fresnel(self.cos_inside(cos_viewer), self.index_of_refraction);
+    self.base.emitted(ray, record) * Radiance::from(self.transmittance(cos_viewer)) * escaping
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::HitRecord,
+    materials::{lambertian::Lambertian, Material},
+    newtypes::{direction::Direction, percent::Strict as StrictPercent, point::Point},
+    random::Random,
+    ray::Ray,
+    test_support::{unit_sphere, white_furnace},
+    vec3::Vec3,
+  };
+
+  use super::Coated;
+
+  fn coat_over(base: Vec3, roughness: f32, absorption: Vec3) -> Coated {
+    Coated::new(
+      Arc::new(Lambertian::new(base)),
+      1.5,
+      StrictPercent::new(roughness).unwrap(),
+      0.1,
+      absorption,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn rejects_unphysical_coatings() {
+    let base = Arc::new(Lambertian::new(Vec3::new([0.5, 0.5, 0.5])));
+    let smooth = StrictPercent::new(0.0).unwrap();
+    assert!(Coated::new(base.clone(), 0.5, smooth, 0.1, Vec3::default()).is_err());
+    assert!(Coated::new(base.clone(), 1.5, smooth, -0.1, Vec3::default()).is_err());
+    assert!(Coated::new(base, 1.5, smooth, 0.1, Vec3::new([0.0, -1.0, 0.0])).is_err());
+  }
+  #[test]
+  fn coating_over_black_reflects_its_fresnel_fraction() {
+    let material = coat_over(Vec3::default(), 0.0, Vec3::default());
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 1.0, 0.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = HitRecord::new(&ray, 1.0, Direction::from_f32_array_const([0.0, 1.0, 0.0]), [0.0, 0.0]);
+    let mut random = Random::new(12);
+    let samples: u16 = 20_000;
+    let reflected: f32 = (0..samples)
+      .filter_map(|_| material.scatter(&ray, &record, &mut random))
+      .map(|scatter| scatter.attenuation().red())
+      .sum();
+    assert!((reflected / f32::from(samples) - 0.04).abs() < 0.005);
+  }
+
+  fn furnace(material: Coated) -> Vec3 {
+    let (albedo, brightest) = white_furnace(&unit_sphere(Arc::new(material)), 4000, 50, 13);
+    assert!(brightest <= 1.0 + 1e-4, "A sample returned {}", brightest);
+    albedo
+  }
+
+  #[test]
+  fn white_furnace_never_gains_energy() {
+    for roughness in [0.0, 0.5] {
+      let albedo = furnace(coat_over(Vec3::new([1.0, 1.0, 1.0]), roughness, Vec3::default())).x();
+      assert!(
+        albedo <= 1.0 + 1e-4 && albedo > 0.85,
+        "roughness {} gave {}",
+        roughness,
+        albedo
+      );
+    }
+  }
+  #[test]
+  fn absorbing_coatings_tint_the_base() {
+    let albedo = furnace(coat_over(Vec3::new([1.0, 1.0, 1.0]), 0.2, Vec3::new([0.0, 2.0, 4.0])));
+    assert!(albedo.x() > albedo.y() && albedo.y() > albedo.z());
+    assert!(albedo.z() > 0.04);
+  }
+}
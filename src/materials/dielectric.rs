@@ -0,0 +1,162 @@
+use crate::{
+  error::LocalError,
+  hittable::HitRecord,
+  materials::{Material, Scatter, Specular},
+  newtypes::radiance::Radiance,
+  random::Random,
+  ray::Ray,
+};
+
+/// The sodium D line where glass indices of refraction are quoted, and the hydrogen F and C lines that bound the Abbe
+/// number's dispersion, in nanometres.
+const SODIUM_D_WAVELENGTH: f32 = 589.3;
+const HYDROGEN_F_WAVELENGTH: f32 = 486.1;
+const HYDROGEN_C_WAVELENGTH: f32 = 656.3;
+
+/// Clear glass. Dispersive glass follows Cauchy's equation, `n = a + b / wavelength^2`, on rays tagged with a
+/// wavelength, and uses its index at the sodium D line otherwise.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Dielectric {
+  index_of_refraction: f32,
+  dispersion: f32,
+}
+
+/// Schlick's approximation of the Fresnel reflectance.
+pub fn reflectance(cosine: f32, eta_ratio: f32) -> f32 {
+  let r0 = ((1.0 - eta_ratio) / (1.0 + eta_ratio)).powi(2);
+  r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
+}
+
+/// The exact unpolarized Fresnel reflectance for light arriving at `cos_incident` to the normal, where `eta_ratio` is the
+/// incident over the transmitted index of refraction. Total internal reflection reflects everything.
+pub fn fresnel(cos_incident: f32, eta_ratio: f32) -> f32 {
+  let cos_incident = cos_incident.clamp(0.0, 1.0);
+  let sin_transmitted_squared = eta_ratio * eta_ratio * (1.0 - cos_incident * cos_incident);
+  if sin_transmitted_squared >= 1.0 {
+    return 1.0;
+  }
+  let cos_transmitted = (1.0 - sin_transmitted_squared).sqrt();
+  let perpendicular = (eta_ratio * cos_incident - cos_transmitted) / (eta_ratio * cos_incident + cos_transmitted);
+  let parallel = (cos_incident - eta_ratio * cos_transmitted) / (cos_incident + eta_ratio * cos_transmitted);
+  0.5 * (perpendicular * perpendicular + parallel * parallel)
+}
+
+impl Dielectric {
+  pub const fn new(index_of_refraction: f32) -> Self {
+    Self {
+      index_of_refraction,
+      dispersion: 0.0,
+    }
+  }
+  /// Glass with `index_of_refraction` at the sodium D line and the given Abbe number; lower Abbe numbers spread colors
+  /// further apart, from about 64 for crown glass to about 30 for dense flint.
+  pub fn new_dispersive(index_of_refraction: f32, abbe_number: f32) -> Result<Self, LocalError> {
+    if !(index_of_refraction.is_finite() && index_of_refraction >= 1.0) {
+      return Err("A dispersive index of refraction must be finite and at least 1.0".into());
+    }
+    if !(abbe_number.is_finite() && abbe_number > 0.0) {
+      return Err("An Abbe number must be positive and finite".into());
+    }
+    let inverse_square = |wavelength: f32| 1.0 / (wavelength * wavelength);
+    let spread = (index_of_refraction - 1.0) / abbe_number;
+    let dispersion = spread / (inverse_square(HYDROGEN_F_WAVELENGTH) - inverse_square(HYDROGEN_C_WAVELENGTH));
+    Ok(Self {
+      index_of_refraction,
+      dispersion,
+    })
+  }
+  pub const fn index_of_refraction(self) -> f32 {
+    self.index_of_refraction
+  }
+  /// Cauchy's `b` coefficient in square nanometres, zero for glass that does not disperse.
+  pub const fn dispersion(self) -> f32 {
+    self.dispersion
+  }
+  /// The index of refraction for light of `wavelength` nanometres, or at the sodium D line without one.
+  pub fn index_at(self, wavelength: Option<f32>) -> f32 {
+    let wavelength = match wavelength {
+      Some(wavelength) => wavelength,
+      None => return self.index_of_refraction,
+    };
+    let inverse_square = |wavelength: f32| 1.0 / (wavelength * wavelength);
+    self.index_of_refraction + self.dispersion * (inverse_square(wavelength) - inverse_square(SODIUM_D_WAVELENGTH))
+  }
+}
+
+impl Material for Dielectric {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let index_of_refraction = self.index_at(ray.wavelength());
+    let eta_ratio = if record.front_face() {
+      1.0 / index_of_refraction
+    } else {
+      index_of_refraction
+    };
+    let unit_direction = ray.direction().unit_direction();
+    let cos_theta = (-unit_direction).dot(record.shading_normal()).min(1.0);
+    let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+    let cannot_refract = eta_ratio * sin_theta > 1.0;
+    let (direction, specular) = if cannot_refract || reflectance(cos_theta, eta_ratio) > random.next_f32() {
+      (unit_direction.reflect(record.shading_normal()), Specular::Reflection)
+    } else {
+      (
+        unit_direction.refract(record.shading_normal(), eta_ratio),
+        Specular::Transmission(eta_ratio),
+      )
+    };
+    Some(Scatter::new_specular(
+      Radiance::new([1.0, 1.0, 1.0]),
+      Ray::new_at_time(record.point(), direction, ray.time()),
+      specular,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::HitRecord,
+    materials::Material,
+    newtypes::{direction::Direction, point::Point},
+    random::Random,
+    ray::Ray,
+  };
+
+  use super::{fresnel, reflectance, Dielectric};
+
+  #[test]
+  fn exact_fresnel_matches_schlick_head_on_and_reflects_totally() {
+    assert!((fresnel(1.0, 1.0 / 1.5) - reflectance(1.0, 1.0 / 1.5)).abs() < 1e-6);
+    assert!((fresnel(1.0, 1.5) - 0.04).abs() < 1e-6);
+    assert!(fresnel(0.0, 1.0 / 1.5) > 0.999);
+    assert!(fresnel(0.5, 1.5).eq(&1.0));
+    // Light crossing the boundary either way at matching angles reflects the same fraction.
+    let cos_outside: f32 = 0.6;
+    let cos_inside = (1.0 - (1.0 - cos_outside * cos_outside) / (1.5 * 1.5)).sqrt();
+    assert!((fresnel(cos_outside, 1.0 / 1.5) - fresnel(cos_inside, 1.5)).abs() < 1e-5);
+  }
+  #[test]
+  fn dispersive_glass_bends_blue_more_than_red() {
+    assert!(Dielectric::new_dispersive(1.5, 0.0).is_err());
+    let glass = Dielectric::new_dispersive(1.6, 36.0).unwrap();
+    assert!((glass.index_at(Some(589.3)) - 1.6).abs() < 1e-6);
+    assert!((glass.index_at(None) - 1.6).abs() < 1e-6);
+    let spread = glass.index_at(Some(486.1)) - glass.index_at(Some(656.3));
+    assert!((spread - 0.6 / 36.0).abs() < 1e-5);
+    let ray = Ray::new(
+      Point::from_f32_array_const([-1.0, 1.0, 0.0]),
+      Direction::from_f32_array_const([1.0, -1.0, 0.0]),
+    );
+    let record = HitRecord::new(&ray, 1.0, Direction::from_f32_array_const([0.0, 1.0, 0.0]), [0.0, 0.0]);
+    // Keeps the refracted sample for each wavelength, skipping the occasional Fresnel reflection.
+    let refracted_x = |wavelength: f32| {
+      let mut random = Random::new(15);
+      (0..100)
+        .filter_map(|_| glass.scatter(&ray.with_wavelength(wavelength), &record, &mut random))
+        .map(|scatter| scatter.ray().direction())
+        .find(|direction| direction.y() < 0.0)
+        .expect("Most rays refract")
+        .x()
+    };
+    assert!(refracted_x(450.0) < refracted_x(650.0));
+  }
+}
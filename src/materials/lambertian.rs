@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::{
+  hittable::HitRecord,
+  materials::{Material, Scatter},
+  newtypes::radiance::Radiance,
+  random::Random,
+  ray::Ray,
+  textures::{solid_color::SolidColor, Texture},
+  vec3::Vec3,
+};
+
+#[derive(Debug, Clone)]
+pub struct Lambertian {
+  albedo: Arc<dyn Texture>,
+}
+
+impl Lambertian {
+  pub fn new(albedo: Vec3) -> Self {
+    Self::new_from_texture(Arc::new(SolidColor::new(albedo)))
+  }
+  pub fn new_from_texture(albedo: Arc<dyn Texture>) -> Self {
+    Self { albedo }
+  }
+  pub fn albedo(&self) -> &Arc<dyn Texture> {
+    &self.albedo
+  }
+}
+
+impl Material for Lambertian {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let mut scatter_direction = record.shading_normal() + random.unit_direction();
+    if scatter_direction.near_zero() {
+      scatter_direction = record.shading_normal();
+    }
+    let scatter_direction = record.kept_above_surface(scatter_direction);
+    Some(Scatter::new(
+      Radiance::from(
+        self
+          .albedo
+          .filtered_value([record.u(), record.v()], record.point(), record.texture_footprint()),
+      ),
+      Ray::new_at_time(record.point(), scatter_direction, ray.time()),
+    ))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::HitRecord,
+    materials::Material,
+    newtypes::{direction::Direction, point::Point, radiance::Radiance},
+    random::Random,
+    ray::Ray,
+    vec3::Vec3,
+  };
+
+  use super::Lambertian;
+
+  #[test]
+  fn scatters_into_normal_hemisphere() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 1.0, 0.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = HitRecord::new(&ray, 1.0, Direction::from_f32_array_const([0.0, 1.0, 0.0]), [0.0, 0.0]);
+    let material = Lambertian::new(Vec3::new([0.5, 0.5, 0.5]));
+    let mut random = Random::new(1);
+    for _ in 0..100 {
+      let scatter = material
+        .scatter(&ray, &record, &mut random)
+        .expect("Lambertian never absorbs");
+      assert!(scatter.ray().direction().y() >= 0.0);
+      assert_eq!(scatter.attenuation(), Radiance::new([0.5, 0.5, 0.5]));
+    }
+  }
+}
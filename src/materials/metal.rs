@@ -0,0 +1,75 @@
+use crate::{
+  hittable::HitRecord,
+  materials::{Material, Scatter, Specular},
+  newtypes::{percent::Strict as StrictPercent, radiance::Radiance},
+  random::Random,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Metal {
+  albedo: Vec3,
+  fuzz: StrictPercent,
+}
+
+impl Metal {
+  pub const fn new(albedo: Vec3, fuzz: StrictPercent) -> Self {
+    Self { albedo, fuzz }
+  }
+  pub const fn albedo(&self) -> Vec3 {
+    self.albedo
+  }
+  pub const fn fuzz(&self) -> StrictPercent {
+    self.fuzz
+  }
+}
+
+impl Material for Metal {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let reflected = ray.direction().unit_direction().reflect(record.shading_normal());
+    let scattered = reflected + random.in_unit_sphere() * self.fuzz.as_f32();
+    if scattered.dot(record.normal()) <= 0.0 {
+      return None;
+    }
+    let scattered_ray = Ray::new_at_time(record.point(), scattered, ray.time());
+    if self.fuzz.as_f32() > 0.0 {
+      return Some(Scatter::new(Radiance::from(self.albedo), scattered_ray));
+    }
+    Some(Scatter::new_specular(
+      Radiance::from(self.albedo),
+      scattered_ray,
+      Specular::Reflection,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::HitRecord,
+    materials::{Material, Specular},
+    newtypes::{direction::Direction, percent::Strict as StrictPercent, point::Point},
+    random::Random,
+    ray::Ray,
+    vec3::Vec3,
+  };
+
+  use super::Metal;
+
+  #[test]
+  fn polished_metal_mirrors() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-1.0, 1.0, 0.0]),
+      Direction::from_f32_array_const([1.0, -1.0, 0.0]),
+    );
+    let record = HitRecord::new(&ray, 1.0, Direction::from_f32_array_const([0.0, 1.0, 0.0]), [0.0, 0.0]);
+    let material = Metal::new(Vec3::new([0.9, 0.9, 0.9]), StrictPercent::new(0.0).unwrap());
+    let scatter = material
+      .scatter(&ray, &record, &mut Random::new(1))
+      .expect("A mirror reflects");
+    let expected = Direction::from_f32_array_const([1.0, 1.0, 0.0]).unit_direction();
+    assert!((scatter.ray().direction() - expected).length().as_f32() < 1e-6);
+    assert_eq!(scatter.specular(), Some(Specular::Reflection));
+  }
+}
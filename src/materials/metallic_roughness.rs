@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use crate::{
+  hittable::HitRecord,
+  materials::{
+    dielectric::reflectance,
+    microfacet::{schlick_fresnel, Ggx},
+    Material, Scatter, Specular,
+  },
+  newtypes::{percent::Strict as StrictPercent, radiance::Radiance},
+  orthonormal_basis::OrthonormalBasis,
+  random::Random,
+  ray::Ray,
+  textures::{solid_color::SolidColor, Texture},
+  vec3::Vec3,
+};
+
+/// The index of refraction `glTF` assumes for non-metals, reflecting 4% head on.
+const DIELECTRIC_INDEX_OF_REFRACTION: f32 = 1.5;
+
+/// The `glTF` metallic-roughness model: a GGX specular lobe over either a conductor tinted by the base color, or a
+/// diffuse base coated by a clear dielectric. `metallic` blends the two and `roughness` widens the specular lobe.
+#[derive(Debug, Clone)]
+pub struct MetallicRoughness {
+  base_color: Arc<dyn Texture>,
+  metallic: StrictPercent,
+  roughness: StrictPercent,
+  distribution: Ggx,
+}
+
+impl MetallicRoughness {
+  pub fn new(base_color: Vec3, metallic: StrictPercent, roughness: StrictPercent) -> Self {
+    Self::new_from_texture(Arc::new(SolidColor::new(base_color)), metallic, roughness)
+  }
+  pub fn new_from_texture(base_color: Arc<dyn Texture>, metallic: StrictPercent, roughness: StrictPercent) -> Self {
+    Self {
+      base_color,
+      metallic,
+      roughness,
+      distribution: Ggx::new_from_roughness(roughness),
+    }
+  }
+  pub fn base_color(&self) -> &Arc<dyn Texture> {
+    &self.base_color
+  }
+  pub const fn metallic(&self) -> StrictPercent {
+    self.metallic
+  }
+  pub const fn roughness(&self) -> StrictPercent {
+    self.roughness
+  }
+}
+
+impl Material for MetallicRoughness {
+  /// Samples the visible microfacet normals, then picks the metal lobe with probability `metallic`. The dielectric
+  /// lobe reflects off the microfacet with its Fresnel probability and otherwise scatters diffusely, so no lobe ever
+  /// returns more energy than arrives.
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let base_color = self
+      .base_color
+      .filtered_value([record.u(), record.v()], record.point(), record.texture_footprint());
+    let basis = OrthonormalBasis::new_from_w(record.shading_normal());
+    let outgoing = basis.world_to_local(-ray.direction().unit_direction());
+    if outgoing.z() <= 0.0 {
+      return None;
+    }
+    let microfacet_normal = self.distribution.sample_visible_normal(outgoing, random);
+    let cos_outgoing_microfacet = outgoing.dot(microfacet_normal);
+    let tint = if random.next_f32() < self.metallic.as_f32() {
+      schlick_fresnel(base_color, cos_outgoing_microfacet)
+    } else if random.next_f32() < reflectance(cos_outgoing_microfacet, 1.0 / DIELECTRIC_INDEX_OF_REFRACTION) {
+      Vec3::new([1.0, 1.0, 1.0])
+    } else {
+      let mut scatter_direction = record.shading_normal() + random.unit_direction();
+      if scatter_direction.near_zero() {
+        scatter_direction = record.shading_normal();
+      }
+      return Some(Scatter::new(
+        Radiance::from(base_color),
+        Ray::new_at_time(record.point(), record.kept_above_surface(scatter_direction), ray.time()),
+      ));
+    };
+    let incoming = -outgoing.reflect(microfacet_normal);
+    if incoming.z() <= 0.0 {
+      return None;
+    }
+    let direction = basis.local_to_world(incoming);
+    if direction.dot(record.normal()) <= 0.0 {
+      return None;
+    }
+    let attenuation =
+      Radiance::from(tint) * (self.distribution.masking_shadowing(outgoing, incoming) / self.distribution.masking(outgoing));
+    let scattered = Ray::new_at_time(record.point(), direction, ray.time());
+    if self.roughness.as_f32() > 0.0 {
+      return Some(Scatter::new(attenuation, scattered));
+    }
+    Some(Scatter::new_specular(attenuation, scattered, Specular::Reflection))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    newtypes::percent::Strict as StrictPercent,
+    test_support::{unit_sphere, white_furnace},
+    vec3::Vec3,
+  };
+
+  use super::MetallicRoughness;
+
+  /// The average radiance seen looking at a white-lit sphere; a material that conserves energy never exceeds one.
+  fn furnace(metallic: f32, roughness: f32) -> f32 {
+    let material = MetallicRoughness::new(
+      Vec3::new([1.0, 1.0, 1.0]),
+      StrictPercent::new(metallic).unwrap(),
+      StrictPercent::new(roughness).unwrap(),
+    );
+    let (albedo, brightest) = white_furnace(&unit_sphere(Arc::new(material)), 4000, 50, 7);
+    assert!(brightest <= 1.0 + 1e-4, "A sample returned {}", brightest);
+    albedo.x()
+  }
+
+  #[test]
+  fn white_furnace_never_gains_energy() {
+    for metallic in [0.0, 0.5, 1.0] {
+      for roughness in [0.0, 0.3, 0.7, 1.0] {
+        let albedo = furnace(metallic, roughness);
+        assert!(
+          albedo <= 1.0 + 1e-4,
+          "metallic {} roughness {} gave {}",
+          metallic,
+          roughness,
+          albedo
+        );
+      }
+    }
+  }
+  #[test]
+  fn white_furnace_loses_little_energy_when_smooth() {
+    assert!(furnace(1.0, 0.0) > 0.999);
+    assert!(furnace(0.0, 0.0) > 0.999);
+    assert!(furnace(1.0, 0.3) > 0.95);
+    assert!(furnace(0.0, 1.0) > 0.9);
+    // Single scattering misses the light that bounces between microfacets, which is most noticeable on rough metal.
+    assert!(furnace(1.0, 1.0) > 0.35);
+  }
+}
@@ -0,0 +1,138 @@
+use std::f32::consts::PI;
+
+use crate::{
+  newtypes::{direction::Direction, percent::Strict as StrictPercent},
+  random::Random,
+  vec3::Vec3,
+};
+
+/// Keeps perfectly smooth surfaces from dividing by zero; narrower lobes are indistinguishable from a mirror.
+const MINIMUM_ALPHA: f32 = 1e-3;
+
+/// The Trowbridge-Reitz (GGX) distribution of microfacet normals with Smith masking, in a local frame where the
+/// macroscopic surface normal is `+z`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Ggx {
+  alpha: f32,
+}
+
+impl Ggx {
+  /// `roughness` is perceptual, as in `glTF`: the distribution's width is its square.
+  pub fn new_from_roughness(roughness: StrictPercent) -> Self {
+    Self {
+      alpha: (roughness.as_f32() * roughness.as_f32()).max(MINIMUM_ALPHA),
+    }
+  }
+  pub const fn alpha(self) -> f32 {
+    self.alpha
+  }
+  /// The density of microfacets facing along the unit `normal`, per unit of projected area.
+  pub fn distribution(self, normal: Direction) -> f32 {
+    let alpha_squared = self.alpha * self.alpha;
+    let cos_squared = normal.z() * normal.z();
+    let denominator = cos_squared * (alpha_squared - 1.0) + 1.0;
+    alpha_squared / (PI * denominator * denominator)
+  }
+  /// Smith's auxiliary function, the ratio of hidden to visible projected microfacet area seen from `direction`.
+  fn lambda(self, direction: Direction) -> f32 {
+    let cos_squared = direction.z() * direction.z();
+    if cos_squared <= 0.0 {
+      return f32::INFINITY;
+    }
+    let tan_squared = (1.0 - cos_squared).max(0.0) / cos_squared;
+    ((1.0 + self.alpha * self.alpha * tan_squared).sqrt() - 1.0) * 0.5
+  }
+  /// The fraction of microfacets visible from `direction`.
+  pub fn masking(self, direction: Direction) -> f32 {
+    1.0 / (1.0 + self.lambda(direction))
+  }
+  /// The fraction of microfacets visible from both directions, with height-correlated masking and shadowing.
+  pub fn masking_shadowing(self, outgoing: Direction, incoming: Direction) -> f32 {
+    1.0 / (1.0 + self.lambda(outgoing) + self.lambda(incoming))
+  }
+  /// Picks a microfacet normal in proportion to how much of it is visible from the unit `outgoing` direction, which
+  /// must be above the surface (Heitz 2018).
+  pub fn sample_visible_normal(self, outgoing: Direction, random: &mut Random) -> Direction {
+    let stretched =
+      Direction::from_f32_array_const([self.alpha * outgoing.x(), self.alpha * outgoing.y(), outgoing.z()]).unit_direction();
+    let length_squared = stretched.x() * stretched.x() + stretched.y() * stretched.y();
+    let first_axis = if length_squared > 0.0 {
+      Direction::from_f32_array_const([-stretched.y(), stretched.x(), 0.0]) / length_squared.sqrt()
+    } else {
+      Direction::from_f32_array_const([1.0, 0.0, 0.0])
+    };
+    let second_axis = stretched.cross(first_axis);
+    let radius = random.next_f32().sqrt();
+    let angle = 2.0 * PI * random.next_f32();
+    let first = radius * angle.cos();
+    let blend = 0.5 * (1.0 + stretched.z());
+    let second = (1.0 - blend) * (1.0 - first * first).max(0.0).sqrt() + blend * radius * angle.sin();
+    let height = (1.0 - first * first - second * second).max(0.0).sqrt();
+    let normal = first_axis * first + second_axis * second + stretched * height;
+    Direction::from_f32_array_const([self.alpha * normal.x(), self.alpha * normal.y(), normal.z().max(0.0)]).unit_direction()
+  }
+}
+
+/// Schlick's approximation of the Fresnel reflectance of a surface reflecting `normal_reflectance` head on.
+pub fn schlick_fresnel(normal_reflectance: Vec3, cosine: f32) -> Vec3 {
+  let grazing = (1.0 - cosine.clamp(0.0, 1.0)).powi(5);
+  normal_reflectance + (Vec3::new([1.0, 1.0, 1.0]) - normal_reflectance) * grazing
+}
+
+#[cfg(test)]
+mod test {
+  use std::f32::consts::PI;
+
+  use crate::{
+    newtypes::{direction::Direction, percent::Strict as StrictPercent},
+    random::Random,
+    vec3::Vec3,
+  };
+
+  use super::{schlick_fresnel, Ggx};
+
+  #[test]
+  fn projected_normals_cover_the_surface_once() {
+    // The integral of D(m) cos(m) over the hemisphere is one; estimate it with uniform hemisphere samples.
+    let mut random = Random::new(5);
+    for roughness in [0.3, 0.6, 1.0] {
+      let ggx = Ggx::new_from_roughness(StrictPercent::new(roughness).unwrap());
+      let samples: u16 = 40_000;
+      let total: f32 = (0..samples)
+        .map(|_| {
+          let direction = random.unit_direction();
+          let normal = Direction::from_f32_array_const([direction.x(), direction.y(), direction.z().abs()]);
+          ggx.distribution(normal) * normal.z() * 2.0 * PI
+        })
+        .sum();
+      let estimate = total / f32::from(samples);
+      assert!((estimate - 1.0).abs() < 0.05, "roughness {} gave {}", roughness, estimate);
+    }
+  }
+  #[test]
+  fn visible_normals_face_the_viewer() {
+    let ggx = Ggx::new_from_roughness(StrictPercent::new(0.7).unwrap());
+    let outgoing = Direction::from_f32_array_const([0.8, 0.0, 0.6]);
+    let mut random = Random::new(6);
+    for _ in 0..1000 {
+      let normal = ggx.sample_visible_normal(outgoing, &mut random);
+      assert!(normal.z() >= 0.0);
+      assert!(normal.dot(outgoing) >= -1e-5);
+      assert!((normal.length().as_f32() - 1.0).abs() < 1e-5);
+    }
+  }
+  #[test]
+  fn masking_is_one_head_on_and_vanishes_at_grazing() {
+    let ggx = Ggx::new_from_roughness(StrictPercent::new(0.5).unwrap());
+    let up = Direction::from_f32_array_const([0.0, 0.0, 1.0]);
+    assert!((ggx.masking(up) - 1.0).abs() < 1e-6);
+    assert!(ggx.masking(Direction::from_f32_array_const([1.0, 0.0, 0.0])).abs() < 1e-6);
+    assert!(ggx.masking_shadowing(up, up) <= ggx.masking(up));
+  }
+  #[test]
+  fn fresnel_rises_to_one_at_grazing() {
+    let head_on = Vec3::new([0.04, 0.5, 0.9]);
+    assert_eq!(schlick_fresnel(head_on, 1.0), head_on);
+    assert_eq!(schlick_fresnel(head_on, 0.0), Vec3::new([1.0, 1.0, 1.0]));
+  }
+}
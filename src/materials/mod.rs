@@ -0,0 +1,63 @@
+pub mod coated;
+pub mod dielectric;
+pub mod lambertian;
+pub mod metal;
+pub mod metallic_roughness;
+pub mod microfacet;
+pub mod rough_dielectric;
+pub mod volumetric;
+
+use std::fmt::Debug;
+
+use crate::{hittable::HitRecord, newtypes::radiance::Radiance, random::Random, ray::Ray};
+
+/// How a perfectly sharp scatter bent the ray, so ray differentials can follow it.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Specular {
+  Reflection,
+  /// Refraction with the ratio of incident over transmitted index of refraction.
+  Transmission(f32),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Scatter {
+  attenuation: Radiance,
+  ray: Ray,
+  specular: Option<Specular>,
+}
+
+impl Scatter {
+  pub const fn new(attenuation: Radiance, ray: Ray) -> Self {
+    Self {
+      attenuation,
+      ray,
+      specular: None,
+    }
+  }
+  pub const fn new_specular(attenuation: Radiance, ray: Ray, specular: Specular) -> Self {
+    Self {
+      attenuation,
+      ray,
+      specular: Some(specular),
+    }
+  }
+  pub const fn attenuation(&self) -> Radiance {
+    self.attenuation
+  }
+  pub const fn ray(&self) -> Ray {
+    self.ray
+  }
+  /// `None` for scatters that spread light out, such as diffuse or rough reflection.
+  pub const fn specular(&self) -> Option<Specular> {
+    self.specular
+  }
+}
+
+pub trait Material: Debug + Send + Sync {
+  /// Returns `None` when the ray is absorbed.
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter>;
+  /// Light given off at the hit, added whether or not the ray also scatters.
+  fn emitted(&self, _ray: &Ray, _record: &HitRecord) -> Radiance {
+    Radiance::default()
+  }
+}
@@ -0,0 +1,277 @@
+use crate::{
+  error::LocalError,
+  hittable::HitRecord,
+  materials::{dielectric::fresnel, microfacet::Ggx, Material, Scatter, Specular},
+  newtypes::{direction::Direction, percent::Strict as StrictPercent, radiance::Radiance},
+  orthonormal_basis::OrthonormalBasis,
+  random::Random,
+  ray::Ray,
+};
+
+/// Glass whose surface is a GGX distribution of microfacets that each reflect or refract (Walter et al. 2007).
+/// Thin-walled glass is a sheet with air on both sides: light passing through leaves along the direction it arrived,
+/// and the reflectance accounts for both faces.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct RoughDielectric {
+  index_of_refraction: f32,
+  roughness: StrictPercent,
+  distribution: Ggx,
+  thin_walled: bool,
+}
+
+impl RoughDielectric {
+  pub fn new(index_of_refraction: f32, roughness: StrictPercent) -> Result<Self, LocalError> {
+    if !(index_of_refraction.is_finite() && index_of_refraction >= 1.0) {
+      return Err("A rough dielectric's index of refraction must be finite and at least 1.0".into());
+    }
+    Ok(Self {
+      index_of_refraction,
+      roughness,
+      distribution: Ggx::new_from_roughness(roughness),
+      thin_walled: false,
+    })
+  }
+  pub fn new_thin_walled(index_of_refraction: f32, roughness: StrictPercent) -> Result<Self, LocalError> {
+    Ok(Self {
+      thin_walled: true,
+      ..Self::new(index_of_refraction, roughness)?
+    })
+  }
+  pub const fn index_of_refraction(&self) -> f32 {
+    self.index_of_refraction
+  }
+  pub const fn roughness(&self) -> StrictPercent {
+    self.roughness
+  }
+  pub const fn thin_walled(&self) -> bool {
+    self.thin_walled
+  }
+  /// The index of refraction on the side of the surface `direction` points to, in a frame with `+z` outside.
+  fn index_towards(&self, direction: Direction) -> f32 {
+    if direction.z() > 0.0 || self.thin_walled {
+      1.0
+    } else {
+      self.index_of_refraction
+    }
+  }
+  /// The fraction of light a microfacet reflects when seen at `cos_outgoing` from the side with `eta_ratio`; a thin
+  /// sheet adds the light bouncing back and forth between its two faces.
+  fn reflectance(&self, cos_outgoing: f32, eta_ratio: f32) -> f32 {
+    if self.thin_walled {
+      let single = fresnel(cos_outgoing, 1.0 / self.index_of_refraction);
+      return 2.0 * single / (1.0 + single);
+    }
+    fresnel(cos_outgoing, eta_ratio)
+  }
+  /// The BSDF for light arriving along `incoming` and leaving along `outgoing`, both unit directions pointing away from
+  /// the surface in a frame with `+z` outside. It is reciprocal up to the usual factor: `evaluate(o, i)` over the
+  /// squared index on the side of `o` equals `evaluate(i, o)` over the squared index on the side of `i`. Thin-walled
+  /// transmission is a delta and not included.
+  pub fn evaluate(&self, outgoing: Direction, incoming: Direction) -> f32 {
+    let (index_outgoing, index_incoming) = (self.index_towards(outgoing), self.index_towards(incoming));
+    let cos_product = outgoing.z() * incoming.z();
+    let upward = |direction: Direction| direction * 1.0_f32.copysign(direction.z());
+    if cos_product > 0.0 {
+      let half = upward((outgoing + incoming).unit_direction());
+      let reflectance = self.reflectance(outgoing.dot(half).abs(), index_outgoing / self.index_towards(-outgoing));
+      return reflectance * self.distribution.distribution(half) * self.distribution.masking_shadowing(outgoing, incoming)
+        / (4.0 * cos_product);
+    }
+    if cos_product >= 0.0 || self.thin_walled {
+      return 0.0;
+    }
+    let half = incoming * index_incoming + outgoing * index_outgoing;
+    if half.near_zero() {
+      return 0.0;
+    }
+    let half = upward(half.unit_direction());
+    let (cos_outgoing, cos_incoming) = (outgoing.dot(half), incoming.dot(half));
+    if cos_outgoing * cos_incoming >= 0.0 {
+      return 0.0;
+    }
+    let transmittance = 1.0 - fresnel(cos_outgoing.abs(), index_outgoing / index_incoming);
+    let spread = index_incoming * cos_incoming + index_outgoing * cos_outgoing;
+    self.distribution.distribution(half)
+      * self.distribution.masking_shadowing(outgoing, incoming)
+      * transmittance
+      * (cos_outgoing * cos_incoming).abs()
+      * index_outgoing
+      * index_outgoing
+      / (cos_product.abs() * spread * spread)
+  }
+}
+
+impl Material for RoughDielectric {
+  /// Samples a visible microfacet, then reflects off it with its Fresnel probability and refracts through it otherwise,
+  /// so each lobe is weighted by the masking alone, and by the squared index ratio for refracted radiance.
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let basis = OrthonormalBasis::new_from_w(record.outward_shading_normal());
+    let outgoing = basis.world_to_local(-ray.direction().unit_direction());
+    let side = 1.0_f32.copysign(outgoing.z());
+    let flip = |direction: Direction| Direction::from_f32_array_const([direction.x(), direction.y(), direction.z() * side]);
+    let microfacet_normal = flip(self.distribution.sample_visible_normal(flip(outgoing), random));
+    let index_outgoing = self.index_towards(outgoing);
+    let eta_ratio = index_outgoing / self.index_towards(-outgoing);
+    let reflectance = self.reflectance(outgoing.dot(microfacet_normal), eta_ratio);
+    let smooth = self.roughness.as_f32() <= 0.0;
+    if random.next_f32() >= reflectance {
+      if self.thin_walled {
+        let passed = Ray::new_at_time(record.point(), ray.direction(), ray.time());
+        return Some(Scatter::new_specular(
+          Radiance::new([1.0, 1.0, 1.0]),
+          passed,
+          Specular::Transmission(1.0),
+        ));
+      }
+      let incoming = (-outgoing).refract(microfacet_normal, eta_ratio);
+      let direction = basis.local_to_world(incoming);
+      if incoming.z() * outgoing.z() >= 0.0 || direction.dot(record.normal()) >= 0.0 {
+        return None;
+      }
+      let masking = self.distribution.masking_shadowing(outgoing, incoming) / self.distribution.masking(outgoing);
+      let weight = masking * eta_ratio * eta_ratio;
+      let scattered = Ray::new_at_time(record.point(), direction, ray.time());
+      let attenuation = Radiance::new([weight, weight, weight]);
+      if smooth {
+        return Some(Scatter::new_specular(
+          attenuation,
+          scattered,
+          Specular::Transmission(eta_ratio),
+        ));
+      }
+      return Some(Scatter::new(attenuation, scattered));
+    }
+    let incoming = -outgoing.reflect(microfacet_normal);
+    let direction = basis.local_to_world(incoming);
+    if incoming.z() * outgoing.z() <= 0.0 || direction.dot(record.normal()) <= 0.0 {
+      return None;
+    }
+    let weight = self.distribution.masking_shadowing(outgoing, incoming) / self.distribution.masking(outgoing);
+    let scattered = Ray::new_at_time(record.point(), direction, ray.time());
+    let attenuation = Radiance::new([weight, weight, weight]);
+    if smooth {
+      return Some(Scatter::new_specular(attenuation, scattered, Specular::Reflection));
+    }
+    Some(Scatter::new(attenuation, scattered))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::{f32::consts::PI, sync::Arc};
+
+  use crate::{
+    hittable::HitRecord,
+    materials::Material,
+    newtypes::{direction::Direction, percent::Strict as StrictPercent, point::Point},
+    random::Random,
+    ray::Ray,
+    test_support::{unit_sphere, white_furnace},
+  };
+
+  use super::RoughDielectric;
+
+  fn glass(roughness: f32) -> RoughDielectric {
+    RoughDielectric::new(1.5, StrictPercent::new(roughness).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn rejects_unphysical_indices() {
+    let smooth = StrictPercent::new(0.0).unwrap();
+    for index_of_refraction in [0.5, f32::NAN, f32::INFINITY] {
+      assert!(RoughDielectric::new(index_of_refraction, smooth).is_err());
+      assert!(RoughDielectric::new_thin_walled(index_of_refraction, smooth).is_err());
+    }
+  }
+  #[test]
+  fn evaluation_is_reciprocal() {
+    let mut random = Random::new(8);
+    for material in [
+      glass(0.4),
+      RoughDielectric::new_thin_walled(1.5, StrictPercent::new(0.4).unwrap()).unwrap(),
+    ] {
+      for _ in 0..500 {
+        let (outgoing, incoming) = (random.unit_direction(), random.unit_direction());
+        let index_squared = |direction: Direction| material.index_towards(direction).powi(2);
+        let forward = material.evaluate(outgoing, incoming) / index_squared(outgoing);
+        let backward = material.evaluate(incoming, outgoing) / index_squared(incoming);
+        assert!(
+          (forward - backward).abs() <= 1e-4 * forward.max(1.0),
+          "{} != {}",
+          forward,
+          backward
+        );
+      }
+    }
+  }
+  #[test]
+  fn sampled_weights_match_the_evaluated_albedo() {
+    let material = glass(0.7);
+    let mut random = Random::new(9);
+    for outgoing in [
+      Direction::from_f32_array_const([0.6, 0.0, 0.8]),
+      Direction::from_f32_array_const([0.6, 0.0, -0.8]),
+    ] {
+      // The surface's outward normal is +z and the ray arrives from the side `outgoing` points to.
+      let ray = Ray::new(
+        Point::from_f32_array_const([outgoing.x(), outgoing.y(), outgoing.z()]),
+        -outgoing,
+      );
+      let record = HitRecord::new(&ray, 1.0, Direction::from_f32_array_const([0.0, 0.0, 1.0]), [0.0, 0.0]);
+      let samples: u16 = 60_000;
+      let sampled_albedo: f32 = (0..samples)
+        .map(|_| {
+          material
+            .scatter(&ray, &record, &mut random)
+            .map_or(0.0, |scatter| scatter.attenuation().red())
+        })
+        .sum::<f32>()
+        / f32::from(samples);
+      let evaluated: f32 = (0..samples)
+        .map(|_| {
+          let incoming = random.unit_direction();
+          material.evaluate(outgoing, incoming) * incoming.z().abs() * 4.0 * PI
+        })
+        .sum::<f32>()
+        / f32::from(samples);
+      assert!(
+        (sampled_albedo - evaluated).abs() < 0.05 * evaluated,
+        "{} != {}",
+        sampled_albedo,
+        evaluated
+      );
+    }
+  }
+
+  fn furnace(material: RoughDielectric) -> f32 {
+    let (albedo, brightest) = white_furnace(&unit_sphere(Arc::new(material)), 4000, 100, 10);
+    assert!(brightest <= 1.0 + 1e-3, "A sample returned {}", brightest);
+    albedo.x()
+  }
+
+  #[test]
+  fn white_furnace_conserves_energy() {
+    assert!(furnace(glass(0.0)) > 0.99);
+    assert!(furnace(glass(0.3)) > 0.9);
+    // Single scattering loses the light bouncing between microfacets, and rough interiors trap more of it.
+    assert!(furnace(glass(0.5)) > 0.7);
+    assert!(furnace(RoughDielectric::new_thin_walled(1.5, StrictPercent::new(0.0).unwrap()).unwrap()) > 0.99);
+    assert!(furnace(RoughDielectric::new_thin_walled(1.5, StrictPercent::new(0.5).unwrap()).unwrap()) > 0.9);
+  }
+  #[test]
+  fn thin_walls_pass_light_straight_through() {
+    let material = RoughDielectric::new_thin_walled(1.5, StrictPercent::new(0.3).unwrap()).unwrap();
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 1.0]),
+      Direction::from_f32_array_const([0.3, 0.0, -1.0]),
+    );
+    let record = HitRecord::new(&ray, 1.0, Direction::from_f32_array_const([0.0, 0.0, 1.0]), [0.0, 0.0]);
+    let mut random = Random::new(11);
+    let transmitted = (0..1000)
+      .filter_map(|_| material.scatter(&ray, &record, &mut random))
+      .filter(|scatter| scatter.ray().direction().z() < 0.0)
+      .inspect(|scatter| assert_eq!(scatter.ray().direction(), ray.direction()))
+      .count();
+    assert!(transmitted > 800);
+  }
+}
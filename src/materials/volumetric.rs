@@ -0,0 +1,38 @@
+use crate::{
+  hittable::HitRecord,
+  materials::{Material, Scatter},
+  newtypes::radiance::Radiance,
+  phase_function::PhaseFunction,
+  random::Random,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+/// The scattering behaviour of a participating medium; the hit normal is ignored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Volumetric {
+  albedo: Vec3,
+  phase_function: PhaseFunction,
+}
+
+impl Volumetric {
+  pub const fn new(albedo: Vec3, phase_function: PhaseFunction) -> Self {
+    Self { albedo, phase_function }
+  }
+  pub const fn albedo(&self) -> Vec3 {
+    self.albedo
+  }
+  pub const fn phase_function(&self) -> PhaseFunction {
+    self.phase_function
+  }
+}
+
+impl Material for Volumetric {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let direction = self.phase_function.sample(ray.direction(), random);
+    Some(Scatter::new(
+      Radiance::from(self.albedo),
+      Ray::new_at_time(record.point(), direction, ray.time()),
+    ))
+  }
+}
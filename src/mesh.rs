@@ -0,0 +1,361 @@
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+use crate::{
+  aabb::Aabb,
+  bvh::Bvh,
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  ray::Ray,
+  shapes::triangle::intersect,
+};
+
+const DEGENERATE_UV_EPSILON: f32 = 1e-12;
+
+/// An indexed triangle mesh with a normal, texture coordinate and tangent frame at every vertex.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct Mesh {
+  positions: Vec<Point>,
+  normals: Vec<Direction>,
+  uvs: Vec<[f32; 2]>,
+  tangents: Vec<Direction>,
+  bitangents: Vec<Direction>,
+  triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+  /// Missing normals are averaged from the faces around each vertex, weighted by area. Tangents always follow the
+  /// direction of increasing `u`, and bitangents increasing `v`, so tangent-space normal maps line up.
+  pub fn new(
+    positions: Vec<Point>,
+    normals: Option<Vec<Direction>>,
+    uvs: Option<Vec<[f32; 2]>>,
+    triangles: Vec<[usize; 3]>,
+  ) -> Result<Self, LocalError> {
+    let vertex_count = positions.len();
+    if triangles.iter().flatten().any(|index| *index >= vertex_count) {
+      return Err("A mesh triangle refers to a vertex that does not exist".into());
+    }
+    if normals.as_ref().map_or(false, |normals| normals.len() != vertex_count)
+      || uvs.as_ref().map_or(false, |uvs| uvs.len() != vertex_count)
+    {
+      return Err("Every mesh vertex needs exactly one normal and texture coordinate".into());
+    }
+    let mut mesh = Self {
+      positions,
+      normals: normals.unwrap_or_default(),
+      uvs: uvs.unwrap_or_else(|| vec![[0.0, 0.0]; vertex_count]),
+      tangents: Vec::new(),
+      bitangents: Vec::new(),
+      triangles,
+    };
+    if mesh.normals.is_empty() {
+      mesh.normals = mesh.face_weighted_normals();
+    }
+    mesh.compute_tangents();
+    Ok(mesh)
+  }
+  pub fn load_obj(path: &Path) -> Result<Self, LocalError> {
+    Self::parse_obj(&fs::read_to_string(path)?)
+  }
+  /// Reads `v`, `vt`, `vn` and polygonal `f` records, splitting polygons into fans and ignoring everything else.
+  pub fn parse_obj(text: &str) -> Result<Self, LocalError> {
+    let mut obj_positions = Vec::new();
+    let mut obj_uvs = Vec::new();
+    let mut obj_normals = Vec::new();
+    let mut vertex_indices: HashMap<(usize, Option<usize>, Option<usize>), usize> = HashMap::new();
+    let mut corners = Vec::new();
+    let mut triangles = Vec::new();
+    for (line_number, line) in text.lines().enumerate() {
+      let mut fields = line.split_whitespace();
+      let numbers = |fields: std::str::SplitWhitespace<'_>, count: usize| -> Result<Vec<f32>, LocalError> {
+        let values = fields.take(count).map(str::parse).collect::<Result<Vec<f32>, _>>()?;
+        if values.len() < count {
+          return Err(format!("Line {} of the OBJ file has too few numbers", line_number + 1).into());
+        }
+        Ok(values)
+      };
+      match fields.next() {
+        Some("v") => {
+          let values = numbers(fields, 3)?;
+          obj_positions.push(Point::from_f32_array_const([values[0], values[1], values[2]]));
+        }
+        Some("vt") => {
+          let values = numbers(fields, 2)?;
+          obj_uvs.push([values[0], values[1]]);
+        }
+        Some("vn") => {
+          let values = numbers(fields, 3)?;
+          obj_normals.push(Direction::from_f32_array_const([values[0], values[1], values[2]]).unit_direction());
+        }
+        Some("f") => {
+          let face = fields
+            .map(|corner| {
+              let mut parts = corner.split('/');
+              let position = resolve_index(parts.next(), obj_positions.len())?.ok_or_else(|| {
+                format!(
+                  "Line {} of the OBJ file has a face corner without a position",
+                  line_number + 1
+                )
+              })?;
+              let uv = resolve_index(parts.next(), obj_uvs.len())?;
+              let normal = resolve_index(parts.next(), obj_normals.len())?;
+              let next_index = vertex_indices.len();
+              Ok(*vertex_indices.entry((position, uv, normal)).or_insert_with(|| {
+                corners.push((position, uv, normal));
+                next_index
+              }))
+            })
+            .collect::<Result<Vec<usize>, LocalError>>()?;
+          if face.len() < 3 {
+            return Err(
+              format!(
+                "Line {} of the OBJ file has a face with fewer than three corners",
+                line_number + 1
+              )
+              .into(),
+            );
+          }
+          triangles.extend((1..face.len() - 1).map(|index| [face[0], face[index], face[index + 1]]));
+        }
+        _ => {}
+      }
+    }
+    let positions = corners.iter().map(|(position, _, _)| obj_positions[*position]).collect();
+    let uvs = corners
+      .iter()
+      .map(|(_, uv, _)| uv.map(|uv| obj_uvs[uv]))
+      .collect::<Option<Vec<[f32; 2]>>>();
+    let normals = corners
+      .iter()
+      .map(|(_, _, normal)| normal.map(|normal| obj_normals[normal]))
+      .collect::<Option<Vec<Direction>>>();
+    Self::new(positions, normals, uvs, triangles)
+  }
+  pub fn positions(&self) -> &[Point] {
+    &self.positions
+  }
+  pub fn normals(&self) -> &[Direction] {
+    &self.normals
+  }
+  pub fn uvs(&self) -> &[[f32; 2]] {
+    &self.uvs
+  }
+  pub fn tangents(&self) -> &[Direction] {
+    &self.tangents
+  }
+  pub fn bitangents(&self) -> &[Direction] {
+    &self.bitangents
+  }
+  pub fn triangles(&self) -> &[[usize; 3]] {
+    &self.triangles
+  }
+  /// Shares the mesh between one hittable per triangle, arranged in a bounding volume hierarchy.
+  pub fn into_bvh(self) -> Bvh {
+    let mesh = Arc::new(self);
+    Bvh::new(
+      (0..mesh.triangles.len())
+        .map(|index| {
+          Arc::new(MeshTriangle {
+            mesh: mesh.clone(),
+            index,
+          }) as Arc<dyn Hittable>
+        })
+        .collect(),
+    )
+  }
+  fn corners(&self, triangle: [usize; 3]) -> [Point; 3] {
+    triangle.map(|index| self.positions[index])
+  }
+  fn face_weighted_normals(&self) -> Vec<Direction> {
+    let mut normals = vec![Direction::default(); self.positions.len()];
+    for triangle in &self.triangles {
+      let [first, second, third] = self.corners(*triangle);
+      let area_weighted = Direction::from(second - first).cross((third - first).into());
+      for index in triangle {
+        normals[*index] = normals[*index] + area_weighted;
+      }
+    }
+    normals
+      .into_iter()
+      .map(|normal| {
+        if normal.near_zero() {
+          Direction::from_f32_array_const([0.0, 1.0, 0.0])
+        } else {
+          normal.unit_direction()
+        }
+      })
+      .collect()
+  }
+  /// How points on a face move with `u` and `v`, or `None` when its texture coordinates are degenerate.
+  fn surface_derivatives(&self, triangle: [usize; 3]) -> Option<(Direction, Direction)> {
+    let [first, second, third] = self.corners(triangle);
+    let edge_1 = Direction::from(second - first);
+    let edge_2 = Direction::from(third - first);
+    let [first_uv, second_uv, third_uv] = triangle.map(|index| self.uvs[index]);
+    let (u_step_1, v_step_1) = (second_uv[0] - first_uv[0], second_uv[1] - first_uv[1]);
+    let (u_step_2, v_step_2) = (third_uv[0] - first_uv[0], third_uv[1] - first_uv[1]);
+    let determinant = u_step_1 * v_step_2 - u_step_2 * v_step_1;
+    if determinant.abs() < DEGENERATE_UV_EPSILON {
+      return None;
+    }
+    Some((
+      (edge_1 * v_step_2 - edge_2 * v_step_1) / determinant,
+      (edge_2 * u_step_1 - edge_1 * u_step_2) / determinant,
+    ))
+  }
+  /// Accumulates each face's texture-space derivatives onto its vertices (Lengyel's method), then makes them
+  /// perpendicular to the vertex normal. Vertices without usable coordinates get an arbitrary frame.
+  fn compute_tangents(&mut self) {
+    let mut tangents = vec![Direction::default(); self.positions.len()];
+    let mut bitangents = vec![Direction::default(); self.positions.len()];
+    for triangle in &self.triangles {
+      let (tangent, bitangent) = match self.surface_derivatives(*triangle) {
+        Some(derivatives) => derivatives,
+        None => continue,
+      };
+      for index in triangle {
+        tangents[*index] = tangents[*index] + tangent;
+        bitangents[*index] = bitangents[*index] + bitangent;
+      }
+    }
+    for (index, normal) in self.normals.iter().enumerate() {
+      let tangent = tangents[index] - *normal * tangents[index].dot(*normal);
+      if tangent.near_zero() {
+        let basis = OrthonormalBasis::new_from_w(*normal);
+        tangents[index] = basis.u();
+        bitangents[index] = basis.v();
+        continue;
+      }
+      let tangent = tangent.unit_direction();
+      let handedness = if normal.cross(tangent).dot(bitangents[index]) < 0.0 {
+        -1.0
+      } else {
+        1.0
+      };
+      tangents[index] = tangent;
+      bitangents[index] = normal.cross(tangent) * handedness;
+    }
+    self.tangents = tangents;
+    self.bitangents = bitangents;
+  }
+}
+
+/// Turns a one-based, possibly negative OBJ index into a zero-based one; an empty field means the attribute is absent.
+fn resolve_index(field: Option<&str>, count: usize) -> Result<Option<usize>, LocalError> {
+  let field = match field {
+    None | Some("") => return Ok(None),
+    Some(field) => field,
+  };
+  let index: isize = field.parse()?;
+  let count_signed = isize::try_from(count)?;
+  let resolved = if index < 0 { count_signed + index } else { index - 1 };
+  if resolved < 0 || resolved >= count_signed {
+    return Err(format!("The OBJ index {} is out of range", index).into());
+  }
+  Ok(Some(usize::try_from(resolved)?))
+}
+
+#[derive(Debug, Clone)]
+struct MeshTriangle {
+  mesh: Arc<Mesh>,
+  index: usize,
+}
+
+impl Hittable for MeshTriangle {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let triangle = self.mesh.triangles[self.index];
+    let corners = self.mesh.corners(triangle);
+    let (distance, [weight_1, weight_2]) = intersect(ray, &corners)?;
+    if distance < minimum_distance || distance > maximum_distance {
+      return None;
+    }
+    let weights = [1.0 - weight_1 - weight_2, weight_1, weight_2];
+    let blend = |values: &[Direction]| {
+      triangle
+        .iter()
+        .zip(weights)
+        .fold(Direction::default(), |sum, (index, weight)| sum + values[*index] * weight)
+    };
+    let uv = triangle.iter().zip(weights).fold([0.0, 0.0], |sum, (index, weight)| {
+      let uv = self.mesh.uvs[*index];
+      [sum[0] + uv[0] * weight, sum[1] + uv[1] * weight]
+    });
+    let geometric_normal = Direction::from(corners[1] - corners[0])
+      .cross((corners[2] - corners[0]).into())
+      .unit_direction();
+    let record = HitRecord::new(ray, distance, geometric_normal, uv).with_shading_frame(
+      blend(&self.mesh.normals),
+      blend(&self.mesh.tangents),
+      blend(&self.mesh.bitangents),
+    );
+    Some(match self.mesh.surface_derivatives(triangle) {
+      Some((along_u, along_v)) => record.with_surface_derivatives(along_u, along_v),
+      None => record,
+    })
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    Aabb::new_from_points(&self.mesh.corners(self.mesh.triangles[self.index])).map(|bounding_box| bounding_box.padded())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+  };
+
+  use super::Mesh;
+
+  const QUAD: &str = "# a unit quad in the xy plane\n\
+    v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n\
+    vt 0 0\nvt 1 0\nvt 1 1\nvt 0 1\n\
+    vn 0 0 1\n\
+    f 1/1/1 2/2/1 3/3/1 4/4/1\n";
+
+  #[test]
+  fn parses_polygons_into_shared_vertices() {
+    let mesh = Mesh::parse_obj(QUAD).expect("This is a valid OBJ");
+    assert_eq!(mesh.positions().len(), 4);
+    assert_eq!(mesh.triangles(), &[[0, 1, 2], [0, 2, 3]]);
+    let relative = Mesh::parse_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\n").expect("Relative indices are valid");
+    assert_eq!(relative.triangles(), &[[0, 1, 2]]);
+    assert!((relative.normals()[0].z() - 1.0).abs() < 1e-6);
+    assert!(Mesh::parse_obj("v 0 0 0\nf 1 2 3\n").is_err());
+    assert!(Mesh::parse_obj("v 0 0\n").is_err());
+  }
+  #[test]
+  fn tangents_follow_texture_coordinates() {
+    let mesh = Mesh::parse_obj(QUAD).unwrap();
+    for (tangent, bitangent) in mesh.tangents().iter().zip(mesh.bitangents()) {
+      assert!((tangent.x() - 1.0).abs() < 1e-5);
+      assert!((bitangent.y() - 1.0).abs() < 1e-5);
+    }
+    let mirrored = QUAD.replace("vt 0 0\nvt 1 0\nvt 1 1\nvt 0 1", "vt 1 0\nvt 0 0\nvt 0 1\nvt 1 1");
+    let mirrored = Mesh::parse_obj(&mirrored).unwrap();
+    assert!((mirrored.tangents()[0].x() + 1.0).abs() < 1e-5);
+    assert!((mirrored.bitangents()[0].y() - 1.0).abs() < 1e-5);
+  }
+  #[test]
+  fn hits_interpolate_the_shading_frame() {
+    let text = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn -1 0 1\nvn 1 0 1\nvn -1 0 1\nf 1//1 2//2 3//3\n";
+    let bvh = Mesh::parse_obj(text).unwrap().into_bvh();
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.5, 0.25, 2.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = bvh.hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the triangle");
+    assert!((record.normal().z() - 1.0).abs() < 1e-6);
+    assert!((record.shading_normal().z() - 1.0).abs() < 1e-6);
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.9, 0.05, 2.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = bvh.hit(&ray, 0.0, f32::INFINITY).unwrap();
+    assert!(record.shading_normal().x() > 0.5);
+    assert!(record.shading_normal().dot(record.normal()) > 0.0);
+  }
+}
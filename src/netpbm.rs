@@ -0,0 +1,269 @@
+use std::{
+  fs::File,
+  io::{Read, Write},
+  path::Path,
+};
+
+use crate::{error::LocalError, newtypes::color::Rgb, rgb_image::RgbImage, vec3::Vec3};
+
+/// A decoded grayscale Netpbm (PGM) image, with samples stored row by row from the top.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GrayImage {
+  width: usize,
+  height: usize,
+  max_value: u16,
+  samples: Vec<u16>,
+}
+
+impl GrayImage {
+  pub fn load(path: &Path) -> Result<Self, LocalError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    Self::parse(&bytes)
+  }
+  /// Reads ASCII (`P2`) and binary (`P5`) graymaps.
+  pub fn parse(bytes: &[u8]) -> Result<Self, LocalError> {
+    let mut reader = HeaderReader { bytes, position: 0 };
+    let magic = reader.token()?;
+    if magic != "P2" && magic != "P5" {
+      return Err(format!("Unsupported graymap format {}", magic).into());
+    }
+    let width: usize = reader.token()?.parse()?;
+    let height: usize = reader.token()?.parse()?;
+    let max_value: u16 = reader.token()?.parse()?;
+    if max_value == 0 {
+      return Err("A graymap's maximum value must be positive".into());
+    }
+    let sample_count = width
+      .checked_mul(height)
+      .ok_or_else(|| format!("A {}x{} graymap is too large", width, height))?;
+    let samples = if magic == "P2" {
+      (0..sample_count)
+        .map(|_| Ok(reader.token()?.parse()?))
+        .collect::<Result<Vec<u16>, LocalError>>()?
+    } else {
+      let data = reader.binary_data()?;
+      let bytes_per_sample = if max_value > 255 { 2 } else { 1 };
+      if sample_count
+        .checked_mul(bytes_per_sample)
+        .map_or(true, |length| data.len() < length)
+      {
+        return Err("The graymap ends before all samples were read".into());
+      }
+      data
+        .chunks_exact(bytes_per_sample)
+        .take(sample_count)
+        .map(|chunk| chunk.iter().fold(0_u16, |value, byte| (value << 8) | u16::from(*byte)))
+        .collect()
+    };
+    if samples.iter().any(|sample| *sample > max_value) {
+      return Err(format!("A graymap sample exceeds its maximum value {}", max_value).into());
+    }
+    Ok(Self {
+      width,
+      height,
+      max_value,
+      samples,
+    })
+  }
+  pub const fn width(&self) -> usize {
+    self.width
+  }
+  pub const fn height(&self) -> usize {
+    self.height
+  }
+  pub const fn max_value(&self) -> u16 {
+    self.max_value
+  }
+  /// The sample at `column`, `row` scaled into `[0, 1]`.
+  pub fn intensity(&self, column: usize, row: usize) -> f32 {
+    f32::from(self.samples[row * self.width + column]) / f32::from(self.max_value)
+  }
+}
+
+/// Reads ASCII (`P3`) and binary (`P6`) pixmaps, and graymaps as grey pixels.
+pub fn parse_pixmap(bytes: &[u8]) -> Result<RgbImage, LocalError> {
+  let mut reader = HeaderReader { bytes, position: 0 };
+  let magic = reader.token()?;
+  if magic == "P2" || magic == "P5" {
+    let gray = GrayImage::parse(bytes)?;
+    let pixels = (0..gray.height())
+      .flat_map(|row| (0..gray.width()).map(move |column| (column, row)))
+      .map(|(column, row)| {
+        let intensity = gray.intensity(column, row);
+        Vec3::new([intensity; 3])
+      })
+      .collect();
+    return RgbImage::new(gray.width(), gray.height(), pixels);
+  }
+  if magic != "P3" && magic != "P6" {
+    return Err(format!("Unsupported pixmap format {}", magic).into());
+  }
+  let width: usize = reader.token()?.parse()?;
+  let height: usize = reader.token()?.parse()?;
+  let max_value: u16 = reader.token()?.parse()?;
+  if max_value == 0 {
+    return Err("A pixmap's maximum value must be positive".into());
+  }
+  let sample_count = width
+    .checked_mul(height)
+    .and_then(|pixel_count| pixel_count.checked_mul(3))
+    .ok_or_else(|| format!("A {}x{} pixmap is too large", width, height))?;
+  let samples = if magic == "P3" {
+    (0..sample_count)
+      .map(|_| Ok(reader.token()?.parse()?))
+      .collect::<Result<Vec<u16>, LocalError>>()?
+  } else {
+    let data = reader.binary_data()?;
+    let bytes_per_sample = if max_value > 255 { 2 } else { 1 };
+    if sample_count
+      .checked_mul(bytes_per_sample)
+      .map_or(true, |length| data.len() < length)
+    {
+      return Err("The pixmap ends before all samples were read".into());
+    }
+    data
+      .chunks_exact(bytes_per_sample)
+      .take(sample_count)
+      .map(|chunk| chunk.iter().fold(0_u16, |value, byte| (value << 8) | u16::from(*byte)))
+      .collect()
+  };
+  if samples.iter().any(|sample| *sample > max_value) {
+    return Err(format!("A pixmap sample exceeds its maximum value {}", max_value).into());
+  }
+  let scale = f32::from(max_value);
+  let pixels = samples
+    .chunks_exact(3)
+    .map(|pixel| {
+      Vec3::new([
+        f32::from(pixel[0]) / scale,
+        f32::from(pixel[1]) / scale,
+        f32::from(pixel[2]) / scale,
+      ])
+    })
+    .collect();
+  RgbImage::new(width, height, pixels)
+}
+
+/// Writes an ASCII (`P3`) pixmap of `pixels` stored row by row from the top, with each of `comments` on its own
+/// header line.
+pub fn write_pixmap(
+  writer: &mut impl Write,
+  width: usize,
+  height: usize,
+  comments: &[String],
+  pixels: &[Rgb],
+) -> Result<(), LocalError> {
+  if pixels.len() != width * height {
+    return Err(
+      format!(
+        "A {}x{} pixmap needs {} pixels, not {}",
+        width,
+        height,
+        width * height,
+        pixels.len()
+      )
+      .into(),
+    );
+  }
+  writeln!(writer, "P3")?;
+  for comment in comments {
+    writeln!(writer, "# {}", comment)?;
+  }
+  writeln!(writer, "{} {}\n255", width, height)?;
+  for pixel in pixels {
+    writeln!(writer, "{}", pixel)?;
+  }
+  Ok(())
+}
+
+struct HeaderReader<'a> {
+  bytes: &'a [u8],
+  position: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+  fn skip_whitespace_and_comments(&mut self) {
+    while let Some(byte) = self.bytes.get(self.position) {
+      if *byte == b'#' {
+        while self.bytes.get(self.position).map_or(false, |byte| *byte != b'\n') {
+          self.position += 1;
+        }
+      } else if byte.is_ascii_whitespace() {
+        self.position += 1;
+      } else {
+        break;
+      }
+    }
+  }
+  fn token(&mut self) -> Result<&'a str, LocalError> {
+    self.skip_whitespace_and_comments();
+    let start = self.position;
+    while self
+      .bytes
+      .get(self.position)
+      .map_or(false, |byte| !byte.is_ascii_whitespace())
+    {
+      self.position += 1;
+    }
+    if start == self.position {
+      return Err("Unexpected end of Netpbm data".into());
+    }
+    Ok(std::str::from_utf8(&self.bytes[start..self.position])?)
+  }
+  /// Binary samples start after exactly one whitespace byte following the header.
+  fn binary_data(&self) -> Result<&'a [u8], LocalError> {
+    self
+      .bytes
+      .get(self.position + 1..)
+      .ok_or_else(|| "The graymap has no sample data".into())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::vec3::Vec3;
+
+  use super::{parse_pixmap, GrayImage};
+
+  #[test]
+  fn parses_ascii_graymap_with_comments() {
+    let image = GrayImage::parse(b"P2\n# a comment\n3 2\n10\n0 5 10\n10 5 0\n").expect("This is a valid graymap");
+    assert_eq!((image.width(), image.height(), image.max_value()), (3, 2, 10));
+    assert!((image.intensity(1, 0) - 0.5).abs() < 1e-6);
+    assert!((image.intensity(0, 1) - 1.0).abs() < 1e-6);
+  }
+  #[test]
+  fn parses_binary_graymaps() {
+    let mut bytes = b"P5 2 1 255\n".to_vec();
+    bytes.extend([0, 255]);
+    let image = GrayImage::parse(&bytes).expect("This is a valid graymap");
+    assert!((image.intensity(1, 0) - 1.0).abs() < 1e-6);
+    let mut bytes = b"P5 1 1 65535\n".to_vec();
+    bytes.extend([0x80, 0x00]);
+    let image = GrayImage::parse(&bytes).expect("This is a valid sixteen bit graymap");
+    assert!((image.intensity(0, 0) - 0.5).abs() < 1e-4);
+  }
+  #[test]
+  fn rejects_truncated_and_unknown_data() {
+    assert!(GrayImage::parse(b"P5 2 2 255\n\x00").is_err());
+    assert!(GrayImage::parse(b"P7 1 1 255\n\x00").is_err());
+    assert!(GrayImage::parse(b"P2 2 1 10\n5 11\n").is_err());
+    assert!(GrayImage::parse(b"P5 1 1 100\n\xff").is_err());
+    assert!(GrayImage::parse(b"P5 4294967296 4294967296 255\n\x00").is_err());
+    assert!(GrayImage::parse(b"P5 9223372036854775808 1 65535\n\x00").is_err());
+  }
+  #[test]
+  fn parses_pixmaps() {
+    let image = parse_pixmap(b"P3 2 1 255\n255 0 0  0 51 255\n").expect("This is a valid pixmap");
+    assert_eq!((image.width(), image.height()), (2, 1));
+    assert_eq!(image.pixel(1, 0), Vec3::new([0.0, 0.2, 1.0]));
+    let mut bytes = b"P6 1 1 255\n".to_vec();
+    bytes.extend([255, 0, 255]);
+    assert_eq!(parse_pixmap(&bytes).unwrap().pixel(0, 0), Vec3::new([1.0, 0.0, 1.0]));
+    assert_eq!(parse_pixmap(b"P2 1 1 4\n2\n").unwrap().pixel(0, 0), Vec3::new([0.5; 3]));
+    assert!(parse_pixmap(b"P6 1 1 255\n\x00").is_err());
+    assert!(parse_pixmap(b"P3 1 1 4\n1 2 5\n").is_err());
+    assert!(parse_pixmap(b"P6 4294967296 4294967296 255\n\x00").is_err());
+  }
+}
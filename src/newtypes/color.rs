@@ -1,6 +1,9 @@
 use std::{error::Error, fmt::Display};
 
-use crate::vec3::Vec3;
+use crate::{
+  color_space::{ColorSpace, TaggedColor},
+  vec3::Vec3,
+};
 
 use super::percent::Strict as StrictPercent;
 
@@ -70,6 +73,8 @@ pub enum Errors {
   AboveAndBelow(Box<dyn Error + Send + Sync + 'static>),
 }
 
+/// A quantized display color with channels in `[0, 256)`. Light is computed as `Radiance` and only becomes `Rgb` on
+/// its way to an output.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Rgb(Vec3);
 
@@ -85,7 +90,7 @@ const fn is_color_dimension_valid(value_to_check: f32) -> ColorValidationStates
     return ColorValidationStates::Below;
   }
 
-  if value_to_check > MAXIUM_VALID_RGB_COLOR {
+  if value_to_check >= MAXIUM_VALID_RGB_COLOR {
     return ColorValidationStates::Above;
   }
 
@@ -131,10 +136,21 @@ impl Rgb {
   pub fn new_from_percent(input: [StrictPercent; 3]) -> Result<Self, Errors> {
     Self::new(input.map(convert_strict_percent_to_color_value))
   }
+  /// Converts a rendered color to `output` for display, clipping whatever falls outside its gamut or range. `NaN`
+  /// channels become black.
+  pub fn new_from_color(color: TaggedColor, output: ColorSpace) -> Self {
+    let channels = color.converted_to(output).channels();
+    let clipped = [channels.x(), channels.y(), channels.z()].map(|channel| {
+      let clamped = if channel.is_nan() { 0.0 } else { channel.clamp(0.0, 1.0) };
+      StrictPercent::new(clamped).expect("A clamped channel is a valid percent")
+    });
+    Self::new_from_percent(clipped).expect("Clipped channels are valid colors")
+  }
   #[allow(dead_code)]
   pub fn black() -> Self {
     Self::new([0.0, 0.0, 0.0]).expect("This is a valid black color")
   }
+  #[allow(dead_code)]
   pub fn white() -> Self {
     Self::new([255.0, 255.0, 255.0]).expect("This is a valid white color")
   }
@@ -182,7 +198,11 @@ impl Display for Rgb {
 #[cfg(test)]
 mod test {
 
-  use crate::newtypes::percent::Strict as StrictPercent;
+  use crate::{
+    color_space::{ColorSpace, TaggedColor},
+    newtypes::percent::Strict as StrictPercent,
+    vec3::Vec3,
+  };
 
   use super::{Color, Errors as ColorErrors, Rgb};
 
@@ -218,6 +238,16 @@ mod test {
     assert_eq!(color.blue_part(), 255);
   }
   #[test]
+  fn linear_colors_are_encoded_for_output() {
+    let grey = TaggedColor::new(Vec3::new([0.214_041, 0.214_041, 0.214_041]), ColorSpace::LinearRec709);
+    assert_eq!(Rgb::new_from_color(grey, ColorSpace::Srgb).red_part(), 127);
+    let bright = TaggedColor::new(Vec3::new([4.0, 0.5, -1.0]), ColorSpace::AcesCg);
+    let clipped = Rgb::new_from_color(bright, ColorSpace::Srgb);
+    assert_eq!((clipped.red_part(), clipped.blue_part()), (255, 0));
+    let invalid = TaggedColor::new(Vec3::new([f32::NAN, 1.0, 0.0]), ColorSpace::LinearRec709);
+    assert_eq!(Rgb::new_from_color(invalid, ColorSpace::Srgb).red_part(), 0);
+  }
+  #[test]
   fn blend_two_colors() {
     let white = Rgb::white();
     let black = Rgb::black();
@@ -1,8 +1,8 @@
-use std::ops::{Div, Mul, MulAssign};
+use std::ops::{Add, Div, Mul, MulAssign, Neg, Sub};
 
 use crate::vec3::Vec3;
 
-use super::point::Point;
+use super::{distance::Distance, point::Point};
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Direction(Vec3);
@@ -23,6 +23,12 @@ impl Direction {
   pub const fn z(&self) -> f32 {
     self.0.z()
   }
+  pub const fn as_vec3(&self) -> Vec3 {
+    self.0
+  }
+  pub fn length(&self) -> Distance {
+    self.0.length()
+  }
   pub fn unit_direction(&self) -> Self {
     Self::from_vec3_const(self.0.unit_vector())
   }
@@ -31,6 +37,47 @@ impl Direction {
       .0
       .dot(Vec3::new([right_hand_side.x(), right_hand_side.y(), right_hand_side.z()]))
   }
+  pub const fn cross(&self, right_hand_side: Direction) -> Self {
+    Self::from_vec3_const(self.0.cross(right_hand_side.as_vec3()))
+  }
+  pub fn near_zero(&self) -> bool {
+    const NEAR_ZERO: f32 = 1e-8;
+    self.x().abs() < NEAR_ZERO && self.y().abs() < NEAR_ZERO && self.z().abs() < NEAR_ZERO
+  }
+  pub fn reflect(&self, normal: Direction) -> Self {
+    *self - normal * (2.0 * self.dot(normal))
+  }
+  /// Refracts a unit direction through a surface with unit `normal`, where `eta_ratio` is incident over transmitted index.
+  pub fn refract(&self, normal: Direction, eta_ratio: f32) -> Self {
+    let cos_theta = (-*self).dot(normal).min(1.0);
+    let perpendicular = (*self + normal * cos_theta) * eta_ratio;
+    let parallel = normal * -(1.0 - perpendicular.dot(perpendicular)).abs().sqrt();
+    perpendicular + parallel
+  }
+}
+
+impl Add for Direction {
+  type Output = Self;
+
+  fn add(self, right_hand_side: Self) -> Self::Output {
+    Self::from_vec3_const(self.0 + right_hand_side.as_vec3())
+  }
+}
+
+impl Sub for Direction {
+  type Output = Self;
+
+  fn sub(self, right_hand_side: Self) -> Self::Output {
+    Self::from_vec3_const(self.0 - right_hand_side.as_vec3())
+  }
+}
+
+impl Neg for Direction {
+  type Output = Self;
+
+  fn neg(self) -> Self::Output {
+    Self::from_vec3_const(-self.0)
+  }
 }
 
 impl Mul<f32> for Direction {
@@ -71,8 +118,44 @@ impl MulAssign<f32> for Direction {
   }
 }
 
+impl From<Vec3> for Direction {
+  fn from(value: Vec3) -> Self {
+    Self(value)
+  }
+}
+
 impl From<Direction> for Point {
   fn from(starting_value: Direction) -> Self {
     Self::from_f32_array_const([starting_value.x(), starting_value.y(), starting_value.z()])
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::Direction;
+
+  #[test]
+  fn cross_directions() {
+    let x_axis = Direction::from_f32_array_const([1.0, 0.0, 0.0]);
+    let y_axis = Direction::from_f32_array_const([0.0, 1.0, 0.0]);
+    assert_eq!(x_axis.cross(y_axis), Direction::from_f32_array_const([0.0, 0.0, 1.0]));
+    assert_eq!(y_axis.cross(x_axis), Direction::from_f32_array_const([0.0, 0.0, -1.0]));
+  }
+  #[test]
+  fn reflect_and_refract() {
+    let incoming = Direction::from_f32_array_const([1.0, -1.0, 0.0]).unit_direction();
+    let normal = Direction::from_f32_array_const([0.0, 1.0, 0.0]);
+    let reflected = incoming.reflect(normal);
+    assert!((reflected.x() - incoming.x()).abs() < 1e-6 && (reflected.y() + incoming.y()).abs() < 1e-6);
+    let straight_through = incoming.refract(normal, 1.0);
+    assert!((straight_through - incoming).length().as_f32() < 1e-6);
+  }
+  #[test]
+  fn add_and_subtract_directions() {
+    let direction_1 = Direction::from_f32_array_const([1.0, 2.0, 3.0]);
+    let direction_2 = Direction::from_f32_array_const([3.0, 2.0, 1.0]);
+    assert_eq!(direction_1 + direction_2, Direction::from_f32_array_const([4.0, 4.0, 4.0]));
+    assert_eq!(direction_1 - direction_2, Direction::from_f32_array_const([-2.0, 0.0, 2.0]));
+    assert_eq!(-direction_1, Direction::from_f32_array_const([-1.0, -2.0, -3.0]));
+  }
+}
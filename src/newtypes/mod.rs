@@ -4,3 +4,4 @@ pub mod direction;
 pub mod distance;
 pub mod percent;
 pub mod point;
+pub mod radiance;
@@ -14,6 +14,9 @@ impl Point {
   pub const fn from_f32_array_const(input: [f32; 3]) -> Self {
     Self::from_vec3_const(Vec3::new(input))
   }
+  pub const fn as_vec3(&self) -> Vec3 {
+    self.0
+  }
   pub fn x(&self) -> f32 {
     self.0.x()
   }
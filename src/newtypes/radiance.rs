@@ -0,0 +1,133 @@
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign};
+
+use crate::vec3::Vec3;
+
+/// Linear light per color channel, or a throughput to scale it by. Unlike `Rgb` it has no upper bound, since light
+/// transport routinely produces values far brighter than any display.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Radiance(Vec3);
+
+impl Radiance {
+  pub const fn new(channels: [f32; 3]) -> Self {
+    Self(Vec3::new(channels))
+  }
+  pub const fn as_vec3(&self) -> Vec3 {
+    self.0
+  }
+  pub const fn red(&self) -> f32 {
+    self.0.x()
+  }
+  pub const fn green(&self) -> f32 {
+    self.0.y()
+  }
+  pub const fn blue(&self) -> f32 {
+    self.0.z()
+  }
+  /// The brightness perceived from linear Rec.709 channels.
+  pub fn luminance(&self) -> f32 {
+    0.2126 * self.red() + 0.7152 * self.green() + 0.0722 * self.blue()
+  }
+  pub fn max_component(&self) -> f32 {
+    self.red().max(self.green()).max(self.blue())
+  }
+  pub fn is_black(&self) -> bool {
+    self.max_component() <= 0.0 && self.red().min(self.green()).min(self.blue()) >= 0.0
+  }
+  pub fn has_nan(&self) -> bool {
+    self.red().is_nan() || self.green().is_nan() || self.blue().is_nan()
+  }
+  /// Whether every channel is a number other than an infinity, so the value can safely be averaged.
+  pub fn is_finite(&self) -> bool {
+    self.red().is_finite() && self.green().is_finite() && self.blue().is_finite()
+  }
+}
+
+impl Add for Radiance {
+  type Output = Self;
+
+  fn add(self, right_hand_side: Self) -> Self::Output {
+    Self(self.0 + right_hand_side.0)
+  }
+}
+
+impl AddAssign for Radiance {
+  fn add_assign(&mut self, right_hand_side: Self) {
+    self.0 += right_hand_side.0;
+  }
+}
+
+impl Mul for Radiance {
+  type Output = Self;
+
+  fn mul(self, right_hand_side: Self) -> Self::Output {
+    Self(self.0 * right_hand_side.0)
+  }
+}
+
+impl MulAssign for Radiance {
+  fn mul_assign(&mut self, right_hand_side: Self) {
+    self.0 *= right_hand_side.0;
+  }
+}
+
+impl Mul<f32> for Radiance {
+  type Output = Self;
+
+  fn mul(self, right_hand_side: f32) -> Self::Output {
+    Self(self.0 * right_hand_side)
+  }
+}
+
+impl Mul<Radiance> for f32 {
+  type Output = Radiance;
+
+  fn mul(self, right_hand_side: Radiance) -> Self::Output {
+    right_hand_side * self
+  }
+}
+
+impl Div<f32> for Radiance {
+  type Output = Self;
+
+  fn div(self, right_hand_side: f32) -> Self::Output {
+    Self(self.0 / right_hand_side)
+  }
+}
+
+impl From<Vec3> for Radiance {
+  fn from(value: Vec3) -> Self {
+    Self(value)
+  }
+}
+
+impl From<Radiance> for Vec3 {
+  fn from(value: Radiance) -> Self {
+    value.0
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::Radiance;
+
+  #[test]
+  fn arithmetic_is_unbounded() {
+    let mut light = Radiance::new([300.0, 20.0, 1.0]);
+    light += Radiance::new([100.0, 0.0, 1.0]);
+    light *= Radiance::new([0.5, 0.25, 2.0]);
+    assert_eq!(light, Radiance::new([200.0, 5.0, 4.0]));
+    assert_eq!(2.0 * light / 4.0, Radiance::new([100.0, 2.5, 2.0]));
+    assert!((light.max_component() - 200.0).abs() < 1e-6);
+    assert!((Radiance::new([1.0, 1.0, 1.0]).luminance() - 1.0).abs() < 1e-6);
+  }
+  #[test]
+  fn reports_invalid_samples() {
+    assert!(Radiance::default().is_black());
+    assert!(!Radiance::new([0.0, -1.0, 0.0]).is_black());
+    let broken = Radiance::new([f32::NAN, 0.0, 0.0]);
+    assert!(broken.has_nan() && !broken.is_finite());
+    let blown = Radiance::new([f32::INFINITY, 0.0, 0.0]);
+    assert!(!blown.has_nan() && !blown.is_finite());
+    assert!(Radiance::new([1e30, 0.0, 0.0]).is_finite());
+  }
+}
@@ -0,0 +1,64 @@
+use crate::newtypes::direction::Direction;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct OrthonormalBasis {
+  u: Direction,
+  v: Direction,
+  w: Direction,
+}
+
+impl OrthonormalBasis {
+  /// The caller is responsible for `u`, `v` and `w` being unit length, orthogonal and right handed.
+  pub const fn new_const(u: Direction, v: Direction, w: Direction) -> Self {
+    Self { u, v, w }
+  }
+  /// Builds a basis around `w` without branching on its largest component (Duff et al. 2017).
+  pub fn new_from_w(w: Direction) -> Self {
+    let w = w.unit_direction();
+    let sign = 1.0_f32.copysign(w.z());
+    let a = -1.0 / (sign + w.z());
+    let b = w.x() * w.y() * a;
+    Self {
+      u: Direction::from_f32_array_const([1.0 + sign * w.x() * w.x() * a, sign * b, -sign * w.x()]),
+      v: Direction::from_f32_array_const([b, sign + w.y() * w.y() * a, -w.y()]),
+      w,
+    }
+  }
+  pub const fn u(&self) -> Direction {
+    self.u
+  }
+  pub const fn v(&self) -> Direction {
+    self.v
+  }
+  pub const fn w(&self) -> Direction {
+    self.w
+  }
+  pub fn local_to_world(&self, direction: Direction) -> Direction {
+    self.u * direction.x() + self.v * direction.y() + self.w * direction.z()
+  }
+  pub const fn world_to_local(&self, direction: Direction) -> Direction {
+    Direction::from_f32_array_const([direction.dot(self.u), direction.dot(self.v), direction.dot(self.w)])
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::newtypes::direction::Direction;
+
+  use super::OrthonormalBasis;
+
+  #[test]
+  fn new_from_w_is_orthonormal() {
+    for w in [
+      Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+      Direction::from_f32_array_const([1.0, 2.0, 3.0]),
+    ] {
+      let basis = OrthonormalBasis::new_from_w(w);
+      assert!(basis.u().dot(basis.v()).abs() < 1e-6);
+      assert!(basis.u().dot(basis.w()).abs() < 1e-6);
+      assert!(basis.v().dot(basis.w()).abs() < 1e-6);
+      assert!((basis.u().cross(basis.v()) - basis.w()).length().as_f32() < 1e-5);
+    }
+  }
+}
@@ -0,0 +1,110 @@
+use std::f32::consts::PI;
+
+use crate::{error::LocalError, newtypes::direction::Direction, orthonormal_basis::OrthonormalBasis, random::Random};
+
+const ISOTROPIC_ASYMMETRY_EPSILON: f32 = 1e-3;
+
+/// A Henyey-Greenstein asymmetry, always strictly between -1 and 1 so the phase function stays finite.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Asymmetry(f32);
+
+impl Asymmetry {
+  pub const fn as_f32(self) -> f32 {
+    self.0
+  }
+}
+
+/// How a medium redistributes light at a scattering event, as a function of the angle to the travel direction.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum PhaseFunction {
+  Isotropic,
+  /// Positive asymmetries scatter forward, negative ones scatter back.
+  HenyeyGreenstein {
+    asymmetry: Asymmetry,
+  },
+}
+
+impl Default for PhaseFunction {
+  fn default() -> Self {
+    Self::Isotropic
+  }
+}
+
+impl PhaseFunction {
+  pub fn new_henyey_greenstein(asymmetry: f32) -> Result<Self, LocalError> {
+    if !(asymmetry > -1.0 && asymmetry < 1.0) {
+      return Err("The Henyey-Greenstein asymmetry must be between -1.0 and 1.0, exclusive".into());
+    }
+    Ok(Self::HenyeyGreenstein {
+      asymmetry: Asymmetry(asymmetry),
+    })
+  }
+  /// The probability density per steradian of turning by an angle whose cosine is `cos_theta`.
+  pub fn evaluate(self, cos_theta: f32) -> f32 {
+    match self {
+      Self::Isotropic => 1.0 / (4.0 * PI),
+      Self::HenyeyGreenstein { asymmetry } => {
+        let asymmetry = asymmetry.as_f32();
+        let denominator = 1.0 + asymmetry * asymmetry - 2.0 * asymmetry * cos_theta;
+        (1.0 - asymmetry * asymmetry) / (4.0 * PI * denominator * denominator.sqrt())
+      }
+    }
+  }
+  /// Picks an outgoing unit direction for light travelling along `direction`, distributed as `evaluate`.
+  pub fn sample(self, direction: Direction, random: &mut Random) -> Direction {
+    let asymmetry = match self {
+      Self::HenyeyGreenstein { asymmetry } if asymmetry.as_f32().abs() >= ISOTROPIC_ASYMMETRY_EPSILON => asymmetry.as_f32(),
+      _ => return random.unit_direction(),
+    };
+    let first = random.next_f32();
+    let fraction = (1.0 - asymmetry * asymmetry) / (1.0 - asymmetry + 2.0 * asymmetry * first);
+    let cos_theta = ((1.0 + asymmetry * asymmetry - fraction * fraction) / (2.0 * asymmetry)).clamp(-1.0, 1.0);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let angle = 2.0 * PI * random.next_f32();
+    OrthonormalBasis::new_from_w(direction).local_to_world(Direction::from_f32_array_const([
+      sin_theta * angle.cos(),
+      sin_theta * angle.sin(),
+      cos_theta,
+    ]))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{newtypes::direction::Direction, random::Random};
+
+  use super::PhaseFunction;
+
+  #[test]
+  fn rejects_degenerate_asymmetry() {
+    assert!(PhaseFunction::new_henyey_greenstein(1.0).is_err());
+    assert!(PhaseFunction::new_henyey_greenstein(-1.5).is_err());
+    assert!(PhaseFunction::new_henyey_greenstein(f32::NAN).is_err());
+  }
+  #[test]
+  fn henyey_greenstein_mean_cosine_is_asymmetry() {
+    let direction = Direction::from_f32_array_const([0.0, 1.0, 0.0]);
+    let mut random = Random::new(3);
+    for asymmetry in [-0.6, 0.0, 0.3, 0.8] {
+      let phase = PhaseFunction::new_henyey_greenstein(asymmetry).unwrap();
+      let samples: u16 = 20_000;
+      let total: f32 = (0..samples)
+        .map(|_| phase.sample(direction, &mut random).dot(direction))
+        .sum();
+      assert!((total / f32::from(samples) - asymmetry).abs() < 0.02);
+    }
+  }
+  #[test]
+  fn phase_functions_integrate_to_one() {
+    let steps: u16 = 10_000;
+    for phase in [PhaseFunction::Isotropic, PhaseFunction::new_henyey_greenstein(0.7).unwrap()] {
+      let integral: f32 = (0..steps)
+        .map(|step| {
+          let cos_theta = -1.0 + 2.0 * (f32::from(step) + 0.5) / f32::from(steps);
+          phase.evaluate(cos_theta) * 2.0 * std::f32::consts::PI * 2.0 / f32::from(steps)
+        })
+        .sum();
+      assert!((integral - 1.0).abs() < 1e-2);
+    }
+  }
+}
@@ -0,0 +1,245 @@
+use crate::{error::LocalError, inflate::zlib_decompress, rgb_image::RgbImage, vec3::Vec3};
+
+pub const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ColorType {
+  Gray,
+  Rgb,
+  Palette,
+  GrayAlpha,
+  RgbAlpha,
+}
+
+impl ColorType {
+  fn new(code: u8, bit_depth: u8) -> Result<Self, LocalError> {
+    let (color_type, allowed_depths): (Self, &[u8]) = match code {
+      0 => (Self::Gray, &[1, 2, 4, 8, 16]),
+      2 => (Self::Rgb, &[8, 16]),
+      3 => (Self::Palette, &[1, 2, 4, 8]),
+      4 => (Self::GrayAlpha, &[8, 16]),
+      6 => (Self::RgbAlpha, &[8, 16]),
+      _ => return Err(format!("Unknown PNG color type {}", code).into()),
+    };
+    if !allowed_depths.contains(&bit_depth) {
+      return Err(format!("PNG color type {} cannot have bit depth {}", code, bit_depth).into());
+    }
+    Ok(color_type)
+  }
+  const fn channels(self) -> usize {
+    match self {
+      Self::Gray | Self::Palette => 1,
+      Self::GrayAlpha => 2,
+      Self::Rgb => 3,
+      Self::RgbAlpha => 4,
+    }
+  }
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+  let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+  let distance_left = (estimate - i16::from(left)).abs();
+  let distance_up = (estimate - i16::from(up)).abs();
+  let distance_up_left = (estimate - i16::from(up_left)).abs();
+  if distance_left <= distance_up && distance_left <= distance_up_left {
+    left
+  } else if distance_up <= distance_up_left {
+    up
+  } else {
+    up_left
+  }
+}
+
+/// Reverses the per-scanline filters, returning the raw scanlines without their filter bytes.
+fn unfilter(data: &[u8], height: usize, stride: usize, bytes_per_pixel: usize) -> Result<Vec<u8>, LocalError> {
+  let scanlines_length = stride
+    .checked_add(1)
+    .and_then(|line_length| line_length.checked_mul(height))
+    .ok_or("The PNG image is too large")?;
+  if data.len() < scanlines_length {
+    return Err("The PNG image data ends early".into());
+  }
+  let mut output = vec![0_u8; height * stride];
+  for row in 0..height {
+    let filter = data[row * (stride + 1)];
+    let line = &data[row * (stride + 1) + 1..(row + 1) * (stride + 1)];
+    for index in 0..stride {
+      let left = if index >= bytes_per_pixel {
+        output[row * stride + index - bytes_per_pixel]
+      } else {
+        0
+      };
+      let up = if row > 0 { output[(row - 1) * stride + index] } else { 0 };
+      let up_left = if row > 0 && index >= bytes_per_pixel {
+        output[(row - 1) * stride + index - bytes_per_pixel]
+      } else {
+        0
+      };
+      let predictor = match filter {
+        0 => 0,
+        1 => left,
+        2 => up,
+        #[allow(clippy::cast_possible_truncation)]
+        3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+        4 => paeth(left, up, up_left),
+        _ => return Err(format!("Unknown PNG filter {}", filter).into()),
+      };
+      output[row * stride + index] = line[index].wrapping_add(predictor);
+    }
+  }
+  Ok(output)
+}
+
+/// Reads sample `index` of a scanline packed at `bit_depth` bits per sample, most significant bits first.
+fn sample(line: &[u8], index: usize, bit_depth: u8) -> u16 {
+  match bit_depth {
+    16 => u16::from_be_bytes([line[index * 2], line[index * 2 + 1]]),
+    8 => u16::from(line[index]),
+    _ => {
+      let bit_depth = usize::from(bit_depth);
+      let bit = index * bit_depth;
+      let shift = 8 - bit_depth - bit % 8;
+      u16::from((line[bit / 8] >> shift) & ((1 << bit_depth) - 1))
+    }
+  }
+}
+
+/// Decodes a non-interlaced PNG, ignoring any alpha channel.
+pub fn decode(bytes: &[u8]) -> Result<RgbImage, LocalError> {
+  if !bytes.starts_with(&SIGNATURE) {
+    return Err("The data is not a PNG file".into());
+  }
+  let mut position = SIGNATURE.len();
+  let mut header = None;
+  let mut palette = Vec::new();
+  let mut compressed = Vec::new();
+  while position + 8 <= bytes.len() {
+    let length = usize::try_from(u32::from_be_bytes([
+      bytes[position],
+      bytes[position + 1],
+      bytes[position + 2],
+      bytes[position + 3],
+    ]))?;
+    let kind = &bytes[position + 4..position + 8];
+    let body = (position + 8)
+      .checked_add(length)
+      .and_then(|end| bytes.get(position + 8..end))
+      .ok_or("A PNG chunk ends early")?;
+    match kind {
+      b"IHDR" => {
+        if body.len() < 13 {
+          return Err("The PNG header is too short".into());
+        }
+        let width = usize::try_from(u32::from_be_bytes([body[0], body[1], body[2], body[3]]))?;
+        let height = usize::try_from(u32::from_be_bytes([body[4], body[5], body[6], body[7]]))?;
+        let bit_depth = body[8];
+        let color_type = ColorType::new(body[9], bit_depth)?;
+        if width == 0 || height == 0 {
+          return Err("A PNG image needs at least one pixel".into());
+        }
+        if body[12] != 0 {
+          return Err("Interlaced PNG images are not supported".into());
+        }
+        header = Some((width, height, bit_depth, color_type));
+      }
+      b"PLTE" => {
+        palette = body
+          .chunks_exact(3)
+          .map(|entry| {
+            Vec3::new([
+              f32::from(entry[0]) / 255.0,
+              f32::from(entry[1]) / 255.0,
+              f32::from(entry[2]) / 255.0,
+            ])
+          })
+          .collect();
+      }
+      b"IDAT" => compressed.extend_from_slice(body),
+      b"IEND" => break,
+      _ => {}
+    }
+    position += length + 12;
+  }
+  let (width, height, bit_depth, color_type) = header.ok_or("The PNG file has no header")?;
+  let bits_per_pixel = color_type.channels() * usize::from(bit_depth);
+  let stride = width
+    .checked_mul(bits_per_pixel)
+    .and_then(|bits| bits.checked_add(7))
+    .ok_or("The PNG image is too large")?
+    / 8;
+  let data = unfilter(&zlib_decompress(&compressed)?, height, stride, (bits_per_pixel + 7) / 8)?;
+  let maximum = f32::from(u16::MAX >> (16 - bit_depth));
+  let mut pixels = Vec::with_capacity(width.checked_mul(height).ok_or("The PNG image is too large")?);
+  for line in data.chunks_exact(stride) {
+    for column in 0..width {
+      let channel = |offset: usize| f32::from(sample(line, column * color_type.channels() + offset, bit_depth)) / maximum;
+      pixels.push(match color_type {
+        ColorType::Gray | ColorType::GrayAlpha => Vec3::new([channel(0); 3]),
+        ColorType::Rgb | ColorType::RgbAlpha => Vec3::new([channel(0), channel(1), channel(2)]),
+        ColorType::Palette => *palette
+          .get(usize::from(sample(line, column, bit_depth)))
+          .ok_or("A PNG pixel refers past the end of its palette")?,
+      });
+    }
+  }
+  RgbImage::new(width, height, pixels)
+}
+
+#[cfg(test)]
+mod test {
+  use crate::vec3::Vec3;
+
+  use super::decode;
+
+  #[test]
+  fn decodes_filtered_rgb() {
+    let bytes = [
+      137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0, 253, 212, 154,
+      115, 0, 0, 0, 21, 73, 68, 65, 84, 120, 156, 99, 252, 207, 192, 192, 248, 159, 129, 133, 145, 225, 63, 144, 5, 0, 29,
+      43, 4, 4, 133, 154, 29, 5, 0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96, 130,
+    ];
+    let image = decode(&bytes).expect("This is a valid PNG");
+    assert_eq!((image.width(), image.height()), (2, 2));
+    assert_eq!(image.pixel(0, 0), Vec3::new([1.0, 0.0, 0.0]));
+    assert_eq!(image.pixel(1, 0), Vec3::new([0.0, 1.0, 0.0]));
+    assert_eq!(image.pixel(0, 1), Vec3::new([0.0, 0.0, 1.0]));
+    assert_eq!(image.pixel(1, 1), Vec3::new([1.0, 1.0, 1.0]));
+  }
+  #[test]
+  fn decodes_packed_palettes_and_sixteen_bit_gray() {
+    let bytes = [
+      137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 3, 0, 0, 0, 1, 1, 3, 0, 0, 0, 33, 46, 134, 247,
+      0, 0, 0, 6, 80, 76, 84, 69, 0, 0, 0, 255, 128, 0, 32, 124, 21, 105, 0, 0, 0, 10, 73, 68, 65, 84, 120, 156, 99, 88, 0,
+      0, 0, 162, 0, 161, 220, 141, 177, 204, 0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96, 130,
+    ];
+    let image = decode(&bytes).expect("This is a valid palette PNG");
+    assert_eq!(image.pixel(0, 0), Vec3::new([1.0, 128.0 / 255.0, 0.0]));
+    assert_eq!(image.pixel(1, 0), Vec3::new([0.0, 0.0, 0.0]));
+    assert_eq!(image.pixel(2, 0), Vec3::new([1.0, 128.0 / 255.0, 0.0]));
+    let bytes = [
+      137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 1, 0, 0, 0, 1, 16, 0, 0, 0, 0, 106, 238, 71,
+      22, 0, 0, 0, 11, 73, 68, 65, 84, 120, 156, 99, 106, 96, 0, 0, 1, 9, 0, 131, 159, 240, 38, 20, 0, 0, 0, 0, 73, 69, 78,
+      68, 174, 66, 96, 130,
+    ];
+    let image = decode(&bytes).expect("This is a valid sixteen bit PNG");
+    assert!((image.pixel(0, 0).x() - 0.5).abs() < 1e-4);
+  }
+  #[test]
+  fn rejects_truncated_files() {
+    assert!(decode(&[137, 80, 78, 71, 13, 10, 26, 10]).is_err());
+    assert!(decode(b"P6 1 1 255").is_err());
+  }
+  #[test]
+  fn rejects_sizes_that_do_not_fit() {
+    let file = |size: u8| {
+      let mut bytes = vec![137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82];
+      bytes.extend([size; 8]);
+      bytes.extend([16, 6, 0, 0, 0, 0, 0, 0, 0]);
+      bytes.extend([0, 0, 0, 11, 73, 68, 65, 84, 120, 1, 1, 0, 0, 255, 255, 0, 0, 0, 1, 0, 0, 0, 0]);
+      bytes.extend([0, 0, 0, 0, 73, 69, 78, 68, 0, 0, 0, 0]);
+      bytes
+    };
+    assert!(decode(&file(255)).is_err());
+    assert!(decode(&file(0)).is_err());
+  }
+}
@@ -0,0 +1,126 @@
+use std::{cmp::Ordering, f64::consts::PI};
+
+const ZERO_EPSILON: f64 = 1e-12;
+const POLISH_ITERATIONS: usize = 2;
+
+/// Real roots of `a x^2 + b x + c`, in ascending order.
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+  if a.abs() < ZERO_EPSILON {
+    if b.abs() < ZERO_EPSILON {
+      return Vec::new();
+    }
+    return vec![-c / b];
+  }
+  let discriminant = b * b - 4.0 * a * c;
+  if discriminant < 0.0 {
+    return Vec::new();
+  }
+  // Avoids the cancellation in `-b + sqrt(discriminant)` when `b` dominates.
+  let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+  let mut roots = if q.abs() < ZERO_EPSILON {
+    vec![0.0, 0.0]
+  } else {
+    vec![q / a, c / q]
+  };
+  roots.sort_by(|left, right| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+  roots
+}
+
+/// Real roots of `x^3 + a x^2 + b x + c`, in ascending order.
+#[allow(clippy::many_single_char_names)]
+pub fn solve_normalized_cubic(a: f64, b: f64, c: f64) -> Vec<f64> {
+  let shift = a / 3.0;
+  let p = b - a * a / 3.0;
+  let q = 2.0 * a * a * a / 27.0 - a * b / 3.0 + c;
+  let discriminant = q * q / 4.0 + p * p * p / 27.0;
+  let mut roots = if p.abs() < ZERO_EPSILON {
+    vec![(-q).cbrt()]
+  } else if discriminant > ZERO_EPSILON {
+    let root = discriminant.sqrt();
+    vec![(-q / 2.0 + root).cbrt() + (-q / 2.0 - root).cbrt()]
+  } else {
+    let magnitude = 2.0 * (-p / 3.0).sqrt();
+    let angle = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+    (0..3)
+      .map(|index| magnitude * (angle - 2.0 * PI * f64::from(index) / 3.0).cos())
+      .collect()
+  };
+  for root in &mut roots {
+    *root -= shift;
+  }
+  roots.sort_by(|left, right| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+  roots
+}
+
+/// Real roots of `a x^4 + b x^3 + c x^2 + d x + e` using Ferrari's method, in ascending order.
+#[allow(clippy::many_single_char_names)]
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+  if a.abs() < ZERO_EPSILON {
+    return solve_normalized_cubic(c / b, d / b, e / b);
+  }
+  let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+  let shift = b / 4.0;
+  let p = c - 3.0 * b * b / 8.0;
+  let q = d - b * c / 2.0 + b * b * b / 8.0;
+  let r = e - b * d / 4.0 + b * b * c / 16.0 - 3.0 * b * b * b * b / 256.0;
+  let mut roots = Vec::with_capacity(4);
+  if q.abs() < ZERO_EPSILON {
+    for square in solve_quadratic(1.0, p, r) {
+      if square >= 0.0 {
+        roots.push(square.sqrt());
+        roots.push(-square.sqrt());
+      }
+    }
+  } else {
+    let resolvent = solve_normalized_cubic(p, p * p / 4.0 - r, -q * q / 8.0);
+    let m = resolvent.into_iter().fold(f64::NEG_INFINITY, f64::max);
+    if m <= 0.0 {
+      return Vec::new();
+    }
+    let s = (2.0 * m).sqrt();
+    roots.extend(solve_quadratic(1.0, -s, p / 2.0 + m + q / (2.0 * s)));
+    roots.extend(solve_quadratic(1.0, s, p / 2.0 + m - q / (2.0 * s)));
+  }
+  for root in &mut roots {
+    *root -= shift;
+    for _ in 0..POLISH_ITERATIONS {
+      let value = (((*root + b) * *root + c) * *root + d) * *root + e;
+      let derivative = ((4.0 * *root + 3.0 * b) * *root + 2.0 * c) * *root + d;
+      if derivative.abs() > ZERO_EPSILON {
+        *root -= value / derivative;
+      }
+    }
+  }
+  roots.sort_by(|left, right| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+  roots
+}
+
+#[cfg(test)]
+mod test {
+  use super::{solve_normalized_cubic, solve_quadratic, solve_quartic};
+
+  fn assert_roots(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len(), "{:?} != {:?}", actual, expected);
+    for (actual, expected) in actual.iter().zip(expected) {
+      assert!((actual - expected).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+    }
+  }
+
+  #[test]
+  fn quadratic_roots() {
+    assert_roots(&solve_quadratic(1.0, -3.0, 2.0), &[1.0, 2.0]);
+    assert_roots(&solve_quadratic(1.0, 0.0, 1.0), &[]);
+    assert_roots(&solve_quadratic(0.0, 2.0, -4.0), &[2.0]);
+  }
+  #[test]
+  fn cubic_roots() {
+    assert_roots(&solve_normalized_cubic(-6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+    assert_roots(&solve_normalized_cubic(0.0, 0.0, -8.0), &[2.0]);
+  }
+  #[test]
+  fn quartic_roots() {
+    assert_roots(&solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0), &[1.0, 2.0, 3.0, 4.0]);
+    assert_roots(&solve_quartic(2.0, 0.0, -10.0, 0.0, 8.0), &[-2.0, -1.0, 1.0, 2.0]);
+    assert_roots(&solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0), &[]);
+  }
+}
@@ -0,0 +1,112 @@
+use crate::{newtypes::direction::Direction, transform::Matrix4};
+
+const SLERP_LINEAR_THRESHOLD: f32 = 0.9995;
+
+/// A unit quaternion used to interpolate rotations without the distortion of blending matrices.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Quaternion {
+  w: f32,
+  x: f32,
+  y: f32,
+  z: f32,
+}
+
+impl Default for Quaternion {
+  fn default() -> Self {
+    Self::identity()
+  }
+}
+
+impl Quaternion {
+  pub const fn identity() -> Self {
+    Self {
+      w: 1.0,
+      x: 0.0,
+      y: 0.0,
+      z: 0.0,
+    }
+  }
+  pub fn from_axis_angle(axis: Direction, angle_in_radians: f32) -> Self {
+    let axis = axis.unit_direction();
+    let (sine, cosine) = (angle_in_radians / 2.0).sin_cos();
+    Self {
+      w: cosine,
+      x: axis.x() * sine,
+      y: axis.y() * sine,
+      z: axis.z() * sine,
+    }
+  }
+  const fn dot(&self, other: &Self) -> f32 {
+    self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+  }
+  fn normalized(self) -> Self {
+    let length = self.dot(&self).sqrt();
+    Self {
+      w: self.w / length,
+      x: self.x / length,
+      y: self.y / length,
+      z: self.z / length,
+    }
+  }
+  /// Spherical interpolation along the shorter arc between `self` and `end`.
+  pub fn slerp(&self, end: &Self, amount: f32) -> Self {
+    let mut end = *end;
+    let mut cosine = self.dot(&end);
+    if cosine < 0.0 {
+      end = Self {
+        w: -end.w,
+        x: -end.x,
+        y: -end.y,
+        z: -end.z,
+      };
+      cosine = -cosine;
+    }
+    let (start_weight, end_weight) = if cosine > SLERP_LINEAR_THRESHOLD {
+      (1.0 - amount, amount)
+    } else {
+      let angle = cosine.acos();
+      let sine = angle.sin();
+      (((1.0 - amount) * angle).sin() / sine, (amount * angle).sin() / sine)
+    };
+    Self {
+      w: start_weight * self.w + end_weight * end.w,
+      x: start_weight * self.x + end_weight * end.x,
+      y: start_weight * self.y + end_weight * end.y,
+      z: start_weight * self.z + end_weight * end.z,
+    }
+    .normalized()
+  }
+  pub fn to_matrix(self) -> Matrix4 {
+    let Self { w, x, y, z } = self.normalized();
+    Matrix4::new([
+      [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y), 0.0],
+      [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x), 0.0],
+      [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y), 0.0],
+      [0.0, 0.0, 0.0, 1.0],
+    ])
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{newtypes::direction::Direction, transform::Transform};
+
+  use super::Quaternion;
+
+  #[test]
+  fn matches_axis_rotation() {
+    let axis = Direction::from_f32_array_const([1.0, 2.0, 3.0]);
+    let from_quaternion = Quaternion::from_axis_angle(axis, 0.8).to_matrix().rows();
+    let from_transform = Transform::rotation(axis, 0.8).matrix().rows();
+    for (left, right) in from_quaternion.iter().flatten().zip(from_transform.iter().flatten()) {
+      assert!((left - right).abs() < 1e-5);
+    }
+  }
+  #[test]
+  fn slerp_halfway_is_half_the_angle() {
+    let z_axis = Direction::from_f32_array_const([0.0, 0.0, 1.0]);
+    let halfway = Quaternion::identity().slerp(&Quaternion::from_axis_angle(z_axis, 2.0), 0.5);
+    let expected = Quaternion::from_axis_angle(z_axis, 1.0);
+    assert!((halfway.dot(&expected).abs() - 1.0).abs() < 1e-5);
+  }
+}
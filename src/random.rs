@@ -0,0 +1,103 @@
+use crate::newtypes::direction::Direction;
+
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+const INCREMENT: u64 = 1_442_695_040_888_963_407;
+const HASH_OFFSET_BASIS: u64 = 14_695_981_039_346_656_037;
+const HASH_PRIME: u64 = 1_099_511_628_211;
+
+/// A small PCG32 generator, so every render is reproducible from its seed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Random {
+  state: u64,
+}
+
+impl Default for Random {
+  fn default() -> Self {
+    Self::new(0)
+  }
+}
+
+impl Random {
+  pub fn new(seed: u64) -> Self {
+    let mut random = Self { state: 0 };
+    random.next_u32();
+    random.state = random.state.wrapping_add(seed);
+    random.next_u32();
+    random
+  }
+  /// Seeds from the bits of `values`, for code such as `Hittable::hit` that has no generator passed in
+  /// but must still give the same answer for the same query.
+  pub fn new_from_hash(values: &[f32]) -> Self {
+    let seed = values.iter().fold(HASH_OFFSET_BASIS, |hash, value| {
+      (hash ^ u64::from(value.to_bits())).wrapping_mul(HASH_PRIME)
+    });
+    Self::new(seed)
+  }
+  #[allow(clippy::cast_possible_truncation)]
+  pub fn next_u32(&mut self) -> u32 {
+    let previous = self.state;
+    self.state = previous.wrapping_mul(MULTIPLIER).wrapping_add(INCREMENT);
+    let xor_shifted = (((previous >> 18) ^ previous) >> 27) as u32;
+    let rotation = (previous >> 59) as u32;
+    xor_shifted.rotate_right(rotation)
+  }
+  /// A uniformly distributed value in `[0, 1)`.
+  #[allow(clippy::cast_precision_loss)]
+  pub fn next_f32(&mut self) -> f32 {
+    (self.next_u32() >> 8) as f32 / (1_u32 << 24) as f32
+  }
+  pub fn range_f32(&mut self, minimum: f32, maximum: f32) -> f32 {
+    minimum + (maximum - minimum) * self.next_f32()
+  }
+  pub fn in_unit_sphere(&mut self) -> Direction {
+    loop {
+      let candidate = Direction::from_f32_array_const([
+        self.range_f32(-1.0, 1.0),
+        self.range_f32(-1.0, 1.0),
+        self.range_f32(-1.0, 1.0),
+      ]);
+      if candidate.dot(candidate) < 1.0 {
+        return candidate;
+      }
+    }
+  }
+  pub fn unit_direction(&mut self) -> Direction {
+    let z = self.range_f32(-1.0, 1.0);
+    let angle = self.range_f32(0.0, 2.0 * std::f32::consts::PI);
+    let radius = (1.0 - z * z).max(0.0).sqrt();
+    Direction::from_f32_array_const([radius * angle.cos(), radius * angle.sin(), z])
+  }
+  pub fn in_unit_disk(&mut self) -> Direction {
+    loop {
+      let candidate = Direction::from_f32_array_const([self.range_f32(-1.0, 1.0), self.range_f32(-1.0, 1.0), 0.0]);
+      if candidate.dot(candidate) < 1.0 {
+        return candidate;
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::Random;
+
+  #[test]
+  fn same_seed_gives_same_sequence() {
+    let mut first = Random::new(42);
+    let mut second = Random::new(42);
+    let mut other = Random::new(43);
+    let first_values: Vec<u32> = (0..8).map(|_| first.next_u32()).collect();
+    let second_values: Vec<u32> = (0..8).map(|_| second.next_u32()).collect();
+    let other_values: Vec<u32> = (0..8).map(|_| other.next_u32()).collect();
+    assert_eq!(first_values, second_values);
+    assert_ne!(first_values, other_values);
+  }
+  #[test]
+  fn floats_stay_in_unit_interval() {
+    let mut random = Random::new(7);
+    let values: Vec<f32> = (0..10_000).map(|_| random.next_f32()).collect();
+    assert!(values.iter().all(|value| (0.0..1.0).contains(value)));
+    let mean = values.iter().sum::<f32>() / 10_000.0;
+    assert!((mean - 0.5).abs() < 0.02);
+  }
+}
@@ -5,18 +5,77 @@ use crate::{
     percent::Strict as StrictPercent,
     point::Point,
   },
-  sphere::Sphere,
+  shapes::sphere::Sphere,
 };
 
+/// Two auxiliary rays offset by one pixel in x and in y, tracking how wide a ray's footprint has grown.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct RayDifferentials {
+  x_origin: Point,
+  x_direction: Direction,
+  y_origin: Point,
+  y_direction: Direction,
+}
+
+impl RayDifferentials {
+  pub const fn new(x_origin: Point, x_direction: Direction, y_origin: Point, y_direction: Direction) -> Self {
+    Self {
+      x_origin,
+      x_direction,
+      y_origin,
+      y_direction,
+    }
+  }
+  pub const fn x_origin(&self) -> Point {
+    self.x_origin
+  }
+  pub const fn x_direction(&self) -> Direction {
+    self.x_direction
+  }
+  pub const fn y_origin(&self) -> Point {
+    self.y_origin
+  }
+  pub const fn y_direction(&self) -> Direction {
+    self.y_direction
+  }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Ray {
   origin: Point,
   direction: Direction,
+  time: f32,
+  differentials: Option<RayDifferentials>,
+  wavelength: Option<f32>,
 }
 
 impl Ray {
+  #[allow(dead_code)]
   pub const fn new(origin: Point, direction: Direction) -> Self {
-    Ray { origin, direction }
+    Self::new_at_time(origin, direction, 0.0)
+  }
+  pub const fn new_at_time(origin: Point, direction: Direction, time: f32) -> Self {
+    Ray {
+      origin,
+      direction,
+      time,
+      differentials: None,
+      wavelength: None,
+    }
+  }
+  pub const fn with_differentials(self, differentials: RayDifferentials) -> Self {
+    Self {
+      differentials: Some(differentials),
+      ..self
+    }
+  }
+  /// Tags the ray with the single wavelength, in nanometres, that a spectral path carries.
+  pub const fn with_wavelength(self, wavelength: f32) -> Self {
+    Self {
+      wavelength: Some(wavelength),
+      ..self
+    }
   }
   pub const fn origin(&self) -> Point {
     self.origin
@@ -24,9 +83,19 @@ impl Ray {
   pub const fn direction(&self) -> Direction {
     self.direction
   }
-  pub fn _at(&self, distance: f32) -> Point {
+  pub const fn time(&self) -> f32 {
+    self.time
+  }
+  pub const fn differentials(&self) -> Option<RayDifferentials> {
+    self.differentials
+  }
+  pub const fn wavelength(&self) -> Option<f32> {
+    self.wavelength
+  }
+  pub fn at(&self, distance: f32) -> Point {
     self.origin + (distance * self.direction).into()
   }
+  #[allow(dead_code)]
   pub fn find_color(&self, sphere: &Sphere) -> Rgb {
     if sphere.does_ray_hit_sphere(*self) {
       return Rgb::new([255.0, 0.0, 0.0]).expect("A red color");
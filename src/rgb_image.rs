@@ -0,0 +1,82 @@
+use std::{fs::File, io::Read, path::Path};
+
+use crate::{color_space::ColorSpace, error::LocalError, netpbm, png, vec3::Vec3};
+
+/// A decoded color image with channels in `[0, 1]`, stored row by row from the top. Pixels are in linear Rec.709
+/// unless tagged otherwise; image files are tagged as `sRGB`.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct RgbImage {
+  width: usize,
+  height: usize,
+  pixels: Vec<Vec3>,
+  color_space: ColorSpace,
+}
+
+impl RgbImage {
+  pub fn new(width: usize, height: usize, pixels: Vec<Vec3>) -> Result<Self, LocalError> {
+    if width == 0 || height == 0 {
+      return Err("An image needs at least one pixel".into());
+    }
+    if pixels.len() != width * height {
+      return Err(
+        format!(
+          "A {}x{} image needs {} pixels, not {}",
+          width,
+          height,
+          width * height,
+          pixels.len()
+        )
+        .into(),
+      );
+    }
+    Ok(Self {
+      width,
+      height,
+      pixels,
+      color_space: ColorSpace::default(),
+    })
+  }
+  /// Tags the pixels as measured in `color_space` without changing them.
+  pub fn with_color_space(self, color_space: ColorSpace) -> Self {
+    Self { color_space, ..self }
+  }
+  /// The same image with every pixel re-expressed in `color_space`.
+  pub fn converted_to(&self, color_space: ColorSpace) -> Self {
+    Self {
+      width: self.width,
+      height: self.height,
+      pixels: self
+        .pixels
+        .iter()
+        .map(|pixel| self.color_space.convert(*pixel, color_space))
+        .collect(),
+      color_space,
+    }
+  }
+  /// Reads PNG files and Netpbm pixmaps or graymaps, recognised by their leading bytes.
+  pub fn load(path: &Path) -> Result<Self, LocalError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    Self::parse(&bytes)
+  }
+  pub fn parse(bytes: &[u8]) -> Result<Self, LocalError> {
+    let image = if bytes.starts_with(&png::SIGNATURE) {
+      png::decode(bytes)?
+    } else {
+      netpbm::parse_pixmap(bytes)?
+    };
+    Ok(image.with_color_space(ColorSpace::Srgb))
+  }
+  pub const fn width(&self) -> usize {
+    self.width
+  }
+  pub const fn height(&self) -> usize {
+    self.height
+  }
+  pub const fn color_space(&self) -> ColorSpace {
+    self.color_space
+  }
+  pub fn pixel(&self, column: usize, row: usize) -> Vec3 {
+    self.pixels[row * self.width + column]
+  }
+}
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use crate::{
+  bvh::Bvh,
+  error::{LocalError, NodeNameAlreadyUsed, NodeNameIsNotValid},
+  hittable::Hittable,
+  instance::Instance,
+  materials::Material,
+  transform::Transform,
+};
+
+pub const PATH_SEPARATOR: char = '/';
+
+/// A named node whose transform is relative to its parent and whose material is inherited by children without one.
+#[derive(Debug, Clone)]
+pub struct SceneNode {
+  name: String,
+  transform: Transform,
+  material: Option<Arc<dyn Material>>,
+  geometry: Option<Arc<dyn Hittable>>,
+  children: Vec<SceneNode>,
+}
+
+impl SceneNode {
+  pub fn new(name: &str, transform: Transform) -> Result<Self, LocalError> {
+    if name.is_empty() || name.contains(PATH_SEPARATOR) {
+      return Err(Box::new(NodeNameIsNotValid(name.to_string())));
+    }
+    Ok(Self {
+      name: name.to_string(),
+      transform,
+      material: None,
+      geometry: None,
+      children: Vec::new(),
+    })
+  }
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+  pub const fn transform(&self) -> Transform {
+    self.transform
+  }
+  pub fn set_transform(&mut self, transform: Transform) {
+    self.transform = transform;
+  }
+  pub fn material(&self) -> Option<&Arc<dyn Material>> {
+    self.material.as_ref()
+  }
+  pub fn set_material(&mut self, material: Option<Arc<dyn Material>>) {
+    self.material = material;
+  }
+  pub fn geometry(&self) -> Option<&Arc<dyn Hittable>> {
+    self.geometry.as_ref()
+  }
+  pub fn set_geometry(&mut self, geometry: Option<Arc<dyn Hittable>>) {
+    self.geometry = geometry;
+  }
+  pub fn children(&self) -> &[SceneNode] {
+    &self.children
+  }
+  pub fn add_child(&mut self, child: SceneNode) -> Result<(), LocalError> {
+    if self.child(child.name()).is_some() {
+      return Err(Box::new(NodeNameAlreadyUsed(child.name)));
+    }
+    self.children.push(child);
+    Ok(())
+  }
+  pub fn remove_child(&mut self, name: &str) -> Option<SceneNode> {
+    let index = self.children.iter().position(|child| child.name() == name)?;
+    Some(self.children.remove(index))
+  }
+  pub fn child(&self, name: &str) -> Option<&SceneNode> {
+    self.children.iter().find(|child| child.name() == name)
+  }
+  /// Finds a descendant by a `/` separated path of names relative to this node; the empty path is this node.
+  pub fn find(&self, path: &str) -> Option<&SceneNode> {
+    path
+      .split(PATH_SEPARATOR)
+      .filter(|name| !name.is_empty())
+      .try_fold(self, SceneNode::child)
+  }
+  pub fn find_mut(&mut self, path: &str) -> Option<&mut SceneNode> {
+    path
+      .split(PATH_SEPARATOR)
+      .filter(|name| !name.is_empty())
+      .try_fold(self, |node, name| node.children.iter_mut().find(|child| child.name() == name))
+  }
+  /// The transform from the space of the node at `path` into the space this node sits in.
+  pub fn world_transform(&self, path: &str) -> Option<Transform> {
+    let mut node = self;
+    let mut transform = self.transform;
+    for name in path.split(PATH_SEPARATOR).filter(|name| !name.is_empty()) {
+      node = node.child(name)?;
+      transform = node.transform.then(&transform);
+    }
+    Some(transform)
+  }
+  /// Produces one instance per node with geometry, with transforms composed and materials inherited.
+  pub fn flatten(&self) -> Vec<Arc<dyn Hittable>> {
+    let mut objects = Vec::new();
+    self.collect(&Transform::identity(), None, &mut objects);
+    objects
+  }
+  pub fn build(&self) -> Bvh {
+    Bvh::new(self.flatten())
+  }
+  fn collect(
+    &self,
+    parent_transform: &Transform,
+    inherited_material: Option<&Arc<dyn Material>>,
+    objects: &mut Vec<Arc<dyn Hittable>>,
+  ) {
+    let transform = self.transform.then(parent_transform);
+    let material = self.material.as_ref().or(inherited_material);
+    if let Some(geometry) = &self.geometry {
+      objects.push(Arc::new(match material {
+        Some(material) => Instance::new_with_material(geometry.clone(), transform, material.clone()),
+        None => Instance::new(geometry.clone(), transform),
+      }));
+    }
+    for child in &self.children {
+      child.collect(&transform, material, objects);
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    materials::{lambertian::Lambertian, metal::Metal, Material},
+    newtypes::{direction::Direction, distance::Distance, percent::Strict as StrictPercent, point::Point},
+    ray::Ray,
+    shapes::sphere::Sphere,
+    transform::Transform,
+    vec3::Vec3,
+  };
+
+  use super::SceneNode;
+
+  fn same_material(left: &Arc<dyn Material>, right: &Arc<dyn Material>) -> bool {
+    std::ptr::eq(Arc::as_ptr(left).cast::<()>(), Arc::as_ptr(right).cast::<()>())
+  }
+
+  fn robot() -> SceneNode {
+    let sphere: Arc<dyn Hittable> = Arc::new(Sphere::new_const(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Distance::try_from_const(0.5).unwrap(),
+    ));
+    let mut root = SceneNode::new(
+      "robot",
+      Transform::translation(Direction::from_f32_array_const([10.0, 0.0, 0.0])),
+    )
+    .unwrap();
+    root.set_material(Some(Arc::new(Lambertian::new(Vec3::new([0.5, 0.5, 0.5])))));
+    let mut arm = SceneNode::new(
+      "arm",
+      Transform::translation(Direction::from_f32_array_const([0.0, 2.0, 0.0])),
+    )
+    .unwrap();
+    let mut hand = SceneNode::new(
+      "hand",
+      Transform::translation(Direction::from_f32_array_const([0.0, 0.0, 3.0])),
+    )
+    .unwrap();
+    hand.set_geometry(Some(sphere.clone()));
+    arm.set_geometry(Some(sphere));
+    arm.add_child(hand).unwrap();
+    root.add_child(arm).unwrap();
+    root
+  }
+
+  #[test]
+  fn finds_nodes_by_path() {
+    let robot = robot();
+    assert_eq!(robot.find("arm/hand").map(SceneNode::name), Some("hand"));
+    assert_eq!(robot.find("").map(SceneNode::name), Some("robot"));
+    assert!(robot.find("arm/foot").is_none());
+    let hand_transform = robot.world_transform("arm/hand").expect("The hand exists");
+    let hand_center = hand_transform.apply_to_point(Point::from_f32_array_const([0.0, 0.0, 0.0]));
+    assert_eq!(hand_center, Point::from_f32_array_const([10.0, 2.0, 3.0]));
+  }
+  #[test]
+  fn rejects_duplicate_and_invalid_names() {
+    let mut robot = robot();
+    let duplicate = SceneNode::new("arm", Transform::identity()).unwrap();
+    assert!(robot.add_child(duplicate).is_err());
+    assert!(SceneNode::new("arm/hand", Transform::identity()).is_err());
+    assert!(SceneNode::new("", Transform::identity()).is_err());
+  }
+  #[test]
+  fn flattened_world_composes_transforms_and_materials() {
+    let mut robot = robot();
+    let chrome: Arc<dyn Material> = Arc::new(Metal::new(Vec3::new([0.9, 0.9, 0.9]), StrictPercent::new(0.0).unwrap()));
+    robot
+      .find_mut("arm/hand")
+      .expect("The hand exists")
+      .set_material(Some(chrome.clone()));
+    let world = robot.build();
+    let toward_hand = Ray::new(
+      Point::from_f32_array_const([10.0, 2.0, 10.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = world
+      .hit(&toward_hand, 0.0, f32::INFINITY)
+      .expect("The ray points at the hand");
+    assert!((record.distance() - 6.5).abs() < 1e-5);
+    assert!(same_material(record.material().expect("The hand has a material"), &chrome));
+    let toward_arm = Ray::new(
+      Point::from_f32_array_const([10.0, 2.0, -10.0]),
+      Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+    );
+    let record = world.hit(&toward_arm, 0.0, f32::INFINITY).expect("The ray points at the arm");
+    assert!((record.distance() - 9.5).abs() < 1e-5);
+    assert!(!same_material(
+      record.material().expect("The arm inherits a material"),
+      &chrome
+    ));
+  }
+}
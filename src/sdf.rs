@@ -0,0 +1,404 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, point::Point},
+  ray::Ray,
+  vec3::Vec3,
+};
+
+/// A signed distance expression: negative inside, positive outside, zero on the surface.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Sdf {
+  Sphere {
+    radius: f32,
+  },
+  Cuboid {
+    half_extents: Vec3,
+  },
+  /// A ring lying in the xz plane around the y axis.
+  Torus {
+    major_radius: f32,
+    minor_radius: f32,
+  },
+  Translate {
+    offset: Direction,
+    shape: Box<Sdf>,
+  },
+  Union(Box<Sdf>, Box<Sdf>),
+  Intersection(Box<Sdf>, Box<Sdf>),
+  Difference(Box<Sdf>, Box<Sdf>),
+  SmoothUnion {
+    left: Box<Sdf>,
+    right: Box<Sdf>,
+    smoothness: f32,
+  },
+  /// Rotates each horizontal slice around the y axis by `radians_per_unit` times its height.
+  Twist {
+    shape: Box<Sdf>,
+    radians_per_unit: f32,
+  },
+  /// Tiles space with copies of `shape`; a zero period leaves that axis alone.
+  Repeat {
+    shape: Box<Sdf>,
+    period: Vec3,
+  },
+}
+
+fn length_of(x: f32, y: f32, z: f32) -> f32 {
+  (x * x + y * y + z * z).sqrt()
+}
+
+fn repeat_component(value: f32, period: f32) -> f32 {
+  if period == 0.0 {
+    return value;
+  }
+  value - period * (value / period).round()
+}
+
+impl Sdf {
+  pub const fn sphere(radius: f32) -> Self {
+    Self::Sphere { radius }
+  }
+  pub const fn cuboid(half_extents: Vec3) -> Self {
+    Self::Cuboid { half_extents }
+  }
+  pub const fn torus(major_radius: f32, minor_radius: f32) -> Self {
+    Self::Torus {
+      major_radius,
+      minor_radius,
+    }
+  }
+  pub fn translated(self, offset: Direction) -> Self {
+    Self::Translate {
+      offset,
+      shape: Box::new(self),
+    }
+  }
+  pub fn union(self, other: Self) -> Self {
+    Self::Union(Box::new(self), Box::new(other))
+  }
+  pub fn intersection(self, other: Self) -> Self {
+    Self::Intersection(Box::new(self), Box::new(other))
+  }
+  pub fn difference(self, other: Self) -> Self {
+    Self::Difference(Box::new(self), Box::new(other))
+  }
+  pub fn smooth_union(self, other: Self, smoothness: f32) -> Self {
+    Self::SmoothUnion {
+      left: Box::new(self),
+      right: Box::new(other),
+      smoothness,
+    }
+  }
+  pub fn twisted(self, radians_per_unit: f32) -> Self {
+    Self::Twist {
+      shape: Box::new(self),
+      radians_per_unit,
+    }
+  }
+  pub fn repeated(self, period: Vec3) -> Self {
+    Self::Repeat {
+      shape: Box::new(self),
+      period,
+    }
+  }
+  pub fn distance(&self, point: Point) -> f32 {
+    match self {
+      Self::Sphere { radius } => point.as_vec3().length().as_f32() - radius,
+      Self::Cuboid { half_extents } => {
+        let q = [
+          point.x().abs() - half_extents.x(),
+          point.y().abs() - half_extents.y(),
+          point.z().abs() - half_extents.z(),
+        ];
+        length_of(q[0].max(0.0), q[1].max(0.0), q[2].max(0.0)) + q[0].max(q[1]).max(q[2]).min(0.0)
+      }
+      Self::Torus {
+        major_radius,
+        minor_radius,
+      } => {
+        let ring_distance = (point.x() * point.x() + point.z() * point.z()).sqrt() - major_radius;
+        (ring_distance * ring_distance + point.y() * point.y()).sqrt() - minor_radius
+      }
+      Self::Translate { offset, shape } => shape.distance(point - (*offset).into()),
+      Self::Union(left, right) => left.distance(point).min(right.distance(point)),
+      Self::Intersection(left, right) => left.distance(point).max(right.distance(point)),
+      Self::Difference(left, right) => left.distance(point).max(-right.distance(point)),
+      Self::SmoothUnion { left, right, smoothness } => {
+        let left = left.distance(point);
+        let right = right.distance(point);
+        if *smoothness <= 0.0 {
+          return left.min(right);
+        }
+        let blend = (0.5 + 0.5 * (right - left) / smoothness).clamp(0.0, 1.0);
+        right + (left - right) * blend - smoothness * blend * (1.0 - blend)
+      }
+      Self::Twist { shape, radians_per_unit } => {
+        let (sine, cosine) = (radians_per_unit * point.y()).sin_cos();
+        shape.distance(Point::from_f32_array_const([
+          cosine * point.x() - sine * point.z(),
+          point.y(),
+          sine * point.x() + cosine * point.z(),
+        ]))
+      }
+      Self::Repeat { shape, period } => shape.distance(Point::from_f32_array_const([
+        repeat_component(point.x(), period.x()),
+        repeat_component(point.y(), period.y()),
+        repeat_component(point.z(), period.z()),
+      ])),
+    }
+  }
+  /// A box containing the surface, or `None` when it extends forever.
+  pub fn bounding_box(&self) -> Option<Aabb> {
+    let symmetric = |x: f32, y: f32, z: f32| {
+      Aabb::new(
+        Point::from_f32_array_const([-x, -y, -z]),
+        Point::from_f32_array_const([x, y, z]),
+      )
+    };
+    match self {
+      Self::Sphere { radius } => Some(symmetric(*radius, *radius, *radius)),
+      Self::Cuboid { half_extents } => Some(symmetric(half_extents.x(), half_extents.y(), half_extents.z())),
+      Self::Torus {
+        major_radius,
+        minor_radius,
+      } => {
+        let outer = major_radius + minor_radius;
+        Some(symmetric(outer, *minor_radius, outer))
+      }
+      Self::Translate { offset, shape } => {
+        let bounding_box = shape.bounding_box()?;
+        Some(Aabb::new(
+          bounding_box.minimum() + (*offset).into(),
+          bounding_box.maximum() + (*offset).into(),
+        ))
+      }
+      Self::Union(left, right) => Some(left.bounding_box()?.surrounding(&right.bounding_box()?)),
+      Self::Intersection(left, right) => match (left.bounding_box(), right.bounding_box()) {
+        (Some(left), Some(right)) => Some(left.overlap(&right)),
+        (Some(bounding_box), None) | (None, Some(bounding_box)) => Some(bounding_box),
+        (None, None) => None,
+      },
+      Self::Difference(left, _) => left.bounding_box(),
+      Self::SmoothUnion { left, right, smoothness } => {
+        let bounding_box = left.bounding_box()?.surrounding(&right.bounding_box()?);
+        let padding = Point::from_f32_array_const([*smoothness, *smoothness, *smoothness]);
+        Some(Aabb::new(bounding_box.minimum() - padding, bounding_box.maximum() + padding))
+      }
+      Self::Twist { shape, .. } => {
+        let bounding_box = shape.bounding_box()?;
+        let radius = length_of(
+          bounding_box.minimum().x().abs().max(bounding_box.maximum().x().abs()),
+          0.0,
+          bounding_box.minimum().z().abs().max(bounding_box.maximum().z().abs()),
+        );
+        Some(Aabb::new(
+          Point::from_f32_array_const([-radius, bounding_box.minimum().y(), -radius]),
+          Point::from_f32_array_const([radius, bounding_box.maximum().y(), radius]),
+        ))
+      }
+      Self::Repeat { .. } => None,
+    }
+  }
+  /// Estimates the outward normal from four samples on a tetrahedron around `point`.
+  pub fn normal(&self, point: Point, epsilon: f32) -> Direction {
+    [[1.0, -1.0, -1.0], [-1.0, -1.0, 1.0], [-1.0, 1.0, -1.0], [1.0, 1.0, 1.0]]
+      .into_iter()
+      .map(Direction::from_f32_array_const)
+      .fold(Direction::default(), |normal, offset| {
+        normal + offset * self.distance(point + (offset * epsilon).into())
+      })
+      .unit_direction()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MarchSettings {
+  epsilon: f32,
+  maximum_steps: usize,
+  step_scale: f32,
+}
+
+impl Default for MarchSettings {
+  fn default() -> Self {
+    Self {
+      epsilon: 1e-4,
+      maximum_steps: 256,
+      step_scale: 1.0,
+    }
+  }
+}
+
+impl MarchSettings {
+  /// `step_scale` below one keeps distorted fields such as twists from stepping through the surface.
+  pub const fn new(epsilon: f32, maximum_steps: usize, step_scale: f32) -> Self {
+    Self {
+      epsilon,
+      maximum_steps,
+      step_scale,
+    }
+  }
+  pub const fn epsilon(&self) -> f32 {
+    self.epsilon
+  }
+  pub const fn maximum_steps(&self) -> usize {
+    self.maximum_steps
+  }
+  pub const fn step_scale(&self) -> f32 {
+    self.step_scale
+  }
+}
+
+/// Renders an `Sdf` by sphere tracing, so it can sit in the world next to analytic shapes.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct SdfObject {
+  sdf: Sdf,
+  settings: MarchSettings,
+  bounding_box: Option<Aabb>,
+}
+
+impl SdfObject {
+  pub fn new(sdf: Sdf, settings: MarchSettings) -> Self {
+    let bounding_box = sdf.bounding_box().map(|bounding_box| {
+      let padding = Point::from_f32_array_const([settings.epsilon; 3]);
+      Aabb::new(bounding_box.minimum() - padding, bounding_box.maximum() + padding)
+    });
+    Self {
+      sdf,
+      settings,
+      bounding_box,
+    }
+  }
+  pub const fn sdf(&self) -> &Sdf {
+    &self.sdf
+  }
+  pub const fn settings(&self) -> MarchSettings {
+    self.settings
+  }
+}
+
+impl Hittable for SdfObject {
+  /// Marches from the start of the range, even behind the ray origin. Only an unbounded distance function with a range
+  /// starting at negative infinity is marched from the origin instead, as there is no finite place to begin.
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let (start, end) = match &self.bounding_box {
+      Some(bounding_box) => bounding_box.hit_interval(ray, minimum_distance, maximum_distance)?,
+      None => (minimum_distance, maximum_distance),
+    };
+    let direction_length = ray.direction().length().as_f32();
+    let unit_direction = ray.direction() / direction_length;
+    let mut travelled = if start.is_finite() { start } else { start.max(0.0) } * direction_length;
+    let end = end * direction_length;
+    for _ in 0..self.settings.maximum_steps {
+      if travelled > end {
+        return None;
+      }
+      let point = ray.origin() + (unit_direction * travelled).into();
+      let distance = self.sdf.distance(point).abs();
+      if distance < self.settings.epsilon {
+        let ray_distance = travelled / direction_length;
+        if ray_distance < minimum_distance {
+          travelled += self.settings.epsilon * 2.0;
+          continue;
+        }
+        let normal = self.sdf.normal(point, self.settings.epsilon);
+        return Some(HitRecord::new(ray, ray_distance, normal, [0.0, 0.0]));
+      }
+      travelled += distance * self.settings.step_scale;
+    }
+    None
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.bounding_box
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+    vec3::Vec3,
+  };
+
+  use super::{MarchSettings, Sdf, SdfObject};
+
+  fn toward_origin() -> Ray {
+    Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -2.0]),
+    )
+  }
+
+  #[test]
+  fn primitive_distances() {
+    let point = Point::from_f32_array_const([2.0, 0.0, 0.0]);
+    assert!((Sdf::sphere(1.0).distance(point) - 1.0).abs() < 1e-6);
+    assert!((Sdf::cuboid(Vec3::new([1.0, 1.0, 1.0])).distance(point) - 1.0).abs() < 1e-6);
+    assert!((Sdf::torus(2.0, 0.5).distance(point) + 0.5).abs() < 1e-6);
+    let inside = Point::from_f32_array_const([0.0, 0.0, 0.0]);
+    assert!((Sdf::cuboid(Vec3::new([1.0, 2.0, 3.0])).distance(inside) + 1.0).abs() < 1e-6);
+  }
+  #[test]
+  fn smooth_union_blends_below_minimum() {
+    let left = Sdf::sphere(1.0).translated(Direction::from_f32_array_const([-1.0, 0.0, 0.0]));
+    let right = Sdf::sphere(1.0).translated(Direction::from_f32_array_const([1.0, 0.0, 0.0]));
+    let point = Point::from_f32_array_const([0.0, 1.0, 0.0]);
+    let hard = left.clone().union(right.clone()).distance(point);
+    let smooth = left.smooth_union(right, 0.5).distance(point);
+    assert!(smooth < hard);
+  }
+  #[test]
+  fn sphere_tracing_matches_analytic_sphere() {
+    let object = SdfObject::new(Sdf::sphere(1.0), MarchSettings::default());
+    let record = object
+      .hit(&toward_origin(), 0.0, f32::INFINITY)
+      .expect("The ray points at the sphere");
+    assert!((record.distance() - 2.0).abs() < 1e-3);
+    assert!((record.normal().z() - 1.0).abs() < 1e-3);
+    assert!(object.hit(&toward_origin(), 0.0, 1.0).is_none());
+  }
+  #[test]
+  fn marching_covers_the_range_behind_the_origin() {
+    let object = SdfObject::new(Sdf::sphere(1.0), MarchSettings::default());
+    let from_center = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -2.0]),
+    );
+    let record = object
+      .hit(&from_center, f32::NEG_INFINITY, f32::INFINITY)
+      .expect("The sphere surrounds the origin");
+    assert!((record.distance() + 0.5).abs() < 1e-3);
+    assert!(record.front_face());
+  }
+  #[test]
+  fn repeat_and_twist_are_marched() {
+    let repeated = SdfObject::new(
+      Sdf::sphere(0.5).repeated(Vec3::new([4.0, 0.0, 0.0])),
+      MarchSettings::default(),
+    );
+    assert!(repeated.bounding_box().is_none());
+    let far_copy = Ray::new(
+      Point::from_f32_array_const([40.0, 0.0, 5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = repeated.hit(&far_copy, 0.0, f32::INFINITY).expect("Copies repeat along x");
+    assert!((record.distance() - 4.5).abs() < 1e-3);
+    let twisted = SdfObject::new(
+      Sdf::cuboid(Vec3::new([1.0, 2.0, 0.2])).twisted(0.5),
+      MarchSettings::new(1e-4, 512, 0.5),
+    );
+    assert!(twisted.hit(&toward_origin(), 0.0, f32::INFINITY).is_some());
+  }
+  #[test]
+  fn iteration_limit_stops_marching() {
+    let object = SdfObject::new(Sdf::sphere(1.0), MarchSettings::new(1e-6, 1, 1.0));
+    let grazing = Ray::new(
+      Point::from_f32_array_const([0.0, 0.99, 5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    assert!(object.hit(&grazing, 0.0, f32::INFINITY).is_none());
+  }
+}
@@ -0,0 +1,170 @@
+use crate::{
+  aabb::Aabb,
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  polynomial::solve_quadratic,
+  ray::Ray,
+  shapes::{azimuth_coordinate, nearest_in_range, sphere::Sphere},
+};
+
+/// The set of points within `radius` of the segment from `start` to `end`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Capsule {
+  start: Point,
+  end: Point,
+  radius: Distance,
+}
+
+impl Capsule {
+  /// Rejects a segment of zero length, which has no axis to orient the body and its coordinates around.
+  pub fn new(start: Point, end: Point, radius: Distance) -> Result<Self, LocalError> {
+    let segment = Direction::from(end - start);
+    let length_squared = segment.dot(segment);
+    if !(length_squared.is_finite() && length_squared > 0.0) {
+      return Err("A capsule's segment must have a positive length".into());
+    }
+    Ok(Self { start, end, radius })
+  }
+  pub const fn start(&self) -> Point {
+    self.start
+  }
+  pub const fn end(&self) -> Point {
+    self.end
+  }
+  pub const fn radius(&self) -> Distance {
+    self.radius
+  }
+  fn closest_point_on_segment(&self, point: Point) -> Point {
+    let segment = Direction::from(self.end - self.start);
+    let along = (Direction::from(point - self.start).dot(segment) / segment.dot(segment)).clamp(0.0, 1.0);
+    self.start + (segment * along).into()
+  }
+}
+
+impl Hittable for Capsule {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let segment = Direction::from(self.end - self.start);
+    let segment_length_squared = segment.dot(segment);
+    let radius = self.radius.as_f32();
+    let origin_offset = Direction::from(ray.origin() - self.start);
+    let segment_dot_direction = segment.dot(ray.direction());
+    let segment_dot_offset = segment.dot(origin_offset);
+    let mut candidates = Vec::with_capacity(6);
+    for root in solve_quadratic(
+      f64::from(
+        segment_length_squared * ray.direction().dot(ray.direction()) - segment_dot_direction * segment_dot_direction,
+      ),
+      f64::from(
+        2.0 * (segment_length_squared * ray.direction().dot(origin_offset) - segment_dot_offset * segment_dot_direction),
+      ),
+      f64::from(
+        segment_length_squared * origin_offset.dot(origin_offset)
+          - segment_dot_offset * segment_dot_offset
+          - radius * radius * segment_length_squared,
+      ),
+    ) {
+      #[allow(clippy::cast_possible_truncation)]
+      let distance = root as f32;
+      let along = segment_dot_offset + distance * segment_dot_direction;
+      if along > 0.0 && along < segment_length_squared {
+        candidates.push((distance, ()));
+      }
+    }
+    for (cap_center, keeps_side) in [(self.start, -1.0_f32), (self.end, 1.0_f32)] {
+      let cap = Sphere::new_const(cap_center, self.radius);
+      let mut cap_minimum_distance = minimum_distance;
+      while let Some(record) = cap.hit(ray, cap_minimum_distance, maximum_distance) {
+        if keeps_side * Direction::from(record.point() - cap_center).dot(segment) >= 0.0 {
+          candidates.push((record.distance(), ()));
+        }
+        cap_minimum_distance = record.distance() + f32::EPSILON * record.distance().abs().max(1.0);
+      }
+    }
+    let (distance, ()) = nearest_in_range(candidates, minimum_distance, maximum_distance)?;
+    let point = ray.at(distance);
+    let outward_normal = Direction::from(point - self.closest_point_on_segment(point)) / radius;
+    let local = OrthonormalBasis::new_from_w(segment).world_to_local((point - self.start).into());
+    let length = segment_length_squared.sqrt();
+    Some(HitRecord::new(
+      ray,
+      distance,
+      outward_normal,
+      [
+        azimuth_coordinate(local.x(), local.y()),
+        (local.z() + radius) / (length + 2.0 * radius),
+      ],
+    ))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let start = Sphere::new_const(self.start, self.radius).bounding_box()?;
+    let end = Sphere::new_const(self.end, self.radius).bounding_box()?;
+    Some(start.surrounding(&end))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::Capsule;
+
+  fn capsule() -> Capsule {
+    Capsule::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Point::from_f32_array_const([0.0, 2.0, 0.0]),
+      Distance::try_from_const(0.5).unwrap(),
+    )
+    .unwrap()
+  }
+
+  fn assert_near(actual: Direction, expected: [f32; 3]) {
+    let error = actual - Direction::from_f32_array_const(expected);
+    assert!(error.length().as_f32() < 1e-5, "{:?} != {:?}", actual, expected);
+  }
+
+  #[test]
+  fn ray_hits_body() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-5.0, 1.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    let record = capsule().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the body");
+    assert!((record.distance() - 4.5).abs() < 1e-5);
+    assert_near(record.normal(), [-1.0, 0.0, 0.0]);
+    assert!((record.v() - 0.5).abs() < 1e-5);
+  }
+  #[test]
+  fn ray_hits_hemispheres() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 3.0, 0.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = capsule().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the top");
+    assert!((record.distance() - 0.5).abs() < 1e-5);
+    assert_near(record.normal(), [0.0, 1.0, 0.0]);
+    let exit = capsule()
+      .hit(&ray, 1.0, f32::INFINITY)
+      .expect("The ray leaves through the bottom");
+    assert!((exit.distance() - 3.5).abs() < 1e-5);
+    assert!(!exit.front_face());
+  }
+  #[test]
+  fn rejects_zero_length_segments() {
+    let point = Point::from_f32_array_const([1.0, 2.0, 3.0]);
+    assert!(Capsule::new(point, point, Distance::try_from_const(0.5).unwrap()).is_err());
+  }
+  #[test]
+  fn ray_misses_beside_capsule() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-5.0, 3.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    assert!(capsule().hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+}
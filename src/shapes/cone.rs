@@ -0,0 +1,167 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  polynomial::solve_quadratic,
+  ray::Ray,
+  shapes::{azimuth_coordinate, disk::Disk, nearest_in_range},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Surface {
+  Side,
+  BaseCap,
+}
+
+/// A finite cone with its capped base on `base_center` and its apex `height` along `axis`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Cone {
+  base_center: Point,
+  basis: OrthonormalBasis,
+  radius: Distance,
+  height: Distance,
+}
+
+impl Cone {
+  pub fn new(base_center: Point, axis: Direction, radius: Distance, height: Distance) -> Self {
+    Self {
+      base_center,
+      basis: OrthonormalBasis::new_from_w(axis),
+      radius,
+      height,
+    }
+  }
+  pub const fn base_center(&self) -> Point {
+    self.base_center
+  }
+  pub const fn axis(&self) -> Direction {
+    self.basis.w()
+  }
+  pub const fn radius(&self) -> Distance {
+    self.radius
+  }
+  pub const fn height(&self) -> Distance {
+    self.height
+  }
+  pub fn apex(&self) -> Point {
+    self.base_center + (self.axis() * self.height.as_f32()).into()
+  }
+}
+
+impl Hittable for Cone {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let origin = self.basis.world_to_local((ray.origin() - self.base_center).into());
+    let direction = self.basis.world_to_local(ray.direction());
+    let radius = self.radius.as_f32();
+    let height = self.height.as_f32();
+    let slope_squared = (radius / height) * (radius / height);
+    let height_remaining = height - origin.z();
+    let mut candidates = Vec::with_capacity(3);
+    for root in solve_quadratic(
+      f64::from(
+        direction.x() * direction.x() + direction.y() * direction.y() - slope_squared * direction.z() * direction.z(),
+      ),
+      f64::from(
+        2.0 * (origin.x() * direction.x() + origin.y() * direction.y() + slope_squared * height_remaining * direction.z()),
+      ),
+      f64::from(origin.x() * origin.x() + origin.y() * origin.y() - slope_squared * height_remaining * height_remaining),
+    ) {
+      #[allow(clippy::cast_possible_truncation)]
+      let distance = root as f32;
+      let z = origin.z() + distance * direction.z();
+      if (0.0..=height).contains(&z) {
+        candidates.push((distance, Surface::Side));
+      }
+    }
+    if direction.z() != 0.0 {
+      let distance = -origin.z() / direction.z();
+      let x = origin.x() + distance * direction.x();
+      let y = origin.y() + distance * direction.y();
+      if x * x + y * y <= radius * radius {
+        candidates.push((distance, Surface::BaseCap));
+      }
+    }
+    let (distance, surface) = nearest_in_range(candidates, minimum_distance, maximum_distance)?;
+    let local_point = origin + direction * distance;
+    let (local_normal, surface_coordinates) = match surface {
+      Surface::Side => (
+        Direction::from_f32_array_const([local_point.x(), local_point.y(), slope_squared * (height - local_point.z())])
+          .unit_direction(),
+        [azimuth_coordinate(local_point.x(), local_point.y()), local_point.z() / height],
+      ),
+      Surface::BaseCap => (
+        Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+        [0.5 + local_point.x() / (2.0 * radius), 0.5 + local_point.y() / (2.0 * radius)],
+      ),
+    };
+    Some(HitRecord::new(
+      ray,
+      distance,
+      self.basis.local_to_world(local_normal),
+      surface_coordinates,
+    ))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let base = Disk::new(self.base_center, self.axis(), self.radius).bounding_box()?;
+    Some(base.surrounding(&Aabb::new(self.apex(), self.apex())))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::Cone;
+
+  fn cone() -> Cone {
+    Cone::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+      Distance::try_from_const(1.0).unwrap(),
+      Distance::try_from_const(1.0).unwrap(),
+    )
+  }
+
+  fn assert_near(actual: Direction, expected: [f32; 3]) {
+    let error = actual - Direction::from_f32_array_const(expected);
+    assert!(error.length().as_f32() < 1e-5, "{:?} != {:?}", actual, expected);
+  }
+
+  #[test]
+  fn ray_hits_slanted_side() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-5.0, 0.5, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    let record = cone().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the side");
+    assert!((record.distance() - 4.5).abs() < 1e-5);
+    assert_near(
+      record.normal(),
+      [-std::f32::consts::FRAC_1_SQRT_2, std::f32::consts::FRAC_1_SQRT_2, 0.0],
+    );
+    assert!((record.v() - 0.5).abs() < 1e-5);
+  }
+  #[test]
+  fn ray_hits_base_cap() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, -2.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+    );
+    let record = cone().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the base");
+    assert!((record.distance() - 2.0).abs() < 1e-5);
+    assert_near(record.normal(), [0.0, -1.0, 0.0]);
+  }
+  #[test]
+  fn ray_misses_above_apex() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-5.0, 1.5, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    assert!(cone().hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  hittable_list::HittableList,
+  newtypes::{direction::Direction, point::Point},
+  ray::Ray,
+  shapes::quad::Quad,
+};
+
+/// A box made of six quads whose normals all point outwards.
+#[derive(Debug, Default, Clone)]
+pub struct Cuboid {
+  sides: HittableList,
+}
+
+impl Cuboid {
+  pub fn new_from_corners(corner_1: Point, corner_2: Point) -> Self {
+    let minimum = corner_1.as_vec3().minimum(corner_2.as_vec3());
+    let extent = corner_1.as_vec3().maximum(corner_2.as_vec3()) - minimum;
+    Self::new_from_edges(
+      minimum.into(),
+      Direction::from_f32_array_const([extent.x(), 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, extent.y(), 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, extent.z()]),
+    )
+  }
+  /// A parallelepiped with one corner at `corner`, spanned by three edges in either winding.
+  pub fn new_from_edges(corner: Point, edge_1: Direction, edge_2: Direction, edge_3: Direction) -> Self {
+    let (a, b, c) = if edge_1.cross(edge_2).dot(edge_3) < 0.0 {
+      (edge_2, edge_1, edge_3)
+    } else {
+      (edge_1, edge_2, edge_3)
+    };
+    let mut sides = HittableList::new();
+    sides.add(Arc::new(Quad::new(corner, b, a)));
+    sides.add(Arc::new(Quad::new(corner + c.into(), a, b)));
+    sides.add(Arc::new(Quad::new(corner, c, b)));
+    sides.add(Arc::new(Quad::new(corner + a.into(), b, c)));
+    sides.add(Arc::new(Quad::new(corner, a, c)));
+    sides.add(Arc::new(Quad::new(corner + b.into(), c, a)));
+    Self { sides }
+  }
+}
+
+impl Hittable for Cuboid {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    self.sides.hit(ray, minimum_distance, maximum_distance)
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.sides.bounding_box()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+  };
+
+  use super::Cuboid;
+
+  #[test]
+  fn every_face_normal_points_outwards() {
+    let cuboid = Cuboid::new_from_corners(
+      Point::from_f32_array_const([1.0, 1.0, 1.0]),
+      Point::from_f32_array_const([-1.0, -1.0, -1.0]),
+    );
+    for direction in [
+      [1.0, 0.0, 0.0],
+      [-1.0, 0.0, 0.0],
+      [0.0, 1.0, 0.0],
+      [0.0, -1.0, 0.0],
+      [0.0, 0.0, 1.0],
+      [0.0, 0.0, -1.0],
+    ] {
+      let direction = Direction::from_f32_array_const(direction);
+      let ray = Ray::new(Point::from(-3.0 * direction), direction);
+      let record = cuboid.hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the box");
+      assert!(record.distance().eq(&2.0));
+      assert!(record.front_face());
+      assert_eq!(record.normal(), -direction);
+    }
+  }
+  #[test]
+  fn bounding_box_matches_corners() {
+    let cuboid = Cuboid::new_from_edges(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 2.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, 3.0]),
+    );
+    let bounding_box = cuboid.bounding_box().expect("A box is bounded");
+    let minimum_error = bounding_box.minimum() - Point::from_f32_array_const([0.0, 0.0, 0.0]);
+    let maximum_error = bounding_box.maximum() - Point::from_f32_array_const([1.0, 2.0, 3.0]);
+    assert!(minimum_error.as_vec3().length().as_f32() < 1e-3);
+    assert!(maximum_error.as_vec3().length().as_f32() < 1e-3);
+  }
+}
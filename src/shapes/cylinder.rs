@@ -0,0 +1,182 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  polynomial::solve_quadratic,
+  ray::Ray,
+  shapes::{azimuth_coordinate, disk::Disk, nearest_in_range},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Surface {
+  Side,
+  BaseCap,
+  TopCap,
+}
+
+/// A finite cylinder standing on `base_center`, capped at both ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Cylinder {
+  base_center: Point,
+  basis: OrthonormalBasis,
+  radius: Distance,
+  height: Distance,
+}
+
+impl Cylinder {
+  pub fn new(base_center: Point, axis: Direction, radius: Distance, height: Distance) -> Self {
+    Self {
+      base_center,
+      basis: OrthonormalBasis::new_from_w(axis),
+      radius,
+      height,
+    }
+  }
+  pub const fn base_center(&self) -> Point {
+    self.base_center
+  }
+  pub const fn axis(&self) -> Direction {
+    self.basis.w()
+  }
+  pub const fn radius(&self) -> Distance {
+    self.radius
+  }
+  pub const fn height(&self) -> Distance {
+    self.height
+  }
+  fn top_center(&self) -> Point {
+    self.base_center + (self.axis() * self.height.as_f32()).into()
+  }
+}
+
+impl Hittable for Cylinder {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let origin = self.basis.world_to_local((ray.origin() - self.base_center).into());
+    let direction = self.basis.world_to_local(ray.direction());
+    let radius = self.radius.as_f32();
+    let height = self.height.as_f32();
+    let mut candidates = Vec::with_capacity(4);
+    for root in solve_quadratic(
+      f64::from(direction.x() * direction.x() + direction.y() * direction.y()),
+      f64::from(2.0 * (origin.x() * direction.x() + origin.y() * direction.y())),
+      f64::from(origin.x() * origin.x() + origin.y() * origin.y() - radius * radius),
+    ) {
+      #[allow(clippy::cast_possible_truncation)]
+      let distance = root as f32;
+      let z = origin.z() + distance * direction.z();
+      if (0.0..=height).contains(&z) {
+        candidates.push((distance, Surface::Side));
+      }
+    }
+    if direction.z() != 0.0 {
+      for (cap_height, surface) in [(0.0, Surface::BaseCap), (height, Surface::TopCap)] {
+        let distance = (cap_height - origin.z()) / direction.z();
+        let x = origin.x() + distance * direction.x();
+        let y = origin.y() + distance * direction.y();
+        if x * x + y * y <= radius * radius {
+          candidates.push((distance, surface));
+        }
+      }
+    }
+    let (distance, surface) = nearest_in_range(candidates, minimum_distance, maximum_distance)?;
+    let local_point = origin + direction * distance;
+    let (local_normal, surface_coordinates) = match surface {
+      Surface::Side => (
+        Direction::from_f32_array_const([local_point.x() / radius, local_point.y() / radius, 0.0]),
+        [azimuth_coordinate(local_point.x(), local_point.y()), local_point.z() / height],
+      ),
+      Surface::BaseCap => (
+        Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+        [0.5 + local_point.x() / (2.0 * radius), 0.5 + local_point.y() / (2.0 * radius)],
+      ),
+      Surface::TopCap => (
+        Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+        [0.5 + local_point.x() / (2.0 * radius), 0.5 + local_point.y() / (2.0 * radius)],
+      ),
+    };
+    Some(HitRecord::new(
+      ray,
+      distance,
+      self.basis.local_to_world(local_normal),
+      surface_coordinates,
+    ))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let base = Disk::new(self.base_center, self.axis(), self.radius).bounding_box()?;
+    let top = Disk::new(self.top_center(), self.axis(), self.radius).bounding_box()?;
+    Some(base.surrounding(&top))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::Cylinder;
+
+  fn cylinder() -> Cylinder {
+    Cylinder::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+      Distance::try_from_const(1.0).unwrap(),
+      Distance::try_from_const(2.0).unwrap(),
+    )
+  }
+
+  fn assert_near(actual: Direction, expected: [f32; 3]) {
+    let error = actual - Direction::from_f32_array_const(expected);
+    assert!(error.length().as_f32() < 1e-5, "{:?} != {:?}", actual, expected);
+  }
+
+  #[test]
+  fn ray_hits_side() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 1.0, -5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+    );
+    let record = cylinder().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the side");
+    assert!((record.distance() - 4.0).abs() < 1e-5);
+    assert_near(record.normal(), [0.0, 0.0, -1.0]);
+    assert!((record.v() - 0.5).abs() < 1e-5);
+    let exit = cylinder()
+      .hit(&ray, 4.5, f32::INFINITY)
+      .expect("The ray leaves through the side");
+    assert!((exit.distance() - 6.0).abs() < 1e-5);
+    assert!(!exit.front_face());
+  }
+  #[test]
+  fn ray_hits_caps() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.5, 5.0, 0.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = cylinder()
+      .hit(&ray, 0.0, f32::INFINITY)
+      .expect("The ray points at the top cap");
+    assert!((record.distance() - 3.0).abs() < 1e-5);
+    assert_near(record.normal(), [0.0, 1.0, 0.0]);
+    let exit = cylinder()
+      .hit(&ray, 3.5, f32::INFINITY)
+      .expect("The ray leaves through the base cap");
+    assert!((exit.distance() - 5.0).abs() < 1e-5);
+  }
+  #[test]
+  fn ray_misses_beyond_height() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 2.5, -5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+    );
+    assert!(cylinder().hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+  #[test]
+  fn bounding_box_covers_both_caps() {
+    let bounding_box = cylinder().bounding_box().expect("A cylinder is bounded");
+    assert!((bounding_box.minimum().x() + 1.0).abs() < 1e-5);
+    assert!((bounding_box.maximum().y() - 2.0).abs() < 1e-3);
+  }
+}
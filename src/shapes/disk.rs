@@ -0,0 +1,112 @@
+use std::f32::consts::PI;
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  ray::Ray,
+};
+
+const PARALLEL_EPSILON: f32 = 1e-8;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Disk {
+  center: Point,
+  radius: Distance,
+  basis: OrthonormalBasis,
+}
+
+impl Disk {
+  pub fn new(center: Point, normal: Direction, radius: Distance) -> Self {
+    Self {
+      center,
+      radius,
+      basis: OrthonormalBasis::new_from_w(normal),
+    }
+  }
+  pub const fn center(&self) -> Point {
+    self.center
+  }
+  pub const fn radius(&self) -> Distance {
+    self.radius
+  }
+  pub const fn normal(&self) -> Direction {
+    self.basis.w()
+  }
+}
+
+impl Hittable for Disk {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let denominator = self.normal().dot(ray.direction());
+    if denominator.abs() < PARALLEL_EPSILON {
+      return None;
+    }
+    let distance = self.normal().dot((self.center - ray.origin()).into()) / denominator;
+    if distance < minimum_distance || distance > maximum_distance {
+      return None;
+    }
+    let planar_offset = Direction::from(ray.at(distance) - self.center);
+    let radius_squared = planar_offset.dot(planar_offset);
+    if radius_squared > self.radius.as_f32() * self.radius.as_f32() {
+      return None;
+    }
+    let angle = planar_offset.dot(self.basis.v()).atan2(planar_offset.dot(self.basis.u()));
+    Some(HitRecord::new(
+      ray,
+      distance,
+      self.normal(),
+      [(angle + PI) / (2.0 * PI), radius_squared.sqrt() / self.radius.as_f32()],
+    ))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let normal = self.normal();
+    let half_extent = |component: f32| self.radius.as_f32() * (1.0 - component * component).max(0.0).sqrt();
+    let extent = Point::from_f32_array_const([half_extent(normal.x()), half_extent(normal.y()), half_extent(normal.z())]);
+    Some(Aabb::new(self.center - extent, self.center + extent).padded())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::Disk;
+
+  fn disk() -> Disk {
+    Disk::new(
+      Point::from_f32_array_const([0.0, 0.0, -1.0]),
+      Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+      Distance::try_from_const(1.0).unwrap(),
+    )
+  }
+
+  #[test]
+  fn ray_hits_inside_radius() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.5, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = disk().hit(&ray, 0.0, f32::INFINITY).expect("The ray points inside the disk");
+    assert!(record.distance().eq(&1.0));
+    assert!(record.v().eq(&0.5));
+  }
+  #[test]
+  fn ray_misses_outside_radius() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.8, 0.8, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    assert!(disk().hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+  #[test]
+  fn bounding_box_is_tight() {
+    let bounding_box = disk().bounding_box().expect("A disk is bounded");
+    assert!(bounding_box.minimum().x().eq(&-1.0));
+    assert!(bounding_box.maximum().y().eq(&1.0));
+  }
+}
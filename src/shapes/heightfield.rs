@@ -0,0 +1,341 @@
+use crate::{
+  aabb::Aabb,
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  netpbm::GrayImage,
+  newtypes::{direction::Direction, point::Point},
+  ray::Ray,
+  shapes::triangle::intersect,
+};
+
+const CELL_EPSILON: f32 = 1e-5;
+
+/// Heights sampled on a regular grid, stored row by row.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct HeightGrid {
+  columns: usize,
+  rows: usize,
+  heights: Vec<f32>,
+}
+
+impl HeightGrid {
+  pub fn new(columns: usize, rows: usize, heights: Vec<f32>) -> Result<Self, LocalError> {
+    if columns < 2 || rows < 2 {
+      return Err("A height grid needs at least two columns and two rows".into());
+    }
+    let count = columns
+      .checked_mul(rows)
+      .ok_or_else(|| format!("A {}x{} height grid is too large", columns, rows))?;
+    if heights.len() != count {
+      return Err(format!("Expected {} heights but got {}", count, heights.len()).into());
+    }
+    Ok(Self { columns, rows, heights })
+  }
+  /// Uses the image's intensity in `[0, 1]` as the height, with the top row of the image at the far edge.
+  pub fn new_from_image(image: &GrayImage) -> Result<Self, LocalError> {
+    let heights = (0..image.height())
+      .flat_map(|row| (0..image.width()).map(move |column| (column, row)))
+      .map(|(column, row)| image.intensity(column, row))
+      .collect();
+    Self::new(image.width(), image.height(), heights)
+  }
+  pub const fn columns(&self) -> usize {
+    self.columns
+  }
+  pub const fn rows(&self) -> usize {
+    self.rows
+  }
+  pub fn height(&self, column: usize, row: usize) -> f32 {
+    self.heights[row * self.columns + column]
+  }
+}
+
+/// Terrain spanning `extent.x()` by `extent.z()` from `corner`, with grid heights scaled by `extent.y()`.
+/// Rays walk the grid cell by cell and skip cells whose height range they cannot touch.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct Heightfield {
+  grid: HeightGrid,
+  corner: Point,
+  extent: Direction,
+  cell_ranges: Vec<(f32, f32)>,
+  normals: Vec<Direction>,
+  bounding_box: Aabb,
+}
+
+impl Heightfield {
+  pub fn new(grid: HeightGrid, corner: Point, extent: Direction) -> Self {
+    let mut heightfield = Self {
+      grid,
+      corner,
+      extent,
+      ..Self::default()
+    };
+    let (columns, rows) = (heightfield.grid.columns(), heightfield.grid.rows());
+    heightfield.cell_ranges = (0..rows - 1)
+      .flat_map(|row| (0..columns - 1).map(move |column| (column, row)))
+      .map(|(column, row)| {
+        let heights = [
+          heightfield.vertex(column, row).y(),
+          heightfield.vertex(column + 1, row).y(),
+          heightfield.vertex(column, row + 1).y(),
+          heightfield.vertex(column + 1, row + 1).y(),
+        ];
+        (
+          heights.iter().copied().fold(f32::INFINITY, f32::min),
+          heights.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+        )
+      })
+      .collect();
+    heightfield.normals = (0..rows)
+      .flat_map(|row| (0..columns).map(move |column| (column, row)))
+      .map(|(column, row)| heightfield.vertex_normal(column, row))
+      .collect();
+    let vertices: Vec<Point> = (0..rows)
+      .flat_map(|row| (0..columns).map(move |column| (column, row)))
+      .map(|(column, row)| heightfield.vertex(column, row))
+      .collect();
+    heightfield.bounding_box = Aabb::new_from_points(&vertices).expect("A height grid has vertices").padded();
+    heightfield
+  }
+  pub const fn grid(&self) -> &HeightGrid {
+    &self.grid
+  }
+  #[allow(clippy::cast_precision_loss)]
+  fn cell_size(&self) -> (f32, f32) {
+    (
+      self.extent.x() / (self.grid.columns() - 1) as f32,
+      self.extent.z() / (self.grid.rows() - 1) as f32,
+    )
+  }
+  #[allow(clippy::cast_precision_loss)]
+  pub fn vertex(&self, column: usize, row: usize) -> Point {
+    let (cell_width, cell_depth) = self.cell_size();
+    self.corner
+      + Point::from_f32_array_const([
+        column as f32 * cell_width,
+        self.grid.height(column, row) * self.extent.y(),
+        row as f32 * cell_depth,
+      ])
+  }
+  fn vertex_normal(&self, column: usize, row: usize) -> Direction {
+    let (cell_width, cell_depth) = self.cell_size();
+    let left = column.saturating_sub(1);
+    let right = (column + 1).min(self.grid.columns() - 1);
+    let near = row.saturating_sub(1);
+    let far = (row + 1).min(self.grid.rows() - 1);
+    #[allow(clippy::cast_precision_loss)]
+    let slope_x = (self.vertex(right, row).y() - self.vertex(left, row).y()) / ((right - left) as f32 * cell_width);
+    #[allow(clippy::cast_precision_loss)]
+    let slope_z = (self.vertex(column, far).y() - self.vertex(column, near).y()) / ((far - near) as f32 * cell_depth);
+    Direction::from_f32_array_const([-slope_x, 1.0, -slope_z]).unit_direction()
+  }
+  fn normal_at(&self, column: usize, row: usize) -> Direction {
+    self.normals[row * self.grid.columns() + column]
+  }
+  /// The nearest hit in a cell, with the flat triangle normal and the smooth interpolated vertex normal.
+  fn hit_cell(&self, ray: &Ray, column: usize, row: usize) -> Option<(f32, Direction, Direction)> {
+    let corners = [(column, row), (column + 1, row + 1), (column + 1, row), (column, row + 1)];
+    [[0, 1, 2], [0, 3, 1]]
+      .into_iter()
+      .filter_map(|triangle| {
+        let indices = triangle.map(|index| corners[index]);
+        let vertices = indices.map(|(column, row)| self.vertex(column, row));
+        let (distance, [weight_1, weight_2]) = intersect(ray, &vertices)?;
+        let normals = indices.map(|(column, row)| self.normal_at(column, row));
+        let shading_normal =
+          (normals[0] * (1.0 - weight_1 - weight_2) + normals[1] * weight_1 + normals[2] * weight_2).unit_direction();
+        let geometric_normal = Direction::from(vertices[1] - vertices[0])
+          .cross((vertices[2] - vertices[0]).into())
+          .unit_direction();
+        Some((distance, geometric_normal, shading_normal))
+      })
+      .min_by(|(left, _, _), (right, _, _)| left.partial_cmp(right).unwrap_or(std::cmp::Ordering::Equal))
+  }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+fn starting_cell(position: f32, cell_size: f32, cell_count: usize) -> usize {
+  ((position / cell_size).floor().max(0.0) as usize).min(cell_count - 1)
+}
+
+impl Hittable for Heightfield {
+  #[allow(clippy::cast_precision_loss, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let (entry, exit) = self.bounding_box.hit_interval(ray, minimum_distance, maximum_distance)?;
+    let (cell_width, cell_depth) = self.cell_size();
+    let cell_columns = self.grid.columns() - 1;
+    let cell_rows = self.grid.rows() - 1;
+    let entry_offset = Direction::from(ray.at(entry) - self.corner);
+    let mut column = starting_cell(entry_offset.x(), cell_width, cell_columns) as isize;
+    let mut row = starting_cell(entry_offset.z(), cell_depth, cell_rows) as isize;
+    let direction = ray.direction();
+    let origin_offset = Direction::from(ray.origin() - self.corner);
+    let axis_setup = |component: f32, origin: f32, cell: isize, size: f32| {
+      if component == 0.0 {
+        return (0, f32::INFINITY, f32::INFINITY);
+      }
+      let step: isize = if component > 0.0 { 1 } else { -1 };
+      let boundary = (cell + isize::from(component > 0.0)) as f32 * size;
+      (step, (boundary - origin) / component, size / component.abs())
+    };
+    let (step_column, mut next_column, delta_column) = axis_setup(direction.x(), origin_offset.x(), column, cell_width);
+    let (step_row, mut next_row, delta_row) = axis_setup(direction.z(), origin_offset.z(), row, cell_depth);
+    let mut cell_entry = entry;
+    while (0..cell_columns as isize).contains(&column) && (0..cell_rows as isize).contains(&row) && cell_entry <= exit {
+      let cell_exit = next_column.min(next_row).min(exit);
+      let (cell_column, cell_row) = (column as usize, row as usize);
+      let (lowest, highest) = self.cell_ranges[cell_row * cell_columns + cell_column];
+      let entry_height = ray.at(cell_entry).y();
+      let exit_height = ray.at(cell_exit).y();
+      if entry_height.min(exit_height) <= highest + CELL_EPSILON && entry_height.max(exit_height) >= lowest - CELL_EPSILON {
+        if let Some((distance, geometric_normal, shading_normal)) = self.hit_cell(ray, cell_column, cell_row) {
+          if distance >= minimum_distance && distance <= maximum_distance && distance <= cell_exit + CELL_EPSILON {
+            let offset = Direction::from(ray.at(distance) - self.corner);
+            let record = HitRecord::new(
+              ray,
+              distance,
+              geometric_normal,
+              [offset.x() / self.extent.x(), offset.z() / self.extent.z()],
+            );
+            return Some(
+              record
+                .with_shading_frame(
+                  shading_normal,
+                  Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+                  Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+                )
+                .with_surface_derivatives(
+                  Direction::from_f32_array_const([self.extent.x(), 0.0, 0.0]),
+                  Direction::from_f32_array_const([0.0, 0.0, self.extent.z()]),
+                ),
+            );
+          }
+        }
+      }
+      if next_column < next_row {
+        column += step_column;
+        cell_entry = next_column;
+        next_column += delta_column;
+      } else {
+        row += step_row;
+        cell_entry = next_row;
+        next_row += delta_row;
+      }
+    }
+    None
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(self.bounding_box)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    hittable_list::HittableList,
+    netpbm::GrayImage,
+    newtypes::{direction::Direction, point::Point},
+    random::Random,
+    ray::Ray,
+    shapes::triangle::Triangle,
+  };
+
+  use super::{HeightGrid, Heightfield};
+
+  #[test]
+  fn flat_terrain_acts_like_a_floor() {
+    let grid = HeightGrid::new(3, 3, vec![0.5; 9]).unwrap();
+    let heightfield = Heightfield::new(
+      grid,
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([4.0, 2.0, 4.0]),
+    );
+    let ray = Ray::new(
+      Point::from_f32_array_const([1.0, 5.0, 3.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = heightfield
+      .hit(&ray, 0.0, f32::INFINITY)
+      .expect("The ray points at the terrain");
+    assert!((record.distance() - 4.0).abs() < 1e-5);
+    assert!((record.normal().y() - 1.0).abs() < 1e-5);
+    assert!((record.u() - 0.25).abs() < 1e-5 && (record.v() - 0.75).abs() < 1e-5);
+  }
+  #[test]
+  fn slope_has_interpolated_normals() {
+    let heights = (0..3).flat_map(|_| [0.0, 0.5, 1.0]).collect();
+    let heightfield = Heightfield::new(
+      HeightGrid::new(3, 3, heights).unwrap(),
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 1.0, 1.0]),
+    );
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.3, 5.0, 0.6]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = heightfield
+      .hit(&ray, 0.0, f32::INFINITY)
+      .expect("The ray points at the slope");
+    assert!((record.distance() - 4.7).abs() < 1e-5);
+    let expected = Direction::from_f32_array_const([-1.0, 1.0, 0.0]).unit_direction();
+    assert!((record.normal() - expected).length().as_f32() < 1e-5);
+    assert!((record.shading_normal() - expected).length().as_f32() < 1e-5);
+  }
+  #[test]
+  fn grid_walk_matches_every_triangle() {
+    let mut random = Random::new(11);
+    let (columns, rows) = (9, 6);
+    let heights = (0..columns * rows).map(|_| random.next_f32()).collect();
+    let heightfield = Heightfield::new(
+      HeightGrid::new(columns, rows, heights).unwrap(),
+      Point::from_f32_array_const([-2.0, -1.0, -3.0]),
+      Direction::from_f32_array_const([4.0, 1.5, 5.0]),
+    );
+    let mut triangles = HittableList::new();
+    for row in 0..rows - 1 {
+      for column in 0..columns - 1 {
+        let corners = [
+          heightfield.vertex(column, row),
+          heightfield.vertex(column + 1, row + 1),
+          heightfield.vertex(column + 1, row),
+          heightfield.vertex(column, row + 1),
+        ];
+        triangles.add(Arc::new(Triangle::new([corners[0], corners[1], corners[2]])));
+        triangles.add(Arc::new(Triangle::new([corners[0], corners[3], corners[1]])));
+      }
+    }
+    for _ in 0..500 {
+      let origin = Point::from_f32_array_const([
+        random.range_f32(-4.0, 4.0),
+        random.range_f32(1.0, 3.0),
+        random.range_f32(-5.0, 5.0),
+      ]);
+      let target = Point::from_f32_array_const([
+        random.range_f32(-2.0, 2.0),
+        random.range_f32(-1.0, 0.5),
+        random.range_f32(-3.0, 2.0),
+      ]);
+      let ray = Ray::new(origin, (target - origin).into());
+      let expected = triangles.hit(&ray, 0.0, f32::INFINITY).map(|record| record.distance());
+      let actual = heightfield.hit(&ray, 0.0, f32::INFINITY).map(|record| record.distance());
+      match (expected, actual) {
+        (Some(expected), Some(actual)) => assert!((expected - actual).abs() < 1e-4, "{} != {}", expected, actual),
+        (None, None) => (),
+        _ => panic!("Grid walk found {:?} but the triangles found {:?}", actual, expected),
+      }
+    }
+  }
+  #[test]
+  fn grid_from_graymap() {
+    let image = GrayImage::parse(b"P2 2 2 4\n0 4\n2 2\n").unwrap();
+    let grid = HeightGrid::new_from_image(&image).unwrap();
+    assert!((grid.height(1, 0) - 1.0).abs() < 1e-6);
+    assert!((grid.height(0, 1) - 0.5).abs() < 1e-6);
+    assert!(HeightGrid::new(1, 3, vec![0.0; 3]).is_err());
+    assert!(HeightGrid::new(2, 2, vec![0.0; 3]).is_err());
+    assert!(HeightGrid::new(usize::MAX, 2, Vec::new()).is_err());
+  }
+}
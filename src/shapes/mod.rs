@@ -0,0 +1,31 @@
+pub mod capsule;
+pub mod cone;
+pub mod cuboid;
+pub mod cylinder;
+pub mod disk;
+pub mod heightfield;
+pub mod moving_sphere;
+pub mod plane;
+pub mod quad;
+pub mod sphere;
+pub mod torus;
+pub mod triangle;
+
+use std::f32::consts::PI;
+
+/// Picks the closest candidate intersection inside the accepted distance range.
+fn nearest_in_range<T>(
+  candidates: impl IntoIterator<Item = (f32, T)>,
+  minimum_distance: f32,
+  maximum_distance: f32,
+) -> Option<(f32, T)> {
+  candidates
+    .into_iter()
+    .filter(|(distance, _)| *distance >= minimum_distance && *distance <= maximum_distance)
+    .min_by(|(left, _), (right, _)| left.partial_cmp(right).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+/// Maps the angle of `(x, y)` around the origin onto `[0, 1]`.
+fn azimuth_coordinate(x: f32, y: f32) -> f32 {
+  (y.atan2(x) + PI) / (2.0 * PI)
+}
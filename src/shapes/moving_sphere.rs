@@ -0,0 +1,89 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{distance::Distance, point::Point},
+  ray::Ray,
+};
+
+use super::sphere::Sphere;
+
+/// A sphere whose center moves in a straight line between two times; times outside the range hold the nearest center.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MovingSphere {
+  start_center: Point,
+  end_center: Point,
+  start_time: f32,
+  end_time: f32,
+  radius: Distance,
+}
+
+impl MovingSphere {
+  pub const fn new(centers: [Point; 2], times: [f32; 2], radius: Distance) -> Self {
+    Self {
+      start_center: centers[0],
+      end_center: centers[1],
+      start_time: times[0],
+      end_time: times[1],
+      radius,
+    }
+  }
+  pub const fn radius(&self) -> Distance {
+    self.radius
+  }
+  pub fn center(&self, time: f32) -> Point {
+    let duration = self.end_time - self.start_time;
+    if duration <= 0.0 {
+      return self.start_center;
+    }
+    let amount = ((time - self.start_time) / duration).clamp(0.0, 1.0);
+    Point::from(self.start_center.as_vec3() * (1.0 - amount) + self.end_center.as_vec3() * amount)
+  }
+}
+
+impl Hittable for MovingSphere {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    Sphere::new_const(self.center(ray.time()), self.radius).hit(ray, minimum_distance, maximum_distance)
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let start = Sphere::new_const(self.start_center, self.radius).bounding_box()?;
+    let end = Sphere::new_const(self.end_center, self.radius).bounding_box()?;
+    Some(start.surrounding(&end))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::MovingSphere;
+
+  #[test]
+  fn center_follows_time() {
+    let sphere = MovingSphere::new(
+      [
+        Point::from_f32_array_const([0.0, 0.0, 0.0]),
+        Point::from_f32_array_const([0.0, 2.0, 0.0]),
+      ],
+      [0.0, 1.0],
+      Distance::try_from_const(0.5).unwrap(),
+    );
+    assert!((sphere.center(0.5).y() - 1.0).abs() < 1e-6);
+    assert!((sphere.center(2.0).y() - 2.0).abs() < 1e-6);
+    let origin = Point::from_f32_array_const([0.0, 2.0, 5.0]);
+    let direction = Direction::from_f32_array_const([0.0, 0.0, -1.0]);
+    assert!(sphere
+      .hit(&Ray::new_at_time(origin, direction, 0.0), 0.0, f32::INFINITY)
+      .is_none());
+    let record = sphere
+      .hit(&Ray::new_at_time(origin, direction, 1.0), 0.0, f32::INFINITY)
+      .expect("The sphere has moved into the ray");
+    assert!((record.distance() - 4.5).abs() < 1e-5);
+    let bounding_box = sphere.bounding_box().unwrap();
+    assert!((bounding_box.minimum().y() + 0.5).abs() < 1e-6);
+    assert!((bounding_box.maximum().y() - 2.5).abs() < 1e-6);
+  }
+}
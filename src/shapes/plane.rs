@@ -0,0 +1,119 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  ray::Ray,
+  vec3::Axis,
+};
+
+const PARALLEL_EPSILON: f32 = 1e-8;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Plane {
+  point: Point,
+  basis: OrthonormalBasis,
+}
+
+impl Plane {
+  pub fn new(point: Point, normal: Direction) -> Self {
+    Self {
+      point,
+      basis: OrthonormalBasis::new_from_w(normal),
+    }
+  }
+  /// A plane through `offset` along `axis`, facing the positive direction of that axis.
+  pub const fn new_axis_aligned(axis: Axis, offset: f32) -> Self {
+    let x_axis = Direction::from_f32_array_const([1.0, 0.0, 0.0]);
+    let y_axis = Direction::from_f32_array_const([0.0, 1.0, 0.0]);
+    let z_axis = Direction::from_f32_array_const([0.0, 0.0, 1.0]);
+    let (point, basis) = match axis {
+      Axis::X => ([offset, 0.0, 0.0], OrthonormalBasis::new_const(y_axis, z_axis, x_axis)),
+      Axis::Y => ([0.0, offset, 0.0], OrthonormalBasis::new_const(z_axis, x_axis, y_axis)),
+      Axis::Z => ([0.0, 0.0, offset], OrthonormalBasis::new_const(x_axis, y_axis, z_axis)),
+    };
+    Self {
+      point: Point::from_f32_array_const(point),
+      basis,
+    }
+  }
+  pub const fn point(&self) -> Point {
+    self.point
+  }
+  pub const fn normal(&self) -> Direction {
+    self.basis.w()
+  }
+}
+
+impl Hittable for Plane {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let denominator = self.normal().dot(ray.direction());
+    if denominator.abs() < PARALLEL_EPSILON {
+      return None;
+    }
+    let distance = self.normal().dot((self.point - ray.origin()).into()) / denominator;
+    if distance < minimum_distance || distance > maximum_distance {
+      return None;
+    }
+    let planar_offset = Direction::from(ray.at(distance) - self.point);
+    Some(HitRecord::new(
+      ray,
+      distance,
+      self.normal(),
+      [planar_offset.dot(self.basis.u()), planar_offset.dot(self.basis.v())],
+    ))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+    vec3::Axis,
+  };
+
+  use super::Plane;
+
+  #[test]
+  fn ray_hits_plane_from_both_sides() {
+    let plane = Plane::new_axis_aligned(Axis::Y, -1.0);
+    let from_above = Ray::new(
+      Point::from_f32_array_const([2.0, 1.0, 3.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let record = plane
+      .hit(&from_above, 0.0, f32::INFINITY)
+      .expect("The ray points at the plane");
+    assert!(record.distance().eq(&2.0));
+    assert!(record.front_face());
+    assert_eq!(record.normal(), Direction::from_f32_array_const([0.0, 1.0, 0.0]));
+    assert!(record.u().eq(&3.0) && record.v().eq(&2.0));
+    let from_below = Ray::new(
+      Point::from_f32_array_const([0.0, -2.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+    );
+    let record = plane
+      .hit(&from_below, 0.0, f32::INFINITY)
+      .expect("The ray points at the plane");
+    assert!(!record.front_face());
+    assert_eq!(record.normal(), Direction::from_f32_array_const([0.0, -1.0, 0.0]));
+  }
+  #[test]
+  fn parallel_ray_misses_plane() {
+    let plane = Plane::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, 1.0]),
+    );
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 1.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    assert!(plane.hit(&ray, 0.0, f32::INFINITY).is_none());
+    assert!(plane.bounding_box().is_none());
+  }
+}
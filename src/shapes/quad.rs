@@ -0,0 +1,138 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, point::Point},
+  ray::Ray,
+};
+
+const PARALLEL_EPSILON: f32 = 1e-8;
+
+/// A parallelogram spanned by the edges `u` and `v` from `corner`; its normal is `u` cross `v`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Quad {
+  corner: Point,
+  u: Direction,
+  v: Direction,
+  normal: Direction,
+  plane_offset: f32,
+  planar_projection: Direction,
+}
+
+impl Quad {
+  pub fn new(corner: Point, u: Direction, v: Direction) -> Self {
+    let scaled_normal = u.cross(v);
+    let normal = scaled_normal.unit_direction();
+    Self {
+      corner,
+      u,
+      v,
+      normal,
+      plane_offset: normal.dot(corner.into()),
+      planar_projection: scaled_normal / scaled_normal.dot(scaled_normal),
+    }
+  }
+  pub const fn corner(&self) -> Point {
+    self.corner
+  }
+  pub const fn u(&self) -> Direction {
+    self.u
+  }
+  pub const fn v(&self) -> Direction {
+    self.v
+  }
+  pub const fn normal(&self) -> Direction {
+    self.normal
+  }
+}
+
+impl Hittable for Quad {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let denominator = self.normal.dot(ray.direction());
+    if denominator.abs() < PARALLEL_EPSILON {
+      return None;
+    }
+    let distance = (self.plane_offset - self.normal.dot(ray.origin().into())) / denominator;
+    if distance < minimum_distance || distance > maximum_distance {
+      return None;
+    }
+    let planar_hit = Direction::from(ray.at(distance) - self.corner);
+    let alpha = self.planar_projection.dot(planar_hit.cross(self.v));
+    let beta = self.planar_projection.dot(self.u.cross(planar_hit));
+    if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+      return None;
+    }
+    Some(HitRecord::new(ray, distance, self.normal, [alpha, beta]).with_surface_derivatives(self.u, self.v))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    Aabb::new_from_points(&[
+      self.corner,
+      self.corner + self.u.into(),
+      self.corner + self.v.into(),
+      self.corner + self.u.into() + self.v.into(),
+    ])
+    .map(|bounding_box| bounding_box.padded())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point},
+    ray::{Ray, RayDifferentials},
+  };
+
+  use super::Quad;
+
+  fn unit_quad() -> Quad {
+    Quad::new(
+      Point::from_f32_array_const([-1.0, -1.0, -2.0]),
+      Direction::from_f32_array_const([2.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 2.0, 0.0]),
+    )
+  }
+
+  #[test]
+  fn ray_hits_quad_with_uv() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.5, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = unit_quad().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the quad");
+    assert!(record.distance().eq(&2.0));
+    assert_eq!(record.normal(), Direction::from_f32_array_const([0.0, 0.0, 1.0]));
+    assert!(record.u().eq(&0.75) && record.v().eq(&0.5));
+  }
+  #[test]
+  fn ray_misses_outside_quad() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([1.5, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    assert!(unit_quad().hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+  #[test]
+  fn bounding_box_covers_quad() {
+    let bounding_box = unit_quad().bounding_box().expect("A quad is bounded");
+    assert!(bounding_box.minimum().x().eq(&-1.0));
+    assert!(bounding_box.maximum().y().eq(&1.0));
+    assert!(bounding_box.maximum().z() > bounding_box.minimum().z());
+  }
+  #[test]
+  fn pixel_footprint_maps_onto_texture_coordinates() {
+    let ray = Ray::new(Point::default(), Direction::from_f32_array_const([0.0, 0.0, -1.0]));
+    let differentials = RayDifferentials::new(
+      Point::default(),
+      Direction::from_f32_array_const([0.01, 0.0, -1.0]),
+      Point::default(),
+      Direction::from_f32_array_const([0.0, 0.01, -1.0]),
+    );
+    let record = unit_quad()
+      .hit(&ray, 0.0, f32::INFINITY)
+      .expect("The ray points at the quad")
+      .with_footprint_from(&differentials);
+    let footprint = record.texture_footprint();
+    assert!((footprint.along_x()[0] - 0.01).abs() < 1e-6 && footprint.along_x()[1].abs() < 1e-6);
+    assert!(footprint.along_y()[0].abs() < 1e-6 && (footprint.along_y()[1] - 0.01).abs() < 1e-6);
+  }
+}
@@ -0,0 +1,128 @@
+use std::f32::consts::PI;
+
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  ray::Ray,
+};
+
+use super::azimuth_coordinate;
+
+/// Maps a point on the unit sphere to `u` around the y axis starting from -x, and `v` from the bottom pole to the top.
+fn spherical_coordinates(unit_normal: Direction) -> [f32; 2] {
+  [
+    azimuth_coordinate(unit_normal.x(), -unit_normal.z()),
+    (-unit_normal.y()).clamp(-1.0, 1.0).acos() / PI,
+  ]
+}
+
+/// How a point on a sphere of `radius` moves with the `u` and `v` of [`spherical_coordinates`]; `v` does not move the
+/// poles, so its derivative is zero there.
+fn spherical_derivatives(unit_normal: Direction, radius: f32) -> [Direction; 2] {
+  let (x, y, z) = (unit_normal.x(), unit_normal.y(), unit_normal.z());
+  let along_u = Direction::from_f32_array_const([z, 0.0, -x]) * (2.0 * PI * radius);
+  let ring_radius = x.hypot(z);
+  if ring_radius <= 0.0 {
+    return [along_u, Direction::default()];
+  }
+  let along_v = Direction::from_f32_array_const([-x * y / ring_radius, ring_radius, -y * z / ring_radius]) * (PI * radius);
+  [along_u, along_v]
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Sphere {
+  center: Point,
+  radius: Distance,
+}
+
+impl Sphere {
+  pub const fn new_const(center: Point, radius: Distance) -> Self {
+    Self { center, radius }
+  }
+  pub const fn center(&self) -> Point {
+    self.center
+  }
+  pub const fn radius(&self) -> Distance {
+    self.radius
+  }
+  pub fn does_ray_hit_sphere(&self, ray: Ray) -> bool {
+    let center_ray_offset: Direction = (ray.origin() - self.center()).into();
+    let a = ray.direction().dot(ray.direction());
+    let b = 2.0 * center_ray_offset.dot(ray.direction());
+    let c = center_ray_offset.dot(center_ray_offset) - self.radius().as_f32() * self.radius().as_f32();
+    let discriminant = b * b - 4.0 * a * c;
+    discriminant > 0.0
+  }
+}
+
+impl Hittable for Sphere {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let center_ray_offset: Direction = (ray.origin() - self.center()).into();
+    let a = ray.direction().dot(ray.direction());
+    let half_b = center_ray_offset.dot(ray.direction());
+    let c = center_ray_offset.dot(center_ray_offset) - self.radius().as_f32() * self.radius().as_f32();
+    let discriminant = half_b * half_b - a * c;
+    if discriminant < 0.0 {
+      return None;
+    }
+    let discriminant_root = discriminant.sqrt();
+    let mut root = (-half_b - discriminant_root) / a;
+    if root < minimum_distance || root > maximum_distance {
+      root = (-half_b + discriminant_root) / a;
+      if root < minimum_distance || root > maximum_distance {
+        return None;
+      }
+    }
+    let outward_normal = Direction::from(ray.at(root) - self.center()) / self.radius().as_f32();
+    let [along_u, along_v] = spherical_derivatives(outward_normal, self.radius().as_f32());
+    Some(
+      HitRecord::new(ray, root, outward_normal, spherical_coordinates(outward_normal))
+        .with_surface_derivatives(along_u, along_v),
+    )
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let radius = self.radius().as_f32();
+    let extent = Point::from_f32_array_const([radius, radius, radius]);
+    Some(Aabb::new(self.center() - extent, self.center() + extent))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::Sphere;
+
+  #[test]
+  fn hits_carry_spherical_uv() {
+    let sphere = Sphere::new_const(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Distance::try_from_const(2.0).unwrap(),
+    );
+    let uv_from = |origin: [f32; 3]| {
+      let origin = Point::from_f32_array_const(origin);
+      let record = sphere
+        .hit(&Ray::new(origin, Direction::from(origin) * -1.0), 0.0, f32::INFINITY)
+        .expect("The ray points at the center");
+      [record.u(), record.v()]
+    };
+    for (origin, expected) in [
+      ([5.0, 0.0, 0.0], [0.5, 0.5]),
+      ([0.0, 0.0, 5.0], [0.25, 0.5]),
+      ([0.0, 0.0, -5.0], [0.75, 0.5]),
+      ([0.0, 5.0, 0.0], [0.5, 1.0]),
+      ([0.0, -5.0, 0.0], [0.5, 0.0]),
+    ] {
+      let uv = uv_from(origin);
+      assert!((uv[1] - expected[1]).abs() < 1e-5);
+      if expected[1] > 0.0 && expected[1] < 1.0 {
+        assert!((uv[0] - expected[0]).abs() < 1e-5);
+      }
+    }
+  }
+}
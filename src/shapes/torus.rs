@@ -0,0 +1,165 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  orthonormal_basis::OrthonormalBasis,
+  polynomial::solve_quartic,
+  ray::Ray,
+  shapes::{azimuth_coordinate, nearest_in_range},
+};
+
+/// A ring of radius `major_radius` around `axis`, swept by a tube of radius `minor_radius`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Torus {
+  center: Point,
+  basis: OrthonormalBasis,
+  major_radius: Distance,
+  minor_radius: Distance,
+}
+
+impl Torus {
+  pub fn new(center: Point, axis: Direction, major_radius: Distance, minor_radius: Distance) -> Self {
+    Self {
+      center,
+      basis: OrthonormalBasis::new_from_w(axis),
+      major_radius,
+      minor_radius,
+    }
+  }
+  pub const fn center(&self) -> Point {
+    self.center
+  }
+  pub const fn axis(&self) -> Direction {
+    self.basis.w()
+  }
+  pub const fn major_radius(&self) -> Distance {
+    self.major_radius
+  }
+  pub const fn minor_radius(&self) -> Distance {
+    self.minor_radius
+  }
+}
+
+impl Hittable for Torus {
+  #[allow(clippy::many_single_char_names)]
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let origin = self.basis.world_to_local((ray.origin() - self.center).into());
+    let direction_length = ray.direction().length().as_f32();
+    let direction = self.basis.world_to_local(ray.direction()) / direction_length;
+    let [ox, oy, oz] = [origin.x(), origin.y(), origin.z()].map(f64::from);
+    let [dx, dy, dz] = [direction.x(), direction.y(), direction.z()].map(f64::from);
+    let major_squared = f64::from(self.major_radius.as_f32()).powi(2);
+    let minor_squared = f64::from(self.minor_radius.as_f32()).powi(2);
+    let origin_dot_direction = ox * dx + oy * dy + oz * dz;
+    let k = ox * ox + oy * oy + oz * oz + major_squared - minor_squared;
+    let roots = solve_quartic(
+      1.0,
+      4.0 * origin_dot_direction,
+      2.0 * k + 4.0 * origin_dot_direction * origin_dot_direction - 4.0 * major_squared * (dx * dx + dy * dy),
+      4.0 * origin_dot_direction * k - 8.0 * major_squared * (ox * dx + oy * dy),
+      k * k - 4.0 * major_squared * (ox * ox + oy * oy),
+    );
+    #[allow(clippy::cast_possible_truncation)]
+    let candidates = roots.into_iter().map(|root| (root as f32 / direction_length, ()));
+    let (distance, ()) = nearest_in_range(candidates, minimum_distance, maximum_distance)?;
+    let local_point = origin + direction * (distance * direction_length);
+    let ring_distance = (local_point.x() * local_point.x() + local_point.y() * local_point.y()).sqrt();
+    let major_radius = self.major_radius.as_f32();
+    let ring_point = Direction::from_f32_array_const([
+      local_point.x() * major_radius / ring_distance,
+      local_point.y() * major_radius / ring_distance,
+      0.0,
+    ]);
+    let local_normal = (local_point - ring_point) / self.minor_radius.as_f32();
+    Some(HitRecord::new(
+      ray,
+      distance,
+      self.basis.local_to_world(local_normal),
+      [
+        azimuth_coordinate(local_point.x(), local_point.y()),
+        azimuth_coordinate(ring_distance - major_radius, local_point.z()),
+      ],
+    ))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    let axis = self.axis();
+    let major_radius = self.major_radius.as_f32();
+    let minor_radius = self.minor_radius.as_f32();
+    let half_extent = |component: f32| major_radius * (1.0 - component * component).max(0.0).sqrt() + minor_radius;
+    let extent = Point::from_f32_array_const([half_extent(axis.x()), half_extent(axis.y()), half_extent(axis.z())]);
+    Some(Aabb::new(self.center - extent, self.center + extent))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+  };
+
+  use super::Torus;
+
+  fn torus() -> Torus {
+    Torus::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+      Distance::try_from_const(2.0).unwrap(),
+      Distance::try_from_const(0.5).unwrap(),
+    )
+  }
+
+  fn assert_near(actual: Direction, expected: [f32; 3]) {
+    let error = actual - Direction::from_f32_array_const(expected);
+    assert!(error.length().as_f32() < 1e-4, "{:?} != {:?}", actual, expected);
+  }
+
+  #[test]
+  fn ray_through_ring_hits_four_times() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-5.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([2.0, 0.0, 0.0]),
+    );
+    let mut minimum_distance = 0.0;
+    let mut distances = Vec::new();
+    while let Some(record) = torus().hit(&ray, minimum_distance, f32::INFINITY) {
+      distances.push(record.distance());
+      minimum_distance = record.distance() + 1e-3;
+    }
+    let expected = [1.25, 1.75, 3.25, 3.75];
+    assert_eq!(distances.len(), expected.len());
+    for (actual, expected) in distances.iter().zip(expected) {
+      assert!((actual - expected).abs() < 1e-4, "{} != {}", actual, expected);
+    }
+  }
+  #[test]
+  fn normals_point_away_from_tube() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([-5.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    );
+    let entry = torus().hit(&ray, 0.0, f32::INFINITY).expect("The ray points at the tube");
+    assert_near(entry.normal(), [-1.0, 0.0, 0.0]);
+    let exit = torus().hit(&ray, 3.0, f32::INFINITY).expect("The ray leaves the tube");
+    assert!(!exit.front_face());
+    assert_near(entry.point().as_vec3().into(), [-2.5, 0.0, 0.0]);
+    let from_above = Ray::new(
+      Point::from_f32_array_const([0.0, 5.0, 2.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    let top = torus()
+      .hit(&from_above, 0.0, f32::INFINITY)
+      .expect("The ray points at the top of the tube");
+    assert!((top.distance() - 4.5).abs() < 1e-4);
+    assert_near(top.normal(), [0.0, 1.0, 0.0]);
+  }
+  #[test]
+  fn ray_through_hole_misses() {
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 5.0, 0.0]),
+      Direction::from_f32_array_const([0.0, -1.0, 0.0]),
+    );
+    assert!(torus().hit(&ray, 0.0, f32::INFINITY).is_none());
+  }
+}
@@ -0,0 +1,101 @@
+use crate::{
+  aabb::Aabb,
+  hittable::{HitRecord, Hittable},
+  newtypes::{direction::Direction, point::Point},
+  ray::Ray,
+};
+
+const PARALLEL_EPSILON: f32 = 1e-9;
+
+/// Möller-Trumbore intersection, returning the ray distance and the barycentric weights of the second and third vertex.
+pub fn intersect(ray: &Ray, vertices: &[Point; 3]) -> Option<(f32, [f32; 2])> {
+  let edge_1 = Direction::from(vertices[1] - vertices[0]);
+  let edge_2 = Direction::from(vertices[2] - vertices[0]);
+  let perpendicular = ray.direction().cross(edge_2);
+  let determinant = edge_1.dot(perpendicular);
+  if determinant.abs() < PARALLEL_EPSILON {
+    return None;
+  }
+  let inverse_determinant = 1.0 / determinant;
+  let offset = Direction::from(ray.origin() - vertices[0]);
+  let weight_1 = offset.dot(perpendicular) * inverse_determinant;
+  if !(0.0..=1.0).contains(&weight_1) {
+    return None;
+  }
+  let offset_cross_edge = offset.cross(edge_1);
+  let weight_2 = ray.direction().dot(offset_cross_edge) * inverse_determinant;
+  if weight_2 < 0.0 || weight_1 + weight_2 > 1.0 {
+    return None;
+  }
+  Some((edge_2.dot(offset_cross_edge) * inverse_determinant, [weight_1, weight_2]))
+}
+
+/// A single triangle whose front is given by the counter-clockwise winding of its vertices.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Triangle {
+  vertices: [Point; 3],
+  normal: Direction,
+}
+
+impl Triangle {
+  pub fn new(vertices: [Point; 3]) -> Self {
+    let normal = Direction::from(vertices[1] - vertices[0])
+      .cross((vertices[2] - vertices[0]).into())
+      .unit_direction();
+    Self { vertices, normal }
+  }
+  pub const fn vertices(&self) -> [Point; 3] {
+    self.vertices
+  }
+  pub const fn normal(&self) -> Direction {
+    self.normal
+  }
+}
+
+impl Hittable for Triangle {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let (distance, weights) = intersect(ray, &self.vertices)?;
+    if distance < minimum_distance || distance > maximum_distance {
+      return None;
+    }
+    Some(HitRecord::new(ray, distance, self.normal, weights))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    Aabb::new_from_points(&self.vertices).map(|bounding_box| bounding_box.padded())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point},
+    ray::Ray,
+  };
+
+  use super::Triangle;
+
+  #[test]
+  fn ray_hits_inside_and_misses_outside() {
+    let triangle = Triangle::new([
+      Point::from_f32_array_const([0.0, 0.0, -1.0]),
+      Point::from_f32_array_const([1.0, 0.0, -1.0]),
+      Point::from_f32_array_const([0.0, 1.0, -1.0]),
+    ]);
+    let inside = Ray::new(
+      Point::from_f32_array_const([0.25, 0.25, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let record = triangle
+      .hit(&inside, 0.0, f32::INFINITY)
+      .expect("The ray points inside the triangle");
+    assert!((record.distance() - 1.0).abs() < 1e-6);
+    assert!((record.u() - 0.25).abs() < 1e-6 && (record.v() - 0.25).abs() < 1e-6);
+    assert!(record.front_face());
+    let outside = Ray::new(
+      Point::from_f32_array_const([0.75, 0.75, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    assert!(triangle.hit(&outside, 0.0, f32::INFINITY).is_none());
+  }
+}
@@ -0,0 +1,124 @@
+use crate::{color_space::ColorSpace, random::Random, vec3::Vec3};
+
+/// The shortest wavelength traced, in nanometres.
+pub const MINIMUM_WAVELENGTH: f32 = 380.0;
+/// The longest wavelength traced, in nanometres.
+pub const MAXIMUM_WAVELENGTH: f32 = 780.0;
+
+/// Where the blue and red parts of an upsampled spectrum give way to green, and how soft the transitions are, in
+/// nanometres. Fitted so the `sRGB` primaries survive a round trip to within a few percent.
+const BLUE_EDGE: f32 = 487.0;
+const RED_EDGE: f32 = 588.0;
+const EDGE_WIDTH: f32 = 8.0;
+
+/// Linear `sRGB` of the equal-energy spectrum of one over the traced range, used to white balance spectral samples.
+const EQUAL_ENERGY_RGB: [f32; 3] = [128.381, 101.536, 97.0415];
+
+/// Picks a wavelength uniformly over the traced range, returned with its probability density per nanometre.
+pub fn sample_wavelength(random: &mut Random) -> (f32, f32) {
+  let range = MAXIMUM_WAVELENGTH - MINIMUM_WAVELENGTH;
+  (MINIMUM_WAVELENGTH + random.next_f32() * range, 1.0 / range)
+}
+
+/// The CIE 1931 two-degree color matching functions at `wavelength`, as `(x, y, z)`, using the multi-lobe fit of
+/// Wyman, Sloan and Shirley (2013).
+pub fn color_matching(wavelength: f32) -> Vec3 {
+  let lobe = |center: f32, below: f32, above: f32| {
+    let width = if wavelength < center { below } else { above };
+    let offset = (wavelength - center) / width;
+    (-0.5 * offset * offset).exp()
+  };
+  Vec3::new([
+    1.056 * lobe(599.8, 37.9, 31.0) + 0.362 * lobe(442.0, 16.0, 26.7) - 0.065 * lobe(501.1, 20.4, 26.2),
+    0.821 * lobe(568.8, 46.9, 40.5) + 0.286 * lobe(530.9, 16.3, 31.1),
+    1.217 * lobe(437.0, 11.8, 36.0) + 0.681 * lobe(459.0, 26.0, 13.8),
+  ])
+}
+
+/// The value at `wavelength` of a smooth spectrum for a linear RGB color. The blue, green and red parts of the
+/// spectrum always add up to one, so white becomes a flat spectrum and colors in `[0, 1]` stay valid reflectances.
+pub fn upsample_rgb(rgb: Vec3, wavelength: f32) -> f32 {
+  let step = |edge: f32| 1.0 / (1.0 + (-(wavelength - edge) / EDGE_WIDTH).exp());
+  let (past_blue, red) = (step(BLUE_EDGE), step(RED_EDGE));
+  rgb.x() * red + rgb.y() * (past_blue - red) + rgb.z() * (1.0 - past_blue)
+}
+
+/// Estimates a color in `working_space` from one sample of spectral radiance taken at `wavelength` with density
+/// `pdf`, white balanced so that a flat spectrum of one comes out as white.
+pub fn spectral_sample_to_rgb(value: f32, wavelength: f32, pdf: f32, working_space: ColorSpace) -> Vec3 {
+  let rgb = ColorSpace::LinearRec709.color_from_xyz(color_matching(wavelength) * (value / pdf));
+  let balanced = Vec3::new([
+    rgb.x() / EQUAL_ENERGY_RGB[0],
+    rgb.y() / EQUAL_ENERGY_RGB[1],
+    rgb.z() / EQUAL_ENERGY_RGB[2],
+  ]);
+  ColorSpace::LinearRec709.convert(balanced, working_space)
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{color_space::ColorSpace, vec3::Vec3};
+
+  use super::{color_matching, spectral_sample_to_rgb, upsample_rgb, MAXIMUM_WAVELENGTH, MINIMUM_WAVELENGTH};
+
+  /// Integrates the color seen for the spectrum of `rgb` with the midpoint rule.
+  fn round_trip(rgb: Vec3) -> Vec3 {
+    let steps: u16 = 2000;
+    let step = (MAXIMUM_WAVELENGTH - MINIMUM_WAVELENGTH) / f32::from(steps);
+    (0..steps).fold(Vec3::default(), |total, index| {
+      let wavelength = MINIMUM_WAVELENGTH + (f32::from(index) + 0.5) * step;
+      let density = 1.0 / (MAXIMUM_WAVELENGTH - MINIMUM_WAVELENGTH);
+      let value = upsample_rgb(rgb, wavelength);
+      total + spectral_sample_to_rgb(value, wavelength, density, ColorSpace::LinearRec709) / f32::from(steps)
+    })
+  }
+
+  #[test]
+  fn white_round_trips_exactly() {
+    let white = round_trip(Vec3::new([1.0, 1.0, 1.0]));
+    assert!(
+      (white - Vec3::new([1.0, 1.0, 1.0])).length().as_f32() < 1e-3,
+      "White became {}",
+      white
+    );
+  }
+  #[test]
+  fn primaries_round_trip_closely() {
+    for primary in [
+      Vec3::new([1.0, 0.0, 0.0]),
+      Vec3::new([0.0, 1.0, 0.0]),
+      Vec3::new([0.0, 0.0, 1.0]),
+      Vec3::new([0.5, 0.2, 0.1]),
+    ] {
+      let seen = round_trip(primary);
+      assert!((seen - primary).length().as_f32() < 0.05, "{} became {}", primary, seen);
+    }
+  }
+  #[test]
+  fn reflectances_stay_physical() {
+    for index in 0..=400_u16 {
+      let wavelength = MINIMUM_WAVELENGTH + f32::from(index);
+      for rgb in [
+        Vec3::new([1.0, 0.0, 0.0]),
+        Vec3::new([0.2, 0.9, 0.4]),
+        Vec3::new([1.0, 1.0, 1.0]),
+      ] {
+        let value = upsample_rgb(rgb, wavelength);
+        assert!((-1e-6..=1.0 + 1e-6).contains(&value));
+      }
+    }
+  }
+  #[test]
+  fn luminance_peaks_in_the_green() {
+    let brightest = (0..=400_u16)
+      .map(|index| MINIMUM_WAVELENGTH + f32::from(index))
+      .max_by(|first, second| {
+        color_matching(*first)
+          .y()
+          .partial_cmp(&color_matching(*second).y())
+          .unwrap_or(std::cmp::Ordering::Equal)
+      })
+      .unwrap();
+    assert!((550.0..=570.0).contains(&brightest));
+  }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::{
+  environment::Environment,
+  hittable::Hittable,
+  instance::Instance,
+  integrator::ray_color,
+  materials::Material,
+  newtypes::{direction::Direction, distance::Distance, point::Point},
+  random::Random,
+  ray::Ray,
+  shapes::sphere::Sphere,
+  transform::Transform,
+  vec3::Vec3,
+};
+
+/// A unit sphere at the origin made of `material`.
+pub fn unit_sphere(material: Arc<dyn Material>) -> Instance {
+  let sphere = Sphere::new_const(Point::default(), Distance::try_from_const(1.0).unwrap());
+  Instance::new_with_material(Arc::new(sphere), Transform::identity(), material)
+}
+
+/// Looks straight down at `world` through the unit disk under a white environment, returning the mean radiance and the
+/// brightest channel of any single sample. Neither exceeds one for an object that conserves energy.
+pub fn white_furnace(world: &dyn Hittable, samples: u16, maximum_depth: u32, seed: u64) -> (Vec3, f32) {
+  let white = Vec3::new([1.0, 1.0, 1.0]);
+  let environment = Environment::new(white, white);
+  let mut random = Random::new(seed);
+  let (total, brightest) = (0..samples).fold((Vec3::default(), 0.0_f32), |(total, brightest), _| {
+    let target = random.in_unit_disk();
+    let ray = Ray::new(
+      Point::from_f32_array_const([target.x(), target.y(), 5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let color = ray_color(&ray, world, &environment, maximum_depth, &mut random).as_vec3();
+    (total + color, brightest.max(color.x()).max(color.y()).max(color.z()))
+  });
+  (total / f32::from(samples), brightest)
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use crate::{
+  error::LocalError,
+  newtypes::point::Point,
+  textures::{Texture, TextureFootprint},
+  vec3::Vec3,
+};
+
+/// Alternates two textures through space in cubes of side `scale`, so the pattern does not depend on UVs.
+#[derive(Debug, Clone)]
+pub struct Checker {
+  even: Arc<dyn Texture>,
+  odd: Arc<dyn Texture>,
+  scale: f32,
+}
+
+impl Checker {
+  pub fn new(even: Arc<dyn Texture>, odd: Arc<dyn Texture>, scale: f32) -> Result<Self, LocalError> {
+    if !(scale.is_finite() && scale > 0.0) {
+      return Err("A checker's scale must be positive and finite".into());
+    }
+    Ok(Self { even, odd, scale })
+  }
+  pub const fn scale(&self) -> f32 {
+    self.scale
+  }
+  #[allow(clippy::cast_possible_truncation)]
+  fn texture_at(&self, point: Point) -> &Arc<dyn Texture> {
+    let cell = |coordinate: f32| (coordinate / self.scale).floor() as i64;
+    if (cell(point.x()) + cell(point.y()) + cell(point.z())).rem_euclid(2) == 0 {
+      &self.even
+    } else {
+      &self.odd
+    }
+  }
+}
+
+impl Texture for Checker {
+  fn value(&self, uv: [f32; 2], point: Point) -> Vec3 {
+    self.texture_at(point).value(uv, point)
+  }
+  fn filtered_value(&self, uv: [f32; 2], point: Point, footprint: TextureFootprint) -> Vec3 {
+    self.texture_at(point).filtered_value(uv, point, footprint)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    newtypes::point::Point,
+    textures::{solid_color::SolidColor, Texture},
+    vec3::Vec3,
+  };
+
+  use super::Checker;
+
+  #[test]
+  fn alternates_between_cells() {
+    let black = Vec3::new([0.0, 0.0, 0.0]);
+    let white = Vec3::new([1.0, 1.0, 1.0]);
+    let checker = Checker::new(Arc::new(SolidColor::new(white)), Arc::new(SolidColor::new(black)), 0.5).unwrap();
+    let at = |x: f32, y: f32, z: f32| checker.value([0.0, 0.0], Point::from_f32_array_const([x, y, z]));
+    assert_eq!(at(0.1, 0.1, 0.1), white);
+    assert_eq!(at(0.6, 0.1, 0.1), black);
+    assert_eq!(at(-0.1, 0.1, 0.1), black);
+    assert_eq!(at(-0.1, -0.1, 0.1), white);
+  }
+  #[test]
+  fn rejects_degenerate_scales() {
+    let solid = || -> Arc<dyn Texture> { Arc::new(SolidColor::new(Vec3::new([0.5, 0.5, 0.5]))) };
+    for scale in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+      assert!(Checker::new(solid(), solid(), scale).is_err(), "{} was accepted", scale);
+    }
+  }
+}
@@ -0,0 +1,64 @@
+use crate::{error::LocalError, vec3::Vec3};
+
+/// Maps a scalar to a color by interpolating between stops; values beyond the ends take the end colors.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ColorRamp {
+  stops: Vec<(f32, Vec3)>,
+}
+
+impl ColorRamp {
+  /// Stops may be given in any order, but need finite positions.
+  pub fn new(mut stops: Vec<(f32, Vec3)>) -> Result<Self, LocalError> {
+    if stops.is_empty() {
+      return Err("A color ramp needs at least one stop".into());
+    }
+    if stops.iter().any(|(position, _)| !position.is_finite()) {
+      return Err("Color ramp stop positions must be finite".into());
+    }
+    stops.sort_by(|(left, _), (right, _)| left.partial_cmp(right).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(Self { stops })
+  }
+  /// A ramp from black at 0 to white at 1.
+  pub fn grayscale() -> Self {
+    Self {
+      stops: vec![(0.0, Vec3::new([0.0, 0.0, 0.0])), (1.0, Vec3::new([1.0, 1.0, 1.0]))],
+    }
+  }
+  pub fn stops(&self) -> &[(f32, Vec3)] {
+    &self.stops
+  }
+  pub fn color_at(&self, value: f32) -> Vec3 {
+    let upper = self.stops.iter().position(|(position, _)| *position > value);
+    match upper {
+      None => self.stops[self.stops.len() - 1].1,
+      Some(0) => self.stops[0].1,
+      Some(index) => {
+        let (start, start_color) = self.stops[index - 1];
+        let (end, end_color) = self.stops[index];
+        let amount = (value - start) / (end - start);
+        start_color * (1.0 - amount) + end_color * amount
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::vec3::Vec3;
+
+  use super::ColorRamp;
+
+  #[test]
+  fn interpolates_between_sorted_stops() {
+    let red = Vec3::new([1.0, 0.0, 0.0]);
+    let blue = Vec3::new([0.0, 0.0, 1.0]);
+    let white = Vec3::new([1.0, 1.0, 1.0]);
+    let ramp = ColorRamp::new(vec![(1.0, white), (0.0, red), (0.5, blue)]).unwrap();
+    assert_eq!(ramp.color_at(-1.0), red);
+    assert_eq!(ramp.color_at(0.25), Vec3::new([0.5, 0.0, 0.5]));
+    assert_eq!(ramp.color_at(0.5), blue);
+    assert_eq!(ramp.color_at(2.0), white);
+    assert!(ColorRamp::new(Vec::new()).is_err());
+    assert!(ColorRamp::new(vec![(f32::NAN, red)]).is_err());
+  }
+}
@@ -0,0 +1,280 @@
+use std::sync::Arc;
+
+use crate::{
+  color_space::ColorSpace,
+  newtypes::point::Point,
+  rgb_image::RgbImage,
+  textures::{mip_map::MipMap, Texture, TextureFootprint},
+  vec3::Vec3,
+};
+
+/// What happens to coordinates outside `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WrapMode {
+  Repeat,
+  Clamp,
+}
+
+impl Default for WrapMode {
+  fn default() -> Self {
+    Self::Repeat
+  }
+}
+
+impl WrapMode {
+  /// Maps a texel index that may lie outside `0..size` back inside it.
+  pub fn texel(self, index: i64, size: usize) -> usize {
+    let size = i64::try_from(size).expect("Image sizes fit in an i64");
+    let wrapped = match self {
+      Self::Repeat => index.rem_euclid(size),
+      Self::Clamp => index.clamp(0, size - 1),
+    };
+    usize::try_from(wrapped).expect("A wrapped texel index is never negative")
+  }
+}
+
+/// How footprint-aware lookups average the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Filter {
+  /// Ignores the footprint and filters the full-resolution image.
+  Bilinear,
+  /// Blends the two MIP levels whose texel size brackets the footprint's longer axis.
+  Trilinear,
+  /// Averages an elliptical Gaussian fitted to the footprint, on the MIP level matching its shorter axis.
+  Ewa,
+}
+
+impl Default for Filter {
+  fn default() -> Self {
+    Self::Trilinear
+  }
+}
+
+/// Elliptical footprints are widened until they are at most this many times longer than they are wide.
+const MAXIMUM_ECCENTRICITY: f32 = 8.0;
+/// The falloff of the Gaussian weights used by elliptical filtering.
+const GAUSSIAN_FALLOFF: f32 = 2.0;
+
+/// Blends a per-level lookup between the two integer levels around `level`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn between_levels(level: f32, lookup: impl Fn(usize) -> Vec3) -> Vec3 {
+  let finer = level.floor();
+  let blend = level - finer;
+  let finer = finer as usize;
+  if blend <= 0.0 {
+    return lookup(finer);
+  }
+  lookup(finer) * (1.0 - blend) + lookup(finer + 1) * blend
+}
+
+/// Looks colors up in an image with bilinear filtering, with `v = 0` at the bottom row. Lookups given a footprint
+/// average over it using a MIP pyramid. The image is converted to the working space first, so filtering blends light
+/// rather than encoded values.
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+  image: Arc<RgbImage>,
+  wrap_mode: WrapMode,
+  mip_map: MipMap,
+  filter: Filter,
+}
+
+impl ImageTexture {
+  pub fn new(image: Arc<RgbImage>, wrap_mode: WrapMode) -> Self {
+    Self::new_in_working_space(image, wrap_mode, ColorSpace::default())
+  }
+  pub fn new_in_working_space(image: Arc<RgbImage>, wrap_mode: WrapMode, working_space: ColorSpace) -> Self {
+    let image = if image.color_space() == working_space {
+      image
+    } else {
+      Arc::new(image.converted_to(working_space))
+    };
+    Self {
+      mip_map: MipMap::new(image.clone()),
+      image,
+      wrap_mode,
+      filter: Filter::default(),
+    }
+  }
+  pub fn with_filter(self, filter: Filter) -> Self {
+    Self { filter, ..self }
+  }
+  pub fn image(&self) -> &Arc<RgbImage> {
+    &self.image
+  }
+  pub const fn wrap_mode(&self) -> WrapMode {
+    self.wrap_mode
+  }
+  pub const fn mip_map(&self) -> &MipMap {
+    &self.mip_map
+  }
+  pub const fn filter(&self) -> Filter {
+    self.filter
+  }
+  /// The fractional MIP level whose texels are `width` wide in texture coordinates, clamped to the pyramid.
+  #[allow(clippy::cast_precision_loss)]
+  fn level_for(&self, width: f32) -> f32 {
+    let texels = width * self.image.width().max(self.image.height()) as f32;
+    let coarsest = (self.mip_map.levels().len() - 1) as f32;
+    texels.log2().clamp(0.0, coarsest)
+  }
+  fn trilinear(&self, uv: [f32; 2], footprint: TextureFootprint) -> Vec3 {
+    let level = self.level_for(footprint.width());
+    between_levels(level, |level| self.mip_map.bilinear(level, uv, self.wrap_mode))
+  }
+  fn ewa(&self, uv: [f32; 2], footprint: TextureFootprint) -> Vec3 {
+    let length = |axis: [f32; 2]| axis[0].hypot(axis[1]);
+    let (mut major, mut minor) = (footprint.along_x(), footprint.along_y());
+    if length(minor) > length(major) {
+      std::mem::swap(&mut major, &mut minor);
+    }
+    let (major_length, minor_length) = (length(major), length(minor));
+    if minor_length <= 0.0 {
+      return self.trilinear(uv, footprint);
+    }
+    if minor_length * MAXIMUM_ECCENTRICITY < major_length {
+      let widening = major_length / (minor_length * MAXIMUM_ECCENTRICITY);
+      minor = [minor[0] * widening, minor[1] * widening];
+    }
+    let level = self.level_for(length(minor));
+    let coarsest = self.mip_map.levels().len() - 1;
+    between_levels(level, |level| {
+      if level >= coarsest {
+        self.mip_map.texel(coarsest, 0, 0, self.wrap_mode)
+      } else {
+        self.ewa_on_level(level, uv, [major, minor])
+      }
+    })
+  }
+  /// Weights the texels of `level` inside the ellipse spanned by `axes` with a truncated Gaussian.
+  #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::similar_names)]
+  fn ewa_on_level(&self, level: usize, uv: [f32; 2], axes: [[f32; 2]; 2]) -> Vec3 {
+    let image = &self.mip_map.levels()[level];
+    let (width, height) = (image.width() as f32, image.height() as f32);
+    let center = [uv[0] * width - 0.5, (1.0 - uv[1]) * height - 0.5];
+    // Rows run downwards, so the v components flip along with the center.
+    let [first, second] = axes.map(|axis| [axis[0] * width, -axis[1] * height]);
+    let mut a = first[1] * first[1] + second[1] * second[1] + 1.0;
+    let mut b = -2.0 * (first[0] * first[1] + second[0] * second[1]);
+    let mut c = first[0] * first[0] + second[0] * second[0] + 1.0;
+    let inverse_scale = 1.0 / (a * c - b * b * 0.25);
+    a *= inverse_scale;
+    b *= inverse_scale;
+    c *= inverse_scale;
+    let determinant = 4.0 * a * c - b * b;
+    let half_width = 2.0 * (determinant * c).sqrt() / determinant;
+    let half_height = 2.0 * (determinant * a).sqrt() / determinant;
+    let (mut sum, mut total_weight) = (Vec3::default(), 0.0);
+    for row in (center[1] - half_height).ceil() as i64..=(center[1] + half_height).floor() as i64 {
+      let row_offset = row as f32 - center[1];
+      for column in (center[0] - half_width).ceil() as i64..=(center[0] + half_width).floor() as i64 {
+        let column_offset = column as f32 - center[0];
+        let radius_squared =
+          a * column_offset * column_offset + b * column_offset * row_offset + c * row_offset * row_offset;
+        if radius_squared < 1.0 {
+          let weight = (-GAUSSIAN_FALLOFF * radius_squared).exp() - (-GAUSSIAN_FALLOFF).exp();
+          sum += self.mip_map.texel(level, column, row, self.wrap_mode) * weight;
+          total_weight += weight;
+        }
+      }
+    }
+    if total_weight <= 0.0 {
+      return self.mip_map.bilinear(level, uv, self.wrap_mode);
+    }
+    sum * (1.0 / total_weight)
+  }
+}
+
+impl Texture for ImageTexture {
+  fn value(&self, uv: [f32; 2], _point: Point) -> Vec3 {
+    self.mip_map.bilinear(0, uv, self.wrap_mode)
+  }
+  fn filtered_value(&self, uv: [f32; 2], point: Point, footprint: TextureFootprint) -> Vec3 {
+    match self.filter {
+      Filter::Bilinear => self.value(uv, point),
+      Filter::Trilinear => self.trilinear(uv, footprint),
+      Filter::Ewa => self.ewa(uv, footprint),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    color_space::ColorSpace,
+    newtypes::point::Point,
+    rgb_image::RgbImage,
+    textures::{Texture, TextureFootprint},
+    vec3::Vec3,
+  };
+
+  use super::{Filter, ImageTexture, WrapMode};
+
+  fn two_texel_image() -> Arc<RgbImage> {
+    Arc::new(RgbImage::new(2, 1, vec![Vec3::new([0.0, 0.0, 0.0]), Vec3::new([1.0, 1.0, 1.0])]).unwrap())
+  }
+
+  #[test]
+  fn filters_between_texel_centers() {
+    let texture = ImageTexture::new(two_texel_image(), WrapMode::Clamp);
+    let at = |u: f32| texture.value([u, 0.5], Point::default()).x();
+    assert!(at(0.25).abs() < 1e-6);
+    assert!((at(0.5) - 0.5).abs() < 1e-6);
+    assert!((at(0.75) - 1.0).abs() < 1e-6);
+    assert!((at(1.5) - 1.0).abs() < 1e-6);
+    assert!(at(-0.5).abs() < 1e-6);
+  }
+  #[test]
+  fn repeats_across_the_edge() {
+    let texture = ImageTexture::new(two_texel_image(), WrapMode::Repeat);
+    let at = |u: f32| texture.value([u, 0.5], Point::default()).x();
+    assert!((at(0.0) - 0.5).abs() < 1e-6);
+    assert!((at(1.25) - at(0.25)).abs() < 1e-6);
+    assert!((at(-0.25) - at(0.75)).abs() < 1e-6);
+  }
+  fn striped_image() -> Arc<RgbImage> {
+    let pixels = (0..64_u16).map(|index| Vec3::new([f32::from(index % 2); 3])).collect();
+    Arc::new(RgbImage::new(8, 8, pixels).unwrap())
+  }
+
+  #[test]
+  fn wide_footprints_converge_to_the_image_average() {
+    let wide = TextureFootprint::new([1.0, 0.0], [0.0, 1.0]);
+    for filter in [Filter::Trilinear, Filter::Ewa] {
+      let texture = ImageTexture::new(striped_image(), WrapMode::Repeat).with_filter(filter);
+      let filtered = texture.filtered_value([0.3, 0.6], Point::default(), wide).x();
+      assert!((filtered - 0.5).abs() < 1e-3, "{:?} gave {}", filter, filtered);
+    }
+  }
+  #[test]
+  fn small_footprints_keep_the_detail() {
+    let tiny = TextureFootprint::new([1e-4, 0.0], [0.0, 1e-4]);
+    for filter in [Filter::Bilinear, Filter::Trilinear, Filter::Ewa] {
+      let texture = ImageTexture::new(striped_image(), WrapMode::Repeat).with_filter(filter);
+      let filtered = texture.filtered_value([1.5 / 8.0, 0.5], Point::default(), tiny).x();
+      assert!((filtered - 1.0).abs() < 1e-2, "{:?} gave {}", filter, filtered);
+    }
+  }
+  #[test]
+  fn elliptical_filtering_blurs_only_along_the_long_axis() {
+    let texture = ImageTexture::new(striped_image(), WrapMode::Repeat).with_filter(Filter::Ewa);
+    let across_stripes = TextureFootprint::new([0.5, 0.0], [0.0, 0.1]);
+    let along_stripes = TextureFootprint::new([0.0, 0.5], [0.1, 0.0]);
+    let uv = [1.5 / 8.0, 0.5];
+    let blurred = texture.filtered_value(uv, Point::default(), across_stripes).x();
+    let kept = texture.filtered_value(uv, Point::default(), along_stripes).x();
+    assert!((blurred - 0.5).abs() < 0.1);
+    assert!(kept > blurred);
+  }
+  #[test]
+  fn encoded_images_are_decoded_into_the_working_space() {
+    let image = RgbImage::new(1, 1, vec![Vec3::new([0.5, 1.0, 0.0])])
+      .unwrap()
+      .with_color_space(ColorSpace::Srgb);
+    let texture = ImageTexture::new(Arc::new(image), WrapMode::Clamp);
+    assert_eq!(texture.image().color_space(), ColorSpace::LinearRec709);
+    let color = texture.value([0.5, 0.5], Point::default());
+    assert!((color - Vec3::new([0.214_041, 1.0, 0.0])).length().as_f32() < 1e-4);
+  }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::{rgb_image::RgbImage, textures::image_texture::WrapMode, vec3::Vec3};
+
+/// A pyramid of successively halved copies of an image, down to a single texel. Each texel is the box average of the
+/// 2x2 texels beneath it; odd sizes round up and repeat their last row or column.
+#[derive(Debug, Clone)]
+pub struct MipMap {
+  levels: Vec<Arc<RgbImage>>,
+}
+
+impl MipMap {
+  pub fn new(image: Arc<RgbImage>) -> Self {
+    let mut levels = vec![image];
+    while let Some(finer) = levels.last().filter(|level| level.width() > 1 || level.height() > 1) {
+      let coarser = downsample(finer);
+      levels.push(Arc::new(coarser));
+    }
+    Self { levels }
+  }
+  /// The pyramid from the full-resolution image at index zero to the single texel at the end.
+  pub fn levels(&self) -> &[Arc<RgbImage>] {
+    &self.levels
+  }
+  /// The texel at `column`, `row` of `level`, with out-of-range indices wrapped by `wrap_mode`.
+  pub fn texel(&self, level: usize, column: i64, row: i64, wrap_mode: WrapMode) -> Vec3 {
+    let image = &self.levels[level];
+    image.pixel(wrap_mode.texel(column, image.width()), wrap_mode.texel(row, image.height()))
+  }
+  /// Bilinearly filters `level` at `uv`, with `v = 0` at the bottom row.
+  #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+  pub fn bilinear(&self, level: usize, uv: [f32; 2], wrap_mode: WrapMode) -> Vec3 {
+    let image = &self.levels[level];
+    let x = uv[0] * image.width() as f32 - 0.5;
+    let y = (1.0 - uv[1]) * image.height() as f32 - 0.5;
+    let (column, row) = (x.floor(), y.floor());
+    let (horizontal, vertical) = (x - column, y - row);
+    let (column, row) = (column as i64, row as i64);
+    let texel = |column_offset: i64, row_offset: i64| self.texel(level, column + column_offset, row + row_offset, wrap_mode);
+    let top = texel(0, 0) * (1.0 - horizontal) + texel(1, 0) * horizontal;
+    let bottom = texel(0, 1) * (1.0 - horizontal) + texel(1, 1) * horizontal;
+    top * (1.0 - vertical) + bottom * vertical
+  }
+}
+
+fn downsample(image: &RgbImage) -> RgbImage {
+  let (width, height) = ((image.width() + 1) / 2, (image.height() + 1) / 2);
+  let pixels = (0..height)
+    .flat_map(|row| (0..width).map(move |column| (column, row)))
+    .map(|(column, row)| {
+      let columns = [2 * column, (2 * column + 1).min(image.width() - 1)];
+      let rows = [2 * row, (2 * row + 1).min(image.height() - 1)];
+      let sum = rows
+        .iter()
+        .flat_map(|row| columns.iter().map(move |column| image.pixel(*column, *row)))
+        .fold(Vec3::default(), |sum, pixel| sum + pixel);
+      sum * 0.25
+    })
+    .collect();
+  RgbImage::new(width, height, pixels)
+    .expect("A downsampled image keeps at least one pixel")
+    .with_color_space(image.color_space())
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{rgb_image::RgbImage, vec3::Vec3};
+
+  use super::MipMap;
+
+  #[test]
+  fn levels_halve_down_to_one_averaged_texel() {
+    let pixels = (0..12_u16).map(|index| Vec3::new([f32::from(index); 3])).collect();
+    let mip_map = MipMap::new(Arc::new(RgbImage::new(4, 3, pixels).unwrap()));
+    let sizes: Vec<_> = mip_map.levels().iter().map(|level| (level.width(), level.height())).collect();
+    assert_eq!(sizes, vec![(4, 3), (2, 2), (1, 1)]);
+    assert!((mip_map.levels()[1].pixel(0, 0).x() - 2.5).abs() < 1e-6);
+    assert!((mip_map.levels()[1].pixel(1, 1).x() - 10.5).abs() < 1e-6);
+    assert!((mip_map.levels()[2].pixel(0, 0).x() - 6.5).abs() < 1e-6);
+  }
+}
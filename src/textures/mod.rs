@@ -0,0 +1,46 @@
+pub mod checker;
+pub mod color_ramp;
+pub mod image_texture;
+pub mod mip_map;
+pub mod noise;
+pub mod noise_texture;
+pub mod solid_color;
+
+use std::fmt::Debug;
+
+use crate::{newtypes::point::Point, vec3::Vec3};
+
+/// How far texture coordinates move across one pixel, as `(du, dv)` along the image x and y directions.
+/// A zero footprint asks for a point sample.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct TextureFootprint {
+  along_x: [f32; 2],
+  along_y: [f32; 2],
+}
+
+impl TextureFootprint {
+  pub const fn new(along_x: [f32; 2], along_y: [f32; 2]) -> Self {
+    Self { along_x, along_y }
+  }
+  pub const fn along_x(self) -> [f32; 2] {
+    self.along_x
+  }
+  pub const fn along_y(self) -> [f32; 2] {
+    self.along_y
+  }
+  /// The length of the longer of the two footprint axes, in texture coordinates.
+  pub fn width(self) -> f32 {
+    self.along_x[0]
+      .hypot(self.along_x[1])
+      .max(self.along_y[0].hypot(self.along_y[1]))
+  }
+}
+
+pub trait Texture: Debug + Send + Sync {
+  /// The color at surface coordinates `uv` and world position `point`.
+  fn value(&self, uv: [f32; 2], point: Point) -> Vec3;
+  /// The color averaged over `footprint`; textures that do not alias simply evaluate at its center.
+  fn filtered_value(&self, uv: [f32; 2], point: Point, _footprint: TextureFootprint) -> Vec3 {
+    self.value(uv, point)
+  }
+}
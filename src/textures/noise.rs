@@ -0,0 +1,240 @@
+use std::fmt::Debug;
+
+use crate::{newtypes::point::Point, random::Random};
+
+const PERLIN_POINT_COUNT: usize = 256;
+
+/// A smooth scalar field defined everywhere in space.
+pub trait Noise: Debug + Send + Sync {
+  fn noise(&self, point: Point) -> f32;
+  /// Whether samples span `[-1, 1]` around zero rather than starting at zero.
+  fn is_signed(&self) -> bool {
+    false
+  }
+}
+
+fn shuffled_indices(random: &mut Random) -> [usize; PERLIN_POINT_COUNT] {
+  let mut indices = [0; PERLIN_POINT_COUNT];
+  for (index, value) in indices.iter_mut().enumerate() {
+    *value = index;
+  }
+  for index in (1..PERLIN_POINT_COUNT).rev() {
+    let other = usize::try_from(random.next_u32()).expect("A u32 fits in a usize") % (index + 1);
+    indices.swap(index, other);
+  }
+  indices
+}
+
+/// Gradient noise with a random unit gradient at each lattice point, roughly in `[-1, 1]` and zero on the lattice.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Perlin {
+  gradients: Vec<[f32; 3]>,
+  permutations: [[usize; PERLIN_POINT_COUNT]; 3],
+}
+
+impl Perlin {
+  pub fn new(seed: u64) -> Self {
+    let mut random = Random::new(seed);
+    let gradients = (0..PERLIN_POINT_COUNT)
+      .map(|_| {
+        let gradient = random.unit_direction();
+        [gradient.x(), gradient.y(), gradient.z()]
+      })
+      .collect();
+    let permutations = [
+      shuffled_indices(&mut random),
+      shuffled_indices(&mut random),
+      shuffled_indices(&mut random),
+    ];
+    Self { gradients, permutations }
+  }
+  #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  fn gradient(&self, lattice: [i64; 3]) -> [f32; 3] {
+    let wrap = |value: i64| value.rem_euclid(PERLIN_POINT_COUNT as i64) as usize;
+    let index = self.permutations[0][wrap(lattice[0])]
+      ^ self.permutations[1][wrap(lattice[1])]
+      ^ self.permutations[2][wrap(lattice[2])];
+    self.gradients[index]
+  }
+}
+
+impl Noise for Perlin {
+  #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+  fn noise(&self, point: Point) -> f32 {
+    let coordinates = [point.x(), point.y(), point.z()];
+    let lattice = coordinates.map(|coordinate| coordinate.floor() as i64);
+    let fraction = [
+      coordinates[0] - coordinates[0].floor(),
+      coordinates[1] - coordinates[1].floor(),
+      coordinates[2] - coordinates[2].floor(),
+    ];
+    let smooth = fraction.map(|value| value * value * (3.0 - 2.0 * value));
+    let mut total = 0.0;
+    for corner in 0..8_u8 {
+      let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1].map(i64::from);
+      let gradient = self.gradient([lattice[0] + offset[0], lattice[1] + offset[1], lattice[2] + offset[2]]);
+      let mut weight = 1.0;
+      let mut dot = 0.0;
+      for axis in 0..3 {
+        let to_point = fraction[axis] - offset[axis] as f32;
+        weight *= if offset[axis] == 1 { smooth[axis] } else { 1.0 - smooth[axis] };
+        dot += gradient[axis] * to_point;
+      }
+      total += weight * dot;
+    }
+    total
+  }
+  fn is_signed(&self) -> bool {
+    true
+  }
+}
+
+/// Mixes a seed and a lattice cell into a well distributed 64 bit value (the `SplitMix64` finaliser).
+#[allow(clippy::cast_sign_loss)]
+fn cell_seed(seed: u64, cell: [i64; 3]) -> u64 {
+  cell.iter().fold(seed, |hash, coordinate| {
+    let mut value = hash ^ (*coordinate as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+  })
+}
+
+/// Cellular noise: the distance to the nearest of one random feature point per unit cell, in `[0, sqrt(3)]`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Worley {
+  seed: u64,
+}
+
+impl Worley {
+  pub const fn new(seed: u64) -> Self {
+    Self { seed }
+  }
+  #[allow(clippy::cast_precision_loss)]
+  fn feature_point(self, cell: [i64; 3]) -> [f32; 3] {
+    let mut random = Random::new(cell_seed(self.seed, cell));
+    [
+      cell[0] as f32 + random.next_f32(),
+      cell[1] as f32 + random.next_f32(),
+      cell[2] as f32 + random.next_f32(),
+    ]
+  }
+}
+
+impl Noise for Worley {
+  #[allow(clippy::cast_possible_truncation)]
+  fn noise(&self, point: Point) -> f32 {
+    let coordinates = [point.x(), point.y(), point.z()];
+    let cell = coordinates.map(|coordinate| coordinate.floor() as i64);
+    let mut nearest = f32::INFINITY;
+    for x in -1..=1 {
+      for y in -1..=1 {
+        for z in -1..=1 {
+          let feature = self.feature_point([cell[0] + x, cell[1] + y, cell[2] + z]);
+          let distance_squared = (0..3).map(|axis| (feature[axis] - coordinates[axis]).powi(2)).sum::<f32>();
+          nearest = nearest.min(distance_squared);
+        }
+      }
+    }
+    nearest.sqrt()
+  }
+}
+
+/// Sums octaves of a noise, each `lacunarity` times the frequency and `gain` times the weight of the last.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Fractal {
+  octaves: u8,
+  lacunarity: f32,
+  gain: f32,
+}
+
+impl Default for Fractal {
+  fn default() -> Self {
+    Self::new(5, 2.0, 0.5)
+  }
+}
+
+impl Fractal {
+  pub const fn new(octaves: u8, lacunarity: f32, gain: f32) -> Self {
+    Self {
+      octaves,
+      lacunarity,
+      gain,
+    }
+  }
+  pub const fn octaves(self) -> u8 {
+    self.octaves
+  }
+  fn sum(self, noise: &dyn Noise, point: Point, shape: impl Fn(f32) -> f32) -> f32 {
+    let mut total = 0.0;
+    let mut frequency = 1.0;
+    let mut weight = 1.0;
+    for _ in 0..self.octaves {
+      total += weight * shape(noise.noise(Point::from(point.as_vec3() * frequency)));
+      frequency *= self.lacunarity;
+      weight *= self.gain;
+    }
+    total
+  }
+  /// Fractional Brownian motion: the plain weighted sum of octaves.
+  pub fn fbm(self, noise: &dyn Noise, point: Point) -> f32 {
+    self.sum(noise, point, |value| value)
+  }
+  /// The weighted sum of absolute octaves, which gives creases where the noise crosses zero.
+  pub fn turbulence(self, noise: &dyn Noise, point: Point) -> f32 {
+    self.sum(noise, point, f32::abs)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::newtypes::point::Point;
+
+  use super::{Fractal, Noise, Perlin, Worley};
+
+  #[test]
+  fn perlin_is_seeded_and_zero_on_the_lattice() {
+    let point = Point::from_f32_array_const([1.3, -2.7, 0.45]);
+    assert!(Perlin::new(7).noise(point).eq(&Perlin::new(7).noise(point)));
+    assert!(Perlin::new(7).noise(point).ne(&Perlin::new(8).noise(point)));
+    assert!(Perlin::new(7).noise(Point::from_f32_array_const([3.0, -1.0, 2.0])).abs() < 1e-6);
+    let perlin = Perlin::new(1);
+    for step in 0..1000_u16 {
+      let coordinate = f32::from(step) * 0.037;
+      let value = perlin.noise(Point::from_f32_array_const([coordinate, coordinate * 0.5, -coordinate]));
+      assert!(value.abs() <= 1.0);
+    }
+  }
+  #[test]
+  fn perlin_is_continuous_across_cells() {
+    let perlin = Perlin::new(2);
+    let below = perlin.noise(Point::from_f32_array_const([0.9999, 0.3, 0.6]));
+    let above = perlin.noise(Point::from_f32_array_const([1.0001, 0.3, 0.6]));
+    assert!((below - above).abs() < 1e-3);
+  }
+  #[test]
+  fn worley_is_seeded_and_bounded() {
+    let point = Point::from_f32_array_const([4.2, 0.1, -3.3]);
+    assert!(Worley::new(3).noise(point).eq(&Worley::new(3).noise(point)));
+    assert!(Worley::new(3).noise(point).ne(&Worley::new(4).noise(point)));
+    let worley = Worley::new(5);
+    for step in 0..1000_u16 {
+      let coordinate = f32::from(step) * 0.041;
+      let value = worley.noise(Point::from_f32_array_const([coordinate, -coordinate, coordinate * 0.3]));
+      assert!((0.0..=3.0_f32.sqrt()).contains(&value));
+    }
+  }
+  #[test]
+  fn fractal_sums_weighted_octaves() {
+    #[derive(Debug)]
+    struct Constant;
+    impl Noise for Constant {
+      fn noise(&self, _point: Point) -> f32 {
+        -1.0
+      }
+    }
+    let fractal = Fractal::new(3, 2.0, 0.5);
+    assert!((fractal.fbm(&Constant, Point::default()) + 1.75).abs() < 1e-6);
+    assert!((fractal.turbulence(&Constant, Point::default()) - 1.75).abs() < 1e-6);
+  }
+}
@@ -0,0 +1,154 @@
+use std::{f32::consts::PI, sync::Arc};
+
+use crate::{
+  newtypes::point::Point,
+  textures::{
+    color_ramp::ColorRamp,
+    noise::{Fractal, Noise},
+    Texture,
+  },
+  vec3::Vec3,
+};
+
+/// How noise samples are shaped into the scalar fed to the color ramp.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum Pattern {
+  /// Signed noise such as Perlin is remapped from `[-1, 1]`; Worley distances pass through unchanged.
+  Plain,
+  Fbm(Fractal),
+  /// Billowy and creased, suited to stone.
+  Turbulence(Fractal),
+  /// Sine bands along x whose phase is pushed around by turbulence.
+  Marble {
+    fractal: Fractal,
+    distortion: f32,
+  },
+  /// Rings around the y axis, perturbed by fBm.
+  Wood {
+    fractal: Fractal,
+    distortion: f32,
+  },
+}
+
+/// A procedural texture: a noise, sampled at `frequency` times the world position and shaped by `pattern`,
+/// then colored by `ramp`.
+#[derive(Debug, Clone)]
+pub struct NoiseTexture {
+  noise: Arc<dyn Noise>,
+  pattern: Pattern,
+  frequency: f32,
+  ramp: ColorRamp,
+}
+
+impl NoiseTexture {
+  pub fn new(noise: Arc<dyn Noise>, pattern: Pattern, frequency: f32, ramp: ColorRamp) -> Self {
+    Self {
+      noise,
+      pattern,
+      frequency,
+      ramp,
+    }
+  }
+  pub const fn pattern(&self) -> Pattern {
+    self.pattern
+  }
+  pub const fn frequency(&self) -> f32 {
+    self.frequency
+  }
+  pub const fn ramp(&self) -> &ColorRamp {
+    &self.ramp
+  }
+  pub fn scalar(&self, point: Point) -> f32 {
+    let point = Point::from(point.as_vec3() * self.frequency);
+    let noise = self.noise.as_ref();
+    match self.pattern {
+      Pattern::Plain if noise.is_signed() => 0.5 * (noise.noise(point) + 1.0),
+      Pattern::Plain => noise.noise(point),
+      Pattern::Fbm(fractal) => 0.5 * (fractal.fbm(noise, point) + 1.0),
+      Pattern::Turbulence(fractal) => fractal.turbulence(noise, point),
+      Pattern::Marble { fractal, distortion } => {
+        0.5 * (1.0 + (point.x() + distortion * fractal.turbulence(noise, point)).sin())
+      }
+      Pattern::Wood { fractal, distortion } => {
+        let radius = (point.x() * point.x() + point.z() * point.z()).sqrt();
+        let rings = radius + distortion * fractal.fbm(noise, point);
+        0.5 * (1.0 + (2.0 * PI * rings).sin())
+      }
+    }
+  }
+}
+
+impl Texture for NoiseTexture {
+  fn value(&self, _uv: [f32; 2], point: Point) -> Vec3 {
+    self.ramp.color_at(self.scalar(point))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    newtypes::point::Point,
+    textures::{
+      color_ramp::ColorRamp,
+      noise::{Fractal, Noise, Perlin, Worley},
+      Texture,
+    },
+  };
+
+  use super::{NoiseTexture, Pattern};
+
+  #[test]
+  fn same_seed_renders_the_same_texture() {
+    let marble = |seed: u64| {
+      NoiseTexture::new(
+        Arc::new(Perlin::new(seed)),
+        Pattern::Marble {
+          fractal: Fractal::default(),
+          distortion: 5.0,
+        },
+        4.0,
+        ColorRamp::grayscale(),
+      )
+    };
+    let point = Point::from_f32_array_const([0.3, 0.7, -0.2]);
+    assert_eq!(marble(11).value([0.0, 0.0], point), marble(11).value([0.0, 0.0], point));
+    assert_ne!(marble(11).value([0.0, 0.0], point), marble(12).value([0.0, 0.0], point));
+  }
+  #[test]
+  fn patterns_stay_inside_the_ramp() {
+    let patterns = [
+      Pattern::Plain,
+      Pattern::Fbm(Fractal::default()),
+      Pattern::Wood {
+        fractal: Fractal::default(),
+        distortion: 0.3,
+      },
+    ];
+    for pattern in patterns {
+      let texture = NoiseTexture::new(Arc::new(Perlin::new(1)), pattern, 3.0, ColorRamp::grayscale());
+      for step in 0..200_u16 {
+        let coordinate = f32::from(step) * 0.013;
+        let scalar = texture.scalar(Point::from_f32_array_const([coordinate, 0.5, coordinate]));
+        assert!((-0.1..=1.1).contains(&scalar));
+      }
+    }
+    let stone = NoiseTexture::new(Arc::new(Worley::new(2)), Pattern::Plain, 1.0, ColorRamp::grayscale());
+    assert!(stone.scalar(Point::from_f32_array_const([0.2, 0.4, 0.6])) >= 0.0);
+  }
+  #[test]
+  fn plain_perlin_is_continuous_where_the_noise_crosses_zero() {
+    let perlin = Arc::new(Perlin::new(3));
+    let texture = NoiseTexture::new(perlin.clone(), Pattern::Plain, 1.0, ColorRamp::grayscale());
+    let at = |step: u16| Point::from_f32_array_const([f32::from(step) * 0.001, 0.37, 0.61]);
+    let mut crossings = 0;
+    for step in 0..4000_u16 {
+      if (perlin.noise(at(step)) < 0.0) != (perlin.noise(at(step + 1)) < 0.0) {
+        crossings += 1;
+      }
+      assert!((texture.scalar(at(step)) - texture.scalar(at(step + 1))).abs() < 0.01);
+    }
+    assert!(crossings > 0);
+  }
+}
@@ -0,0 +1,21 @@
+use crate::{newtypes::point::Point, textures::Texture, vec3::Vec3};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SolidColor {
+  color: Vec3,
+}
+
+impl SolidColor {
+  pub const fn new(color: Vec3) -> Self {
+    Self { color }
+  }
+  pub const fn color(&self) -> Vec3 {
+    self.color
+  }
+}
+
+impl Texture for SolidColor {
+  fn value(&self, _uv: [f32; 2], _point: Point) -> Vec3 {
+    self.color
+  }
+}
@@ -0,0 +1,216 @@
+use crate::{error::LocalError, newtypes::radiance::Radiance, vec3::Vec3};
+
+type Matrix = [[f32; 3]; 3];
+
+fn apply(matrix: &Matrix, color: Vec3) -> Vec3 {
+  let row = |row: [f32; 3]| row[0] * color.x() + row[1] * color.y() + row[2] * color.z();
+  Vec3::new([row(matrix[0]), row(matrix[1]), row(matrix[2])])
+}
+
+fn per_channel(color: Vec3, curve: impl Fn(f32) -> f32) -> Vec3 {
+  Vec3::new([curve(color.x()), curve(color.y()), curve(color.z())])
+}
+
+/// Radiance beyond this maps to white under every operator; clamping to it keeps the curves' squares finite.
+const MAXIMUM_RADIANCE: f32 = 1e6;
+
+/// The filmic curve John Hable made for Uncharted 2, with its shoulder, linear, toe and white parameters.
+fn hable_curve(value: f32) -> f32 {
+  let (shoulder, linear, angle, toe, numerator, denominator) = (0.15, 0.5, 0.1, 0.2, 0.02, 0.3);
+  (value * (shoulder * value + angle * linear) + toe * numerator) / (value * (shoulder * value + linear) + toe * denominator)
+    - numerator / denominator
+}
+const HABLE_EXPOSURE_BIAS: f32 = 2.0;
+const HABLE_WHITE: f32 = 11.2;
+
+/// Stephen Hill's fit of the ACES reference rendering and `sRGB` output transforms, with the transforms from and to
+/// linear Rec.709 around it.
+const ACES_INPUT: Matrix = [
+  [0.597_19, 0.354_58, 0.048_23],
+  [0.076, 0.908_34, 0.015_66],
+  [0.028_4, 0.133_83, 0.837_77],
+];
+const ACES_OUTPUT: Matrix = [
+  [1.604_75, -0.531_08, -0.073_67],
+  [-0.102_08, 1.108_13, -0.006_05],
+  [-0.003_27, -0.072_76, 1.076_02],
+];
+fn aces_curve(value: f32) -> f32 {
+  (value * (value + 0.024_578_6) - 0.000_090_537) / (value * (0.983_729 * value + 0.432_951) + 0.238_081)
+}
+
+/// `AgX` as fitted by Benjamin Wrensch: an inset into a smaller gamut, a log encoding over the exposure range below,
+/// a polynomial sigmoid, and the outset back to Rec.709 display values with a 2.2 gamma.
+const AGX_INSET: Matrix = [
+  [0.842_479_1, 0.078_433_6, 0.079_223_75],
+  [0.042_328_24, 0.878_468_6, 0.079_166_13],
+  [0.042_375_65, 0.078_433_6, 0.879_143],
+];
+const AGX_OUTSET: Matrix = [
+  [1.196_879, -0.098_020_88, -0.099_029_74],
+  [-0.052_896_85, 1.151_903_1, -0.098_961_18],
+  [-0.052_971_64, -0.098_043_45, 1.151_073_7],
+];
+const AGX_MINIMUM_EXPOSURE: f32 = -12.473_93;
+const AGX_MAXIMUM_EXPOSURE: f32 = 4.026_069;
+fn agx_curve(value: f32) -> f32 {
+  let exposure = value
+    .max(f32::MIN_POSITIVE)
+    .log2()
+    .clamp(AGX_MINIMUM_EXPOSURE, AGX_MAXIMUM_EXPOSURE);
+  let x = (exposure - AGX_MINIMUM_EXPOSURE) / (AGX_MAXIMUM_EXPOSURE - AGX_MINIMUM_EXPOSURE);
+  let (x2, x3) = (x * x, x * x * x);
+  let x4 = x2 * x2;
+  15.5 * x4 * x2 - 40.14 * x4 * x + 31.96 * x4 - 6.868 * x3 + 0.4298 * x2 + 0.1191 * x - 0.002_32
+}
+
+/// The luminance a Reinhard operator maps to white, always positive and finite.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct WhitePoint(f32);
+
+impl WhitePoint {
+  pub const fn as_f32(self) -> f32 {
+    self.0
+  }
+}
+
+/// How unbounded scene radiance is squeezed into the `[0, 1]` range of a display. Every operator takes and returns
+/// linear Rec.709.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum ToneMapping {
+  /// Clips each channel, for scenes already lit to fit.
+  Clip,
+  /// Reinhard's extended operator on luminance, which maps `white_point` and brighter to white.
+  Reinhard { white_point: WhitePoint },
+  /// Hable's filmic curve, with a soft toe and shoulder.
+  Hable,
+  /// The fitted ACES filmic curve, which desaturates highlights.
+  AcesFitted,
+  /// `AgX`, which keeps hues steady as bright colors fade to white.
+  AgX,
+}
+
+impl Default for ToneMapping {
+  fn default() -> Self {
+    Self::Clip
+  }
+}
+
+impl ToneMapping {
+  pub fn new_reinhard(white_point: f32) -> Result<Self, LocalError> {
+    if !(white_point.is_finite() && white_point > 0.0) {
+      return Err("A Reinhard white point must be positive and finite".into());
+    }
+    Ok(Self::Reinhard {
+      white_point: WhitePoint(white_point),
+    })
+  }
+  /// The display values, each in `[0, 1]`, for linear Rec.709 `radiance`. Negative and `NaN` channels count as zero.
+  pub fn apply(self, radiance: Radiance) -> Vec3 {
+    let radiance = per_channel(radiance.as_vec3(), |channel| channel.max(0.0).min(MAXIMUM_RADIANCE));
+    let mapped = match self {
+      Self::Clip => radiance,
+      Self::Reinhard { white_point } => {
+        let luminance = Radiance::from(radiance).luminance();
+        if luminance <= 0.0 {
+          return Vec3::default();
+        }
+        let white_point = white_point.as_f32();
+        let mapped = luminance * (1.0 + luminance / (white_point * white_point)) / (1.0 + luminance);
+        radiance * (mapped / luminance)
+      }
+      Self::Hable => per_channel(radiance, |channel| {
+        hable_curve(channel * HABLE_EXPOSURE_BIAS) / hable_curve(HABLE_WHITE)
+      }),
+      Self::AcesFitted => apply(&ACES_OUTPUT, per_channel(apply(&ACES_INPUT, radiance), aces_curve)),
+      Self::AgX => {
+        let display = apply(&AGX_OUTSET, per_channel(apply(&AGX_INSET, radiance), agx_curve));
+        per_channel(display, |channel| channel.max(0.0).powf(2.2))
+      }
+    };
+    per_channel(mapped, |channel| if channel.is_nan() { 0.0 } else { channel.clamp(0.0, 1.0) })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::newtypes::radiance::Radiance;
+
+  use super::ToneMapping;
+
+  fn operators() -> [ToneMapping; 5] {
+    [
+      ToneMapping::Clip,
+      ToneMapping::new_reinhard(4.0).unwrap(),
+      ToneMapping::Hable,
+      ToneMapping::AcesFitted,
+      ToneMapping::AgX,
+    ]
+  }
+
+  #[test]
+  fn rejects_invalid_white_points() {
+    assert!(ToneMapping::new_reinhard(0.0).is_err());
+    assert!(ToneMapping::new_reinhard(f32::NAN).is_err());
+    assert!(ToneMapping::new_reinhard(-4.0).is_err());
+    assert!(ToneMapping::new_reinhard(f32::INFINITY).is_err());
+  }
+  #[test]
+  fn greys_brighten_monotonically_and_stay_displayable() {
+    for operator in operators() {
+      let mut previous = 0.0;
+      for step in 0..=200_u16 {
+        let value = operator.apply(Radiance::new([f32::from(step) * 0.1; 3]));
+        assert!((0.0..=1.0).contains(&value.x()), "{:?} gave {}", operator, value);
+        assert!(value.x() >= previous - 1e-4, "{:?} darkened at {}", operator, step);
+        assert!((value.x() - value.y()).abs() < 1e-3 && (value.y() - value.z()).abs() < 1e-3);
+        previous = value.x();
+      }
+      assert!(operator.apply(Radiance::default()).x() < 0.01);
+    }
+  }
+  #[test]
+  fn huge_and_invalid_samples_stay_displayable() {
+    let samples = [
+      [3e19, 3e19, 3e19],
+      [1e38, 0.0, 0.0],
+      [f32::MAX, f32::MAX, 0.0],
+      [f32::INFINITY, 1.0, 0.0],
+      [f32::NAN, 1.0, -1.0],
+    ];
+    for operator in operators() {
+      for sample in samples {
+        let value = operator.apply(Radiance::new(sample));
+        for channel in [value.x(), value.y(), value.z()] {
+          assert!(
+            (0.0..=1.0).contains(&channel),
+            "{:?} gave {} for {:?}",
+            operator,
+            value,
+            sample
+          );
+        }
+      }
+    }
+  }
+  #[test]
+  fn curves_compress_highlights_instead_of_clipping() {
+    for operator in [ToneMapping::Hable, ToneMapping::AcesFitted, ToneMapping::AgX] {
+      let bright = operator.apply(Radiance::new([0.8; 3])).x();
+      let brighter = operator.apply(Radiance::new([1.6; 3])).x();
+      assert!(
+        bright < brighter && brighter < 1.0,
+        "{:?} gave {} and {}",
+        operator,
+        bright,
+        brighter
+      );
+    }
+  }
+  #[test]
+  fn reinhard_maps_the_white_point_to_white() {
+    let reinhard = ToneMapping::new_reinhard(4.0).unwrap();
+    assert!((reinhard.apply(Radiance::new([4.0; 3])).x() - 1.0).abs() < 1e-5);
+    assert!((reinhard.apply(Radiance::new([1.0; 3])).x() - 0.531_25).abs() < 1e-5);
+  }
+}
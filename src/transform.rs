@@ -0,0 +1,538 @@
+use std::ops::Mul;
+
+use crate::{
+  aabb::Aabb,
+  newtypes::{direction::Direction, point::Point},
+  quaternion::Quaternion,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+const SINGULAR_EPSILON: f32 = 1e-12;
+const MOTION_BOUND_SAMPLES: u16 = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Matrix4([[f32; 4]; 4]);
+
+impl Default for Matrix4 {
+  fn default() -> Self {
+    Self::identity()
+  }
+}
+
+impl Matrix4 {
+  pub const fn new(rows: [[f32; 4]; 4]) -> Self {
+    Self(rows)
+  }
+  pub const fn identity() -> Self {
+    Self([
+      [1.0, 0.0, 0.0, 0.0],
+      [0.0, 1.0, 0.0, 0.0],
+      [0.0, 0.0, 1.0, 0.0],
+      [0.0, 0.0, 0.0, 1.0],
+    ])
+  }
+  pub const fn rows(&self) -> [[f32; 4]; 4] {
+    self.0
+  }
+  pub fn transpose(&self) -> Self {
+    let mut transposed = [[0.0; 4]; 4];
+    for (row_index, row) in self.0.iter().enumerate() {
+      for (column_index, value) in row.iter().enumerate() {
+        transposed[column_index][row_index] = *value;
+      }
+    }
+    Self(transposed)
+  }
+  /// Gauss-Jordan elimination with partial pivoting; `None` when the matrix is singular.
+  pub fn inverse(&self) -> Option<Self> {
+    let mut working = self.0;
+    let mut inverse = Self::identity().0;
+    for column in 0..4 {
+      let pivot_row = (column..4).max_by(|left, right| {
+        working[*left][column]
+          .abs()
+          .partial_cmp(&working[*right][column].abs())
+          .unwrap_or(std::cmp::Ordering::Equal)
+      })?;
+      if working[pivot_row][column].abs() < SINGULAR_EPSILON {
+        return None;
+      }
+      working.swap(column, pivot_row);
+      inverse.swap(column, pivot_row);
+      let pivot = working[column][column];
+      for index in 0..4 {
+        working[column][index] /= pivot;
+        inverse[column][index] /= pivot;
+      }
+      for row in 0..4 {
+        if row == column {
+          continue;
+        }
+        let factor = working[row][column];
+        for index in 0..4 {
+          working[row][index] -= factor * working[column][index];
+          inverse[row][index] -= factor * inverse[column][index];
+        }
+      }
+    }
+    Some(Self(inverse))
+  }
+  fn apply(&self, input: Vec3, homogeneous: f32) -> Vec3 {
+    let row = |index: usize| {
+      let row = self.0[index];
+      row[0] * input.x() + row[1] * input.y() + row[2] * input.z() + row[3] * homogeneous
+    };
+    Vec3::new([row(0), row(1), row(2)])
+  }
+}
+
+impl Mul for Matrix4 {
+  type Output = Self;
+
+  fn mul(self, right_hand_side: Self) -> Self::Output {
+    let mut product = [[0.0; 4]; 4];
+    for (row, product_row) in product.iter_mut().enumerate() {
+      for (column, value) in product_row.iter_mut().enumerate() {
+        *value = (0..4)
+          .map(|index| self.0[row][index] * right_hand_side.0[index][column])
+          .sum();
+      }
+    }
+    Self(product)
+  }
+}
+
+/// An affine transform that keeps its inverse alongside it so rays can be moved into object space cheaply.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Transform {
+  matrix: Matrix4,
+  inverse: Matrix4,
+}
+
+impl Transform {
+  pub const fn identity() -> Self {
+    Self {
+      matrix: Matrix4::identity(),
+      inverse: Matrix4::identity(),
+    }
+  }
+  pub fn new_from_matrix(matrix: Matrix4) -> Option<Self> {
+    Some(Self {
+      matrix,
+      inverse: matrix.inverse()?,
+    })
+  }
+  pub const fn translation(offset: Direction) -> Self {
+    Self {
+      matrix: Matrix4::new([
+        [1.0, 0.0, 0.0, offset.x()],
+        [0.0, 1.0, 0.0, offset.y()],
+        [0.0, 0.0, 1.0, offset.z()],
+        [0.0, 0.0, 0.0, 1.0],
+      ]),
+      inverse: Matrix4::new([
+        [1.0, 0.0, 0.0, -offset.x()],
+        [0.0, 1.0, 0.0, -offset.y()],
+        [0.0, 0.0, 1.0, -offset.z()],
+        [0.0, 0.0, 0.0, 1.0],
+      ]),
+    }
+  }
+  /// Returns `None` when any factor is zero, since the scale could not be undone.
+  pub fn scale(factors: Vec3) -> Option<Self> {
+    if [factors.x(), factors.y(), factors.z()]
+      .iter()
+      .any(|factor| factor.abs() < SINGULAR_EPSILON)
+    {
+      return None;
+    }
+    Some(Self {
+      matrix: Matrix4::new([
+        [factors.x(), 0.0, 0.0, 0.0],
+        [0.0, factors.y(), 0.0, 0.0],
+        [0.0, 0.0, factors.z(), 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+      ]),
+      inverse: Matrix4::new([
+        [1.0 / factors.x(), 0.0, 0.0, 0.0],
+        [0.0, 1.0 / factors.y(), 0.0, 0.0],
+        [0.0, 0.0, 1.0 / factors.z(), 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+      ]),
+    })
+  }
+  /// A counter-clockwise rotation by `angle_in_radians` when looking down `axis` towards the origin.
+  pub fn rotation(axis: Direction, angle_in_radians: f32) -> Self {
+    let axis = axis.unit_direction();
+    let (sine, cosine) = angle_in_radians.sin_cos();
+    let one_minus_cosine = 1.0 - cosine;
+    let (x, y, z) = (axis.x(), axis.y(), axis.z());
+    let matrix = Matrix4::new([
+      [
+        cosine + x * x * one_minus_cosine,
+        x * y * one_minus_cosine - z * sine,
+        x * z * one_minus_cosine + y * sine,
+        0.0,
+      ],
+      [
+        y * x * one_minus_cosine + z * sine,
+        cosine + y * y * one_minus_cosine,
+        y * z * one_minus_cosine - x * sine,
+        0.0,
+      ],
+      [
+        z * x * one_minus_cosine - y * sine,
+        z * y * one_minus_cosine + x * sine,
+        cosine + z * z * one_minus_cosine,
+        0.0,
+      ],
+      [0.0, 0.0, 0.0, 1.0],
+    ]);
+    Self {
+      matrix,
+      inverse: matrix.transpose(),
+    }
+  }
+  /// Places an object at `from` with its local -z axis facing `to` and its local y axis as close to `up` as possible.
+  pub fn look_at(from: Point, to: Point, up: Direction) -> Option<Self> {
+    let w = Direction::from(from - to).unit_direction();
+    let u = up.cross(w);
+    if u.dot(u) < SINGULAR_EPSILON {
+      return None;
+    }
+    let u = u.unit_direction();
+    let v = w.cross(u);
+    let rotation = Matrix4::new([
+      [u.x(), v.x(), w.x(), 0.0],
+      [u.y(), v.y(), w.y(), 0.0],
+      [u.z(), v.z(), w.z(), 0.0],
+      [0.0, 0.0, 0.0, 1.0],
+    ]);
+    let rotation = Self {
+      matrix: rotation,
+      inverse: rotation.transpose(),
+    };
+    Some(rotation.then(&Self::translation(from.into())))
+  }
+  pub const fn matrix(&self) -> Matrix4 {
+    self.matrix
+  }
+  pub const fn inverse(&self) -> Self {
+    Self {
+      matrix: self.inverse,
+      inverse: self.matrix,
+    }
+  }
+  /// The transform that applies `self` first and `next` second.
+  pub fn then(&self, next: &Self) -> Self {
+    *next * *self
+  }
+  pub fn apply_to_point(&self, point: Point) -> Point {
+    self.matrix.apply(point.as_vec3(), 1.0).into()
+  }
+  pub fn apply_to_direction(&self, direction: Direction) -> Direction {
+    self.matrix.apply(direction.as_vec3(), 0.0).into()
+  }
+  /// Normals use the inverse transpose so they stay perpendicular under non-uniform scale.
+  pub fn apply_to_normal(&self, normal: Direction) -> Direction {
+    Direction::from(self.inverse.transpose().apply(normal.as_vec3(), 0.0)).unit_direction()
+  }
+  pub fn apply_to_ray(&self, ray: &Ray) -> Ray {
+    let transformed = Ray::new_at_time(
+      self.apply_to_point(ray.origin()),
+      self.apply_to_direction(ray.direction()),
+      ray.time(),
+    );
+    match ray.wavelength() {
+      Some(wavelength) => transformed.with_wavelength(wavelength),
+      None => transformed,
+    }
+  }
+  pub fn apply_to_bounding_box(&self, bounding_box: &Aabb) -> Aabb {
+    let minimum = bounding_box.minimum();
+    let maximum = bounding_box.maximum();
+    let corners = [0, 1, 2, 3, 4, 5, 6, 7].map(|corner: u8| {
+      self.apply_to_point(Point::from_f32_array_const([
+        if corner & 1 == 0 { minimum.x() } else { maximum.x() },
+        if corner & 2 == 0 { minimum.y() } else { maximum.y() },
+        if corner & 4 == 0 { minimum.z() } else { maximum.z() },
+      ]))
+    });
+    Aabb::new_from_points(&corners).expect("A box always has corners")
+  }
+}
+
+impl Mul for Transform {
+  type Output = Self;
+
+  fn mul(self, right_hand_side: Self) -> Self::Output {
+    Self {
+      matrix: self.matrix * right_hand_side.matrix,
+      inverse: right_hand_side.inverse * self.inverse,
+    }
+  }
+}
+
+/// A transform split into parts that interpolate well: scale, then rotation, then translation.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Pose {
+  translation: Direction,
+  rotation: Quaternion,
+  scale: Vec3,
+}
+
+impl Default for Pose {
+  fn default() -> Self {
+    Self::new(Direction::default(), Quaternion::identity(), Vec3::new([1.0, 1.0, 1.0]))
+  }
+}
+
+impl Pose {
+  pub const fn new(translation: Direction, rotation: Quaternion, scale: Vec3) -> Self {
+    Self {
+      translation,
+      rotation,
+      scale,
+    }
+  }
+  pub const fn translation(&self) -> Direction {
+    self.translation
+  }
+  pub const fn rotation(&self) -> Quaternion {
+    self.rotation
+  }
+  pub const fn scale(&self) -> Vec3 {
+    self.scale
+  }
+  pub fn interpolate(&self, end: &Self, amount: f32) -> Self {
+    Self {
+      translation: self.translation * (1.0 - amount) + end.translation * amount,
+      rotation: self.rotation.slerp(&end.rotation, amount),
+      scale: self.scale * (1.0 - amount) + end.scale * amount,
+    }
+  }
+  /// Returns `None` when a scale factor is zero.
+  pub fn to_transform(self) -> Option<Transform> {
+    let rotation = self.rotation.to_matrix();
+    let rotation = Transform {
+      matrix: rotation,
+      inverse: rotation.transpose(),
+    };
+    Some(
+      Transform::scale(self.scale)?
+        .then(&rotation)
+        .then(&Transform::translation(self.translation)),
+    )
+  }
+}
+
+/// Moves between two poses while the shutter is open; times outside the range hold the nearest pose.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct AnimatedTransform {
+  start: Pose,
+  end: Pose,
+  start_time: f32,
+  end_time: f32,
+}
+
+impl AnimatedTransform {
+  /// Returns `None` when either pose cannot be inverted, or when a scale factor changes sign and so passes through
+  /// zero on the way.
+  pub fn new(start: Pose, end: Pose, start_time: f32, end_time: f32) -> Option<Self> {
+    start.to_transform()?;
+    end.to_transform()?;
+    let (start_scale, end_scale) = (start.scale(), end.scale());
+    let flips = |start: f32, end: f32| start.is_sign_positive() != end.is_sign_positive();
+    if flips(start_scale.x(), end_scale.x())
+      || flips(start_scale.y(), end_scale.y())
+      || flips(start_scale.z(), end_scale.z())
+    {
+      return None;
+    }
+    Some(Self {
+      start,
+      end,
+      start_time,
+      end_time,
+    })
+  }
+  pub fn pose_at(&self, time: f32) -> Pose {
+    let duration = self.end_time - self.start_time;
+    if duration <= 0.0 {
+      return self.start;
+    }
+    let amount = ((time - self.start_time) / duration).clamp(0.0, 1.0);
+    self.start.interpolate(&self.end, amount)
+  }
+  pub fn at(&self, time: f32) -> Transform {
+    self
+      .pose_at(time)
+      .to_transform()
+      .expect("Poses between two invertible poses are invertible")
+  }
+  /// Unions the box at evenly spaced times, padded by half of the largest corner movement between samples
+  /// so the path between samples stays covered.
+  pub fn apply_to_bounding_box(&self, bounding_box: &Aabb) -> Aabb {
+    let duration = self.end_time - self.start_time;
+    let boxes: Vec<Aabb> = (0..=MOTION_BOUND_SAMPLES)
+      .map(|sample| {
+        let time = self.start_time + duration * f32::from(sample) / f32::from(MOTION_BOUND_SAMPLES);
+        self.at(time).apply_to_bounding_box(bounding_box)
+      })
+      .collect();
+    let padding = boxes
+      .windows(2)
+      .map(|pair| {
+        let minimum_movement = (pair[1].minimum().as_vec3() - pair[0].minimum().as_vec3()).length();
+        let maximum_movement = (pair[1].maximum().as_vec3() - pair[0].maximum().as_vec3()).length();
+        minimum_movement.as_f32().max(maximum_movement.as_f32()) / 2.0
+      })
+      .fold(0.0, f32::max);
+    let union = boxes
+      .iter()
+      .skip(1)
+      .fold(boxes[0], |union, bounding_box| union.surrounding(bounding_box));
+    let padding = Point::from_f32_array_const([padding; 3]);
+    Aabb::new(union.minimum() - padding, union.maximum() + padding)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::f32::consts::FRAC_PI_2;
+
+  use crate::{
+    newtypes::{direction::Direction, point::Point},
+    vec3::Vec3,
+  };
+
+  use super::{AnimatedTransform, Matrix4, Pose, Transform};
+
+  fn assert_near(actual: Vec3, expected: [f32; 3]) {
+    let error = actual - Vec3::new(expected);
+    assert!(error.length().as_f32() < 1e-5, "{:?} != {:?}", actual, expected);
+  }
+
+  #[test]
+  fn translation_moves_points_but_not_directions() {
+    let transform = Transform::translation(Direction::from_f32_array_const([1.0, 2.0, 3.0]));
+    assert_near(
+      transform
+        .apply_to_point(Point::from_f32_array_const([1.0, 1.0, 1.0]))
+        .as_vec3(),
+      [2.0, 3.0, 4.0],
+    );
+    assert_near(
+      transform
+        .apply_to_direction(Direction::from_f32_array_const([1.0, 1.0, 1.0]))
+        .as_vec3(),
+      [1.0, 1.0, 1.0],
+    );
+  }
+  #[test]
+  fn rotation_about_z_turns_x_into_y() {
+    let transform = Transform::rotation(Direction::from_f32_array_const([0.0, 0.0, 1.0]), FRAC_PI_2);
+    assert_near(
+      transform
+        .apply_to_direction(Direction::from_f32_array_const([1.0, 0.0, 0.0]))
+        .as_vec3(),
+      [0.0, 1.0, 0.0],
+    );
+  }
+  #[test]
+  fn composition_applies_in_order() {
+    let rotate = Transform::rotation(Direction::from_f32_array_const([0.0, 0.0, 1.0]), FRAC_PI_2);
+    let translate = Transform::translation(Direction::from_f32_array_const([1.0, 0.0, 0.0]));
+    let point = Point::from_f32_array_const([1.0, 0.0, 0.0]);
+    assert_near(rotate.then(&translate).apply_to_point(point).as_vec3(), [1.0, 1.0, 0.0]);
+    assert_near(translate.then(&rotate).apply_to_point(point).as_vec3(), [0.0, 2.0, 0.0]);
+  }
+  #[test]
+  fn inverse_undoes_transform() {
+    let transform = Transform::scale(Vec3::new([2.0, 3.0, 4.0]))
+      .unwrap()
+      .then(&Transform::rotation(Direction::from_f32_array_const([1.0, 1.0, 0.0]), 0.7))
+      .then(&Transform::translation(Direction::from_f32_array_const([-1.0, 5.0, 2.0])));
+    let point = Point::from_f32_array_const([0.3, -0.2, 0.9]);
+    let round_trip = transform.inverse().apply_to_point(transform.apply_to_point(point));
+    assert_near(round_trip.as_vec3(), [0.3, -0.2, 0.9]);
+    let general_inverse = transform.matrix().inverse().expect("The transform is invertible");
+    let identity = (transform.matrix() * general_inverse).rows();
+    for (row_index, row) in identity.iter().enumerate() {
+      for (column_index, value) in row.iter().enumerate() {
+        let expected = if row_index == column_index { 1.0 } else { 0.0 };
+        assert!((value - expected).abs() < 1e-5);
+      }
+    }
+    assert!(Matrix4::new([[0.0; 4]; 4]).inverse().is_none());
+    assert!(Transform::scale(Vec3::new([1.0, 0.0, 1.0])).is_none());
+  }
+  #[test]
+  fn normals_stay_perpendicular_under_scale() {
+    let transform = Transform::scale(Vec3::new([2.0, 1.0, 1.0])).unwrap();
+    let normal = transform.apply_to_normal(Direction::from_f32_array_const([1.0, 1.0, 0.0]).unit_direction());
+    let tangent = transform.apply_to_direction(Direction::from_f32_array_const([1.0, -1.0, 0.0]));
+    assert!(normal.dot(tangent).abs() < 1e-6);
+  }
+  #[test]
+  fn animated_transform_interpolates_poses() {
+    let start = Pose::default();
+    let end = Pose::new(
+      Direction::from_f32_array_const([2.0, 0.0, 0.0]),
+      crate::quaternion::Quaternion::from_axis_angle(Direction::from_f32_array_const([0.0, 0.0, 1.0]), FRAC_PI_2),
+      Vec3::new([1.0, 1.0, 1.0]),
+    );
+    let animated = AnimatedTransform::new(start, end, 0.0, 1.0).expect("Both poses are invertible");
+    let point = Point::from_f32_array_const([1.0, 0.0, 0.0]);
+    assert_near(animated.at(-1.0).apply_to_point(point).as_vec3(), [1.0, 0.0, 0.0]);
+    assert_near(animated.at(1.0).apply_to_point(point).as_vec3(), [2.0, 1.0, 0.0]);
+    let halfway = animated.at(0.5).apply_to_point(point).as_vec3();
+    let angle = std::f32::consts::FRAC_PI_4;
+    assert_near(halfway, [1.0 + angle.cos(), angle.sin(), 0.0]);
+  }
+  #[test]
+  fn animated_transform_rejects_scales_that_flip_sign() {
+    let mirrored = Pose::new(
+      Direction::default(),
+      crate::quaternion::Quaternion::identity(),
+      Vec3::new([-1.0, 1.0, 1.0]),
+    );
+    assert!(AnimatedTransform::new(Pose::default(), mirrored, 0.0, 1.0).is_none());
+    let flipped = Pose::new(
+      Direction::default(),
+      crate::quaternion::Quaternion::identity(),
+      Vec3::new([-2.0, 1.0, 1.0]),
+    );
+    let animated = AnimatedTransform::new(mirrored, flipped, 0.0, 1.0).expect("Both poses mirror the same axis");
+    assert_near(
+      animated
+        .at(0.5)
+        .apply_to_point(Point::from_f32_array_const([1.0, 0.0, 0.0]))
+        .as_vec3(),
+      [-1.5, 0.0, 0.0],
+    );
+  }
+  #[test]
+  fn look_at_faces_target() {
+    let transform = Transform::look_at(
+      Point::from_f32_array_const([1.0, 0.0, 0.0]),
+      Point::from_f32_array_const([1.0, 0.0, -5.0]),
+      Direction::from_f32_array_const([0.0, 1.0, 0.0]),
+    )
+    .expect("Up is not parallel to the view direction");
+    assert_near(
+      transform
+        .apply_to_direction(Direction::from_f32_array_const([0.0, 0.0, -1.0]))
+        .as_vec3(),
+      [0.0, 0.0, -1.0],
+    );
+    assert_near(
+      transform
+        .apply_to_point(Point::from_f32_array_const([0.0, 0.0, 0.0]))
+        .as_vec3(),
+      [1.0, 0.0, 0.0],
+    );
+  }
+}
@@ -1,10 +1,28 @@
 use std::{
   fmt::Display,
-  ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+  ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 use crate::newtypes::distance::Distance;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Axis {
+  X,
+  Y,
+  Z,
+}
+
+impl Axis {
+  pub const ALL: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+  const fn index(self) -> usize {
+    match self {
+      Axis::X => 0,
+      Axis::Y => 1,
+      Axis::Z => 2,
+    }
+  }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Vec3([f32; 3]);
 
@@ -25,6 +43,26 @@ impl Vec3 {
     self.0[2]
   }
 
+  pub const fn axis(&self, axis: Axis) -> f32 {
+    self.0[axis.index()]
+  }
+
+  pub fn minimum(&self, right_hand_side: Self) -> Self {
+    Self([
+      self.x().min(right_hand_side.x()),
+      self.y().min(right_hand_side.y()),
+      self.z().min(right_hand_side.z()),
+    ])
+  }
+
+  pub fn maximum(&self, right_hand_side: Self) -> Self {
+    Self([
+      self.x().max(right_hand_side.x()),
+      self.y().max(right_hand_side.y()),
+      self.z().max(right_hand_side.z()),
+    ])
+  }
+
   fn length_squared(&self) -> Distance {
     Distance::try_from_const(self.0[0] * self.0[0] + self.0[1] * self.0[1] + self.0[2] * self.0[2])
       .expect("length squared must be positive")
@@ -42,7 +80,7 @@ impl Vec3 {
     Self([
       self.y() * right_hand_side.z() - self.z() * right_hand_side.y(),
       self.z() * right_hand_side.x() - self.x() * right_hand_side.z(),
-      self.x() * right_hand_side.y() - self.y() - right_hand_side.x(),
+      self.x() * right_hand_side.y() - self.y() * right_hand_side.x(),
     ])
   }
 
@@ -97,6 +135,14 @@ impl SubAssign for Vec3 {
   }
 }
 
+impl Neg for Vec3 {
+  type Output = Self;
+
+  fn neg(self) -> Self::Output {
+    Self([-self.x(), -self.y(), -self.z()])
+  }
+}
+
 impl Mul for Vec3 {
   type Output = Self;
 
@@ -267,7 +313,19 @@ mod tests {
   fn cross() {
     let vector_1 = Vec3::new([1.0, 2.0, 3.0]);
     let vector_2 = Vec3::new([4.0, 5.0, 6.0]);
-    assert_eq!(vector_1.cross(vector_2), Vec3::new([-3.0, 6.0, -1.0]));
+    assert_eq!(vector_1.cross(vector_2), Vec3::new([-3.0, 6.0, -3.0]));
+  }
+  #[test]
+  fn negate_vector() {
+    let vector = Vec3::new([1.0, -2.0, 0.0]);
+    assert_eq!(-vector, Vec3::new([-1.0, 2.0, -0.0]));
+  }
+  #[test]
+  fn minimum_and_maximum() {
+    let vector_1 = Vec3::new([1.0, -2.0, 3.0]);
+    let vector_2 = Vec3::new([-1.0, 2.0, 3.0]);
+    assert_eq!(vector_1.minimum(vector_2), Vec3::new([-1.0, -2.0, 3.0]));
+    assert_eq!(vector_1.maximum(vector_2), Vec3::new([1.0, 2.0, 3.0]));
   }
   #[test]
   fn unit_vector() {
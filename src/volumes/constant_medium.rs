@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  materials::{volumetric::Volumetric, Material},
+  newtypes::direction::Direction,
+  phase_function::PhaseFunction,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+use super::{delta_tracking, ray_random};
+
+const EXIT_EPSILON: f32 = 1e-4;
+
+/// A uniform medium such as smoke filling a closed boundary, which reports a hit where the ray scatters inside it.
+#[derive(Debug, Clone)]
+pub struct ConstantMedium {
+  boundary: Arc<dyn Hittable>,
+  density: f32,
+  phase_material: Arc<dyn Material>,
+}
+
+impl ConstantMedium {
+  /// `density` is the scattering coefficient per unit of world distance.
+  pub fn new(
+    boundary: Arc<dyn Hittable>,
+    density: f32,
+    albedo: Vec3,
+    phase_function: PhaseFunction,
+  ) -> Result<Self, LocalError> {
+    if !(density.is_finite() && density > 0.0) {
+      return Err("A medium density must be positive and finite".into());
+    }
+    Ok(Self {
+      boundary,
+      density,
+      phase_material: Arc::new(Volumetric::new(albedo, phase_function)),
+    })
+  }
+  pub fn boundary(&self) -> &Arc<dyn Hittable> {
+    &self.boundary
+  }
+  pub const fn density(&self) -> f32 {
+    self.density
+  }
+}
+
+/// The parametric interval of `ray` that lies inside `boundary`, clipped to the accepted range. Only hits ahead of
+/// `minimum_distance` are asked for, and a first hit on a back face means the ray starts inside.
+pub(super) fn inside_interval(
+  boundary: &dyn Hittable,
+  ray: &Ray,
+  minimum_distance: f32,
+  maximum_distance: f32,
+) -> Option<(f32, f32)> {
+  let first = boundary.hit(ray, minimum_distance, f32::INFINITY)?;
+  let (entry, exit) = if first.front_face() {
+    let entry = first.distance();
+    let exit = boundary
+      .hit(ray, entry + EXIT_EPSILON * entry.abs().max(1.0), f32::INFINITY)?
+      .distance();
+    (entry, exit)
+  } else {
+    (minimum_distance, first.distance())
+  };
+  let exit = exit.min(maximum_distance);
+  (entry < exit).then(|| (entry, exit))
+}
+
+/// A record for a scattering event inside a medium, which has no meaningful surface normal.
+pub(super) fn scattering_record(ray: &Ray, distance: f32, material: &Arc<dyn Material>) -> HitRecord {
+  HitRecord::new(ray, distance, Direction::from_f32_array_const([1.0, 0.0, 0.0]), [0.0, 0.0]).with_default_material(material)
+}
+
+impl Hittable for ConstantMedium {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let (entry, exit) = inside_interval(self.boundary.as_ref(), ray, minimum_distance, maximum_distance)?;
+    let speed = ray.direction().length().as_f32();
+    let mut random = ray_random(ray);
+    let collision = delta_tracking(self.density, entry * speed, exit * speed, |_| self.density, &mut random)?;
+    Some(scattering_record(ray, collision / speed, &self.phase_material))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.boundary.bounding_box()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    aabb::Aabb,
+    hittable::{HitRecord, Hittable},
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    phase_function::PhaseFunction,
+    ray::Ray,
+    sdf::{MarchSettings, Sdf, SdfObject},
+    shapes::sphere::Sphere,
+    vec3::Vec3,
+  };
+
+  use super::ConstantMedium;
+
+  fn unit_sphere() -> Sphere {
+    Sphere::new_const(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Distance::try_from_const(1.0).unwrap(),
+    )
+  }
+
+  fn medium(density: f32) -> ConstantMedium {
+    ConstantMedium::new(
+      Arc::new(unit_sphere()),
+      density,
+      Vec3::new([1.0, 1.0, 1.0]),
+      PhaseFunction::Isotropic,
+    )
+    .unwrap()
+  }
+
+  /// A boundary that, like many non-analytic shapes, never reports hits behind the ray origin.
+  #[derive(Debug)]
+  struct AheadOnly(Sphere);
+
+  impl Hittable for AheadOnly {
+    fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+      self.0.hit(ray, minimum_distance.max(0.0), maximum_distance)
+    }
+    fn bounding_box(&self) -> Option<Aabb> {
+      self.0.bounding_box()
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_density() {
+    let boundary = Arc::new(Sphere::new_const(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Distance::try_from_const(1.0).unwrap(),
+    ));
+    assert!(ConstantMedium::new(boundary, 0.0, Vec3::new([1.0, 1.0, 1.0]), PhaseFunction::Isotropic).is_err());
+  }
+  #[test]
+  fn scatters_inside_the_boundary_as_often_as_expected() {
+    let medium = medium(0.5);
+    let samples: u16 = 5_000;
+    let mut scattered: u16 = 0;
+    for sample in 0..samples {
+      let offset = f32::from(sample) / f32::from(samples) * 1e-3;
+      let ray = Ray::new(
+        Point::from_f32_array_const([offset, 0.0, 5.0]),
+        Direction::from_f32_array_const([0.0, 0.0, -2.0]),
+      );
+      if let Some(record) = medium.hit(&ray, 0.0, f32::INFINITY) {
+        assert!(record.point().z().abs() <= 1.0 + 1e-4);
+        assert!(record.material().is_some());
+        scattered += 1;
+      }
+    }
+    let expected = 1.0 - (-0.5_f32 * 2.0).exp();
+    assert!((f32::from(scattered) / f32::from(samples) - expected).abs() < 0.03);
+  }
+  #[test]
+  fn starts_tracking_at_a_ray_origin_inside_the_medium() {
+    let boundaries: [Arc<dyn Hittable>; 3] = [
+      Arc::new(unit_sphere()),
+      Arc::new(SdfObject::new(Sdf::sphere(1.0), MarchSettings::default())),
+      Arc::new(AheadOnly(unit_sphere())),
+    ];
+    let ray = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    for boundary in boundaries {
+      let medium = ConstantMedium::new(boundary, 1e6, Vec3::new([1.0, 1.0, 1.0]), PhaseFunction::Isotropic).unwrap();
+      let record = medium.hit(&ray, 0.001, f32::INFINITY).expect("The medium is very dense");
+      assert!(record.distance() >= 0.001 && record.distance() < 0.01);
+    }
+  }
+}
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  materials::{volumetric::Volumetric, Material, Scatter},
+  newtypes::{point::Point, radiance::Radiance},
+  phase_function::PhaseFunction,
+  random::Random,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+use super::{
+  constant_medium::scattering_record, delta_tracking, ray_random, residual_ratio_tracking, voxel_grid::VoxelGrid,
+};
+
+/// Maps a world position inside `bounds` to `[0, 1]` along each axis of a voxel grid stretched over it.
+fn grid_position(bounds: &Aabb, point: Point) -> [f32; 3] {
+  let local = (point.as_vec3() - bounds.minimum().as_vec3()) / bounds.extent();
+  [local.x(), local.y(), local.z()]
+}
+
+/// Scatters like `Volumetric`, and glows with `color` scaled by the emission grid wherever a collision lands.
+#[derive(Debug, Clone)]
+struct GridMaterial {
+  scattering: Volumetric,
+  bounds: Aabb,
+  emission: Option<(Arc<VoxelGrid>, Vec3)>,
+}
+
+impl Material for GridMaterial {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    self.scattering.scatter(ray, record, random)
+  }
+  fn emitted(&self, _ray: &Ray, record: &HitRecord) -> Radiance {
+    self.emission.as_ref().map_or_else(Radiance::default, |(grid, color)| {
+      Radiance::from(*color * grid.sample(grid_position(&self.bounds, record.point())).max(0.0))
+    })
+  }
+}
+
+/// A medium whose density varies through a box, read from a voxel grid with trilinear interpolation.
+#[derive(Debug, Clone)]
+pub struct GridMedium {
+  bounds: Aabb,
+  density: Arc<VoxelGrid>,
+  density_scale: f32,
+  material: Arc<GridMaterial>,
+}
+
+impl GridMedium {
+  /// Stretches `density` over `bounds`; each voxel value times `density_scale` is a scattering coefficient per unit
+  /// of world distance.
+  pub fn new(
+    bounds: Aabb,
+    density: Arc<VoxelGrid>,
+    density_scale: f32,
+    albedo: Vec3,
+    phase_function: PhaseFunction,
+  ) -> Result<Self, LocalError> {
+    if !(density_scale.is_finite() && density_scale > 0.0) {
+      return Err("A density scale must be positive and finite".into());
+    }
+    if density.minimum() < 0.0 {
+      return Err("Voxel densities cannot be negative".into());
+    }
+    Ok(Self {
+      bounds,
+      density,
+      density_scale,
+      material: Arc::new(GridMaterial {
+        scattering: Volumetric::new(albedo, phase_function),
+        bounds,
+        emission: None,
+      }),
+    })
+  }
+  /// Adds light for fire-like effects: `color` times the emission grid, stretched over the same box.
+  pub fn with_emission(self, emission: Arc<VoxelGrid>, color: Vec3) -> Self {
+    Self {
+      material: Arc::new(GridMaterial {
+        emission: Some((emission, color)),
+        ..(*self.material).clone()
+      }),
+      ..self
+    }
+  }
+  pub const fn bounds(&self) -> Aabb {
+    self.bounds
+  }
+  pub fn density_at(&self, point: Point) -> f32 {
+    self.density.sample(grid_position(&self.bounds, point)) * self.density_scale
+  }
+  /// Estimates how much light survives along `ray` between the two distances, using residual ratio tracking
+  /// with the grid's smallest density as the control.
+  pub fn transmittance(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32, random: &mut Random) -> f32 {
+    let (entry, exit) = match self.bounds.hit_interval(ray, minimum_distance, maximum_distance) {
+      Some(interval) => interval,
+      None => return 1.0,
+    };
+    let speed = ray.direction().length().as_f32();
+    let control = self.density.minimum() * self.density_scale;
+    let residual_majorant = self.density.maximum() * self.density_scale - control;
+    residual_ratio_tracking(
+      control,
+      residual_majorant,
+      entry * speed,
+      exit * speed,
+      |distance| self.density_at(ray.at(distance / speed)),
+      random,
+    )
+  }
+}
+
+impl Hittable for GridMedium {
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let (entry, exit) = self.bounds.hit_interval(ray, minimum_distance, maximum_distance)?;
+    let speed = ray.direction().length().as_f32();
+    let mut random = ray_random(ray);
+    let majorant = self.density.maximum() * self.density_scale;
+    let collision = delta_tracking(
+      majorant,
+      entry * speed,
+      exit * speed,
+      |distance| self.density_at(ray.at(distance / speed)),
+      &mut random,
+    )?;
+    let material: Arc<dyn Material> = self.material.clone();
+    Some(scattering_record(ray, collision / speed, &material))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    Some(self.bounds)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    aabb::Aabb,
+    hittable::Hittable,
+    newtypes::{direction::Direction, point::Point, radiance::Radiance},
+    phase_function::PhaseFunction,
+    random::Random,
+    ray::Ray,
+    vec3::Vec3,
+    volumes::voxel_grid::VoxelGrid,
+  };
+
+  use super::GridMedium;
+
+  fn unit_box() -> Aabb {
+    Aabb::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.0]),
+      Point::from_f32_array_const([1.0, 1.0, 1.0]),
+    )
+  }
+  fn ray_along_x() -> Ray {
+    Ray::new(
+      Point::from_f32_array_const([-1.0, 0.5, 0.5]),
+      Direction::from_f32_array_const([1.0, 0.0, 0.0]),
+    )
+  }
+
+  #[test]
+  fn transmittance_through_a_gradient() {
+    let grid = Arc::new(VoxelGrid::new([2, 1, 1], vec![0.0, 2.0]).unwrap());
+    let medium = GridMedium::new(unit_box(), grid, 1.0, Vec3::new([1.0, 1.0, 1.0]), PhaseFunction::Isotropic).unwrap();
+    assert!((medium.density_at(Point::from_f32_array_const([0.5, 0.5, 0.5])) - 1.0).abs() < 1e-6);
+    let mut random = Random::new(5);
+    let samples: u16 = 10_000;
+    let total: f32 = (0..samples)
+      .map(|_| medium.transmittance(&ray_along_x(), 0.0, f32::INFINITY, &mut random))
+      .sum();
+    assert!((total / f32::from(samples) - (-1.0_f32).exp()).abs() < 0.01);
+    let mut passed: u16 = 0;
+    for sample in 0..samples {
+      let ray = Ray::new_at_time(ray_along_x().origin(), ray_along_x().direction(), f32::from(sample));
+      if medium.hit(&ray, 0.0, f32::INFINITY).is_none() {
+        passed += 1;
+      }
+    }
+    assert!((f32::from(passed) / f32::from(samples) - (-1.0_f32).exp()).abs() < 0.02);
+  }
+  #[test]
+  fn collisions_carry_grid_emission() {
+    let density = Arc::new(VoxelGrid::new([1, 1, 1], vec![50.0]).unwrap());
+    let emission = Arc::new(VoxelGrid::new([1, 1, 1], vec![3.0]).unwrap());
+    let medium = GridMedium::new(unit_box(), density, 1.0, Vec3::default(), PhaseFunction::Isotropic)
+      .unwrap()
+      .with_emission(emission, Vec3::new([1.0, 0.5, 0.0]));
+    let record = medium.hit(&ray_along_x(), 0.0, f32::INFINITY).expect("The medium is dense");
+    let emitted = record.material().unwrap().emitted(&ray_along_x(), &record);
+    assert_eq!(emitted, Radiance::new([3.0, 1.5, 0.0]));
+  }
+}
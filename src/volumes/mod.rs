@@ -0,0 +1,114 @@
+pub mod constant_medium;
+pub mod grid_medium;
+pub mod subsurface;
+pub mod voxel_grid;
+
+use crate::{random::Random, ray::Ray};
+
+/// A generator seeded from the ray itself, since `Hittable::hit` is not handed one.
+fn ray_random(ray: &Ray) -> Random {
+  let origin = ray.origin();
+  let direction = ray.direction();
+  Random::new_from_hash(&[
+    origin.x(),
+    origin.y(),
+    origin.z(),
+    direction.x(),
+    direction.y(),
+    direction.z(),
+    ray.time(),
+  ])
+}
+
+/// Samples where a ray first collides with a medium between `start` and `end`, or `None` when it passes through.
+/// Tentative collisions are drawn against `majorant`, which must bound `density` everywhere on the segment,
+/// and each is accepted with probability `density / majorant`.
+pub fn delta_tracking(
+  majorant: f32,
+  start: f32,
+  end: f32,
+  density: impl Fn(f32) -> f32,
+  random: &mut Random,
+) -> Option<f32> {
+  if majorant <= 0.0 {
+    return None;
+  }
+  let mut distance = start;
+  loop {
+    distance -= (1.0 - random.next_f32()).ln() / majorant;
+    if distance >= end {
+      return None;
+    }
+    if random.next_f32() * majorant < density(distance) {
+      return Some(distance);
+    }
+  }
+}
+
+/// Estimates the fraction of light passing from `start` to `end` without bias, as the product of
+/// `1 - density / majorant` over tentative collisions drawn against `majorant`.
+pub fn ratio_tracking(majorant: f32, start: f32, end: f32, density: impl Fn(f32) -> f32, random: &mut Random) -> f32 {
+  if majorant <= 0.0 {
+    return 1.0;
+  }
+  let mut transmittance = 1.0;
+  let mut distance = start;
+  loop {
+    distance -= (1.0 - random.next_f32()).ln() / majorant;
+    if distance >= end {
+      return transmittance;
+    }
+    transmittance *= 1.0 - density(distance) / majorant;
+  }
+}
+
+/// Ratio tracking of only the density above `control`, with the transmittance of `control` applied in closed form.
+/// `residual_majorant` bounds `density - control`; a tight `control` needs far fewer steps in dense media.
+pub fn residual_ratio_tracking(
+  control: f32,
+  residual_majorant: f32,
+  start: f32,
+  end: f32,
+  density: impl Fn(f32) -> f32,
+  random: &mut Random,
+) -> f32 {
+  let control_transmittance = (-control * (end - start)).exp();
+  control_transmittance * ratio_tracking(residual_majorant, start, end, |distance| density(distance) - control, random)
+}
+
+#[cfg(test)]
+mod test {
+  use crate::random::Random;
+
+  use super::{delta_tracking, ratio_tracking, residual_ratio_tracking};
+
+  #[test]
+  fn transmittance_follows_beer_lambert() {
+    let mut random = Random::new(9);
+    let samples: u16 = 20_000;
+    let density = 0.5;
+    let mut passed: u16 = 0;
+    for _ in 0..samples {
+      if delta_tracking(2.0, 0.0, 2.0, |_| density, &mut random).is_none() {
+        passed += 1;
+      }
+    }
+    let expected = (-density * 2.0_f32).exp();
+    assert!((f32::from(passed) / f32::from(samples) - expected).abs() < 0.02);
+  }
+  #[test]
+  fn ratio_trackers_match_analytic_transmittance() {
+    let density = |distance: f32| 0.5 + distance;
+    let expected = (-(0.5_f32 * 2.0 + 2.0)).exp();
+    let mut random = Random::new(4);
+    let samples: u16 = 10_000;
+    let mut ratio_total = 0.0;
+    let mut residual_total = 0.0;
+    for _ in 0..samples {
+      ratio_total += ratio_tracking(3.0, 0.0, 2.0, density, &mut random);
+      residual_total += residual_ratio_tracking(0.5, 2.0, 0.0, 2.0, density, &mut random);
+    }
+    assert!((ratio_total / f32::from(samples) - expected).abs() < 0.01);
+    assert!((residual_total / f32::from(samples) - expected).abs() < 0.01);
+  }
+}
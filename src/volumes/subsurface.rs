@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use crate::{
+  aabb::Aabb,
+  error::LocalError,
+  hittable::{HitRecord, Hittable},
+  materials::{dielectric::Dielectric, Material, Scatter},
+  newtypes::radiance::Radiance,
+  phase_function::PhaseFunction,
+  random::Random,
+  ray::Ray,
+  vec3::Vec3,
+};
+
+use super::{constant_medium::scattering_record, ray_random};
+
+/// The fraction of light in each channel surviving `distance` through a medium with per-channel `extinction`.
+fn transmittance(extinction: Vec3, distance: f32) -> Vec3 {
+  Vec3::new([
+    (-extinction.x() * distance).exp(),
+    (-extinction.y() * distance).exp(),
+    (-extinction.z() * distance).exp(),
+  ])
+}
+
+fn channel_mean(color: Vec3) -> f32 {
+  (color.x() + color.y() + color.z()) / 3.0
+}
+
+/// A collision inside a subsurface medium. Its distance from the ray origin was drawn for one channel picked at random,
+/// so each channel is weighted against the average density of the three choices (Chiang et al. 2016).
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct RandomWalk {
+  scattering: Vec3,
+  extinction: Vec3,
+}
+
+impl Material for RandomWalk {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let distance = record.distance() * ray.direction().length().as_f32();
+    let surviving = transmittance(self.extinction, distance);
+    let density = channel_mean(self.extinction * surviving);
+    if density <= 0.0 {
+      return None;
+    }
+    let direction = PhaseFunction::Isotropic.sample(ray.direction(), random);
+    Some(Scatter::new(
+      Radiance::from(self.scattering * surviving / density),
+      Ray::new_at_time(record.point(), direction, ray.time()),
+    ))
+  }
+}
+
+/// The interface of a subsurface medium. Walks reaching it from inside carry the weight of having passed every
+/// channel's collisions by, against the average chance of doing so.
+#[derive(Debug, Clone)]
+struct WalkExit {
+  interface: Arc<dyn Material>,
+  extinction: Vec3,
+}
+
+impl Material for WalkExit {
+  fn scatter(&self, ray: &Ray, record: &HitRecord, random: &mut Random) -> Option<Scatter> {
+    let scatter = self.interface.scatter(ray, record, random)?;
+    if record.front_face() {
+      return Some(scatter);
+    }
+    let distance = record.distance() * ray.direction().length().as_f32();
+    let surviving = transmittance(self.extinction, distance);
+    let escaping = channel_mean(surviving);
+    if escaping <= 0.0 {
+      return None;
+    }
+    let attenuation = scatter.attenuation() * Radiance::from(surviving) / escaping;
+    Some(match scatter.specular() {
+      Some(specular) => Scatter::new_specular(attenuation, scatter.ray(), specular),
+      None => Scatter::new(attenuation, scatter.ray()),
+    })
+  }
+  fn emitted(&self, ray: &Ray, record: &HitRecord) -> Radiance {
+    self.interface.emitted(ray, record)
+  }
+}
+
+/// A translucent object such as skin, wax or marble: a closed `boundary` with outward normals, a dielectric interface,
+/// and a scattering medium inside that light walks through before leaving somewhere else on the surface.
+#[derive(Debug, Clone)]
+pub struct Subsurface {
+  boundary: Arc<dyn Hittable>,
+  interface: Arc<dyn Material>,
+  exit: Arc<dyn Material>,
+  walk: Arc<dyn Material>,
+  albedo: Vec3,
+  mean_free_path: Vec3,
+}
+
+impl Subsurface {
+  /// `albedo` is the chance of surviving each collision and `mean_free_path` the average distance between collisions,
+  /// both per color channel, with distances in world units.
+  pub fn new(
+    boundary: Arc<dyn Hittable>,
+    albedo: Vec3,
+    mean_free_path: Vec3,
+    index_of_refraction: f32,
+  ) -> Result<Self, LocalError> {
+    let channels = |color: Vec3| [color.x(), color.y(), color.z()];
+    if !channels(albedo).iter().all(|channel| (0.0..=1.0).contains(channel)) {
+      return Err("A subsurface albedo must be between 0.0 and 1.0 in every channel".into());
+    }
+    if !channels(mean_free_path)
+      .iter()
+      .all(|channel| channel.is_finite() && *channel > 0.0)
+    {
+      return Err("A mean free path must be positive and finite in every channel".into());
+    }
+    if !(index_of_refraction.is_finite() && index_of_refraction >= 1.0) {
+      return Err("A subsurface index of refraction must be finite and at least 1.0".into());
+    }
+    let extinction = Vec3::new([1.0, 1.0, 1.0]) / mean_free_path;
+    let interface: Arc<dyn Material> = Arc::new(Dielectric::new(index_of_refraction));
+    Ok(Self {
+      boundary,
+      exit: Arc::new(WalkExit {
+        interface: interface.clone(),
+        extinction,
+      }),
+      interface,
+      walk: Arc::new(RandomWalk {
+        scattering: albedo * extinction,
+        extinction,
+      }),
+      albedo,
+      mean_free_path,
+    })
+  }
+  /// Replaces the smooth dielectric surface, for example with rough glass.
+  pub fn with_interface(self, interface: Arc<dyn Material>) -> Self {
+    Self {
+      exit: Arc::new(WalkExit {
+        interface: interface.clone(),
+        extinction: Vec3::new([1.0, 1.0, 1.0]) / self.mean_free_path,
+      }),
+      interface,
+      ..self
+    }
+  }
+  pub fn boundary(&self) -> &Arc<dyn Hittable> {
+    &self.boundary
+  }
+  pub fn interface(&self) -> &Arc<dyn Material> {
+    &self.interface
+  }
+  pub const fn albedo(&self) -> Vec3 {
+    self.albedo
+  }
+  pub const fn mean_free_path(&self) -> Vec3 {
+    self.mean_free_path
+  }
+}
+
+impl Hittable for Subsurface {
+  /// Rays from outside meet the interface. Rays travelling inside, which see the boundary's back face, collide with
+  /// the medium before reaching it or else meet the interface from within.
+  fn hit(&self, ray: &Ray, minimum_distance: f32, maximum_distance: f32) -> Option<HitRecord> {
+    let surface = self
+      .boundary
+      .hit(ray, minimum_distance, maximum_distance)
+      .map(|record| record.with_default_material(&self.exit));
+    let inside = surface.as_ref().map_or(false, |record| !record.front_face());
+    if !inside {
+      return surface;
+    }
+    let surface = surface?;
+    // Distances are drawn from the ray origin, where the walk last turned, so the materials can weight them.
+    let speed = ray.direction().length().as_f32();
+    let mut random = ray_random(ray);
+    let extinction = Vec3::new([1.0, 1.0, 1.0]) / self.mean_free_path;
+    let channel = [extinction.x(), extinction.y(), extinction.z()];
+    let density = channel[random.next_u32() as usize % 3];
+    let collision = -(1.0 - random.next_f32()).ln() / density / speed;
+    if collision >= surface.distance() {
+      return Some(surface);
+    }
+    Some(scattering_record(ray, collision.max(minimum_distance), &self.walk))
+  }
+  fn bounding_box(&self) -> Option<Aabb> {
+    self.boundary.bounding_box()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use crate::{
+    hittable::Hittable,
+    newtypes::{direction::Direction, distance::Distance, point::Point},
+    ray::Ray,
+    shapes::sphere::Sphere,
+    test_support::white_furnace,
+    vec3::Vec3,
+  };
+
+  use super::Subsurface;
+
+  fn ball(albedo: Vec3, mean_free_path: Vec3) -> Subsurface {
+    let boundary = Arc::new(Sphere::new_const(Point::default(), Distance::try_from_const(1.0).unwrap()));
+    Subsurface::new(boundary, albedo, mean_free_path, 1.3).unwrap()
+  }
+
+  fn furnace(object: &Subsurface) -> Vec3 {
+    white_furnace(object, 20_000, 2000, 14).0
+  }
+
+  #[test]
+  fn rejects_invalid_parameters() {
+    let boundary = Arc::new(Sphere::new_const(Point::default(), Distance::try_from_const(1.0).unwrap()));
+    let grey = Vec3::new([0.5, 0.5, 0.5]);
+    assert!(Subsurface::new(boundary.clone(), Vec3::new([1.5, 0.5, 0.5]), grey, 1.3).is_err());
+    assert!(Subsurface::new(boundary.clone(), grey, Vec3::new([0.5, 0.0, 0.5]), 1.3).is_err());
+    assert!(Subsurface::new(boundary, grey, grey, 0.5).is_err());
+  }
+  #[test]
+  fn walks_start_at_the_surface_and_stay_inside() {
+    let object = ball(Vec3::new([0.9, 0.9, 0.9]), Vec3::new([0.01, 0.01, 0.01]));
+    let from_outside = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 5.0]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let surface = object
+      .hit(&from_outside, 0.001, f32::INFINITY)
+      .expect("The ray points at the ball");
+    assert!(surface.front_face() && (surface.distance() - 4.0).abs() < 1e-5);
+    let from_inside = Ray::new(
+      Point::from_f32_array_const([0.0, 0.0, 0.5]),
+      Direction::from_f32_array_const([0.0, 0.0, -1.0]),
+    );
+    let collision = object.hit(&from_inside, 0.001, f32::INFINITY).expect("The medium is dense");
+    assert!(collision.distance() < 0.2);
+  }
+  #[test]
+  fn white_furnace_conserves_every_channel() {
+    let albedo = furnace(&ball(Vec3::new([1.0, 1.0, 1.0]), Vec3::new([1.0, 0.5, 0.25])));
+    for channel in [albedo.x(), albedo.y(), albedo.z()] {
+      assert!((channel - 1.0).abs() < 0.05, "The furnace returned {}", albedo);
+    }
+  }
+  #[test]
+  fn absorption_colors_the_light_that_comes_back() {
+    let albedo = furnace(&ball(Vec3::new([0.99, 0.9, 0.6]), Vec3::new([0.5, 0.25, 0.1])));
+    assert!(
+      albedo.x() > albedo.y() && albedo.y() > albedo.z(),
+      "The furnace returned {}",
+      albedo
+    );
+  }
+}
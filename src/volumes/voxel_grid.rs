@@ -0,0 +1,147 @@
+use std::{fs::File, io::Read, path::Path};
+
+use crate::error::LocalError;
+
+const MAGIC: &str = "VOXELS";
+
+/// A dense block of scalar samples stored x fastest, then y, then z, with each sample at the center of its cell.
+#[derive(Debug, Default, Clone, PartialEq, PartialOrd)]
+pub struct VoxelGrid {
+  dimensions: [usize; 3],
+  values: Vec<f32>,
+}
+
+impl VoxelGrid {
+  pub fn new(dimensions: [usize; 3], values: Vec<f32>) -> Result<Self, LocalError> {
+    if dimensions.contains(&0) {
+      return Err("A voxel grid needs at least one cell along every axis".into());
+    }
+    let count = cell_count(dimensions)?;
+    if values.len() != count {
+      return Err(
+        format!(
+          "A {}x{}x{} voxel grid needs {} values, not {}",
+          dimensions[0],
+          dimensions[1],
+          dimensions[2],
+          count,
+          values.len()
+        )
+        .into(),
+      );
+    }
+    if values.iter().any(|value| !value.is_finite()) {
+      return Err("Voxel values must be finite".into());
+    }
+    Ok(Self { dimensions, values })
+  }
+  pub fn load(path: &Path) -> Result<Self, LocalError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    Self::parse(&bytes)
+  }
+  /// Reads a `VOXELS <x> <y> <z>` text header, one whitespace byte, then the values as little-endian `f32`s.
+  pub fn parse(bytes: &[u8]) -> Result<Self, LocalError> {
+    let header_end = bytes
+      .iter()
+      .enumerate()
+      .filter(|(_, byte)| byte.is_ascii_whitespace())
+      .nth(3)
+      .map(|(index, _)| index)
+      .ok_or("The voxel grid header is incomplete")?;
+    let header = std::str::from_utf8(&bytes[..header_end])?;
+    let mut tokens = header.split_ascii_whitespace();
+    if tokens.next() != Some(MAGIC) {
+      return Err("Voxel grid files must start with VOXELS".into());
+    }
+    let mut dimensions = [0; 3];
+    for dimension in &mut dimensions {
+      *dimension = tokens.next().ok_or("The voxel grid header is incomplete")?.parse()?;
+    }
+    let data = &bytes[header_end + 1..];
+    let count = cell_count(dimensions)?;
+    let byte_count = count.checked_mul(4).ok_or("The voxel grid is too large")?;
+    if data.len() < byte_count {
+      return Err("The voxel grid ends before all values were read".into());
+    }
+    let values = data
+      .chunks_exact(4)
+      .take(count)
+      .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+      .collect();
+    Self::new(dimensions, values)
+  }
+  pub const fn dimensions(&self) -> [usize; 3] {
+    self.dimensions
+  }
+  pub fn value(&self, x: usize, y: usize, z: usize) -> f32 {
+    self.values[(z * self.dimensions[1] + y) * self.dimensions[0] + x]
+  }
+  pub fn minimum(&self) -> f32 {
+    self.values.iter().copied().fold(f32::INFINITY, f32::min)
+  }
+  pub fn maximum(&self) -> f32 {
+    self.values.iter().copied().fold(f32::NEG_INFINITY, f32::max)
+  }
+  /// Trilinearly interpolates at `position`, given in `[0, 1]` across the grid; outside positions clamp to the border.
+  #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  pub fn sample(&self, position: [f32; 3]) -> f32 {
+    let mut lower = [0; 3];
+    let mut upper = [0; 3];
+    let mut fraction = [0.0; 3];
+    for axis in 0..3 {
+      let last = self.dimensions[axis] - 1;
+      let continuous = (position[axis] * self.dimensions[axis] as f32 - 0.5).clamp(0.0, last as f32);
+      lower[axis] = (continuous.floor() as usize).min(last);
+      upper[axis] = (lower[axis] + 1).min(last);
+      fraction[axis] = continuous - lower[axis] as f32;
+    }
+    let lerp = |start: f32, end: f32, amount: f32| start + (end - start) * amount;
+    let along_x = |y: usize, z: usize| lerp(self.value(lower[0], y, z), self.value(upper[0], y, z), fraction[0]);
+    let along_y = |z: usize| lerp(along_x(lower[1], z), along_x(upper[1], z), fraction[1]);
+    lerp(along_y(lower[2]), along_y(upper[2]), fraction[2])
+  }
+}
+
+fn cell_count(dimensions: [usize; 3]) -> Result<usize, LocalError> {
+  dimensions
+    .iter()
+    .try_fold(1_usize, |count, dimension| count.checked_mul(*dimension))
+    .ok_or_else(|| "The voxel grid is too large".into())
+}
+
+#[cfg(test)]
+mod test {
+  use super::VoxelGrid;
+
+  #[test]
+  fn parses_binary_grids() {
+    let mut bytes = b"VOXELS 2 1 1\n".to_vec();
+    for value in [0.25_f32, 4.0] {
+      bytes.extend(value.to_le_bytes());
+    }
+    let grid = VoxelGrid::parse(&bytes).expect("This is a valid grid");
+    assert_eq!(grid.dimensions(), [2, 1, 1]);
+    assert!(grid.value(1, 0, 0).eq(&4.0));
+    assert!(grid.maximum().eq(&4.0));
+    assert!(grid.minimum().eq(&0.25));
+  }
+  #[test]
+  fn rejects_bad_grids() {
+    assert!(VoxelGrid::parse(b"VOXELS 2 1 1\n\x00\x00\x00\x00").is_err());
+    assert!(VoxelGrid::parse(b"VOXELZ 1 1 1\n\x00\x00\x00\x00").is_err());
+    assert!(VoxelGrid::new([0, 1, 1], Vec::new()).is_err());
+    assert!(VoxelGrid::new([1, 1, 1], vec![f32::NAN]).is_err());
+    assert!(VoxelGrid::parse(b"VOXELS 4294967296 4294967296 2\n").is_err());
+    assert!(VoxelGrid::parse(b"VOXELS 4611686018427387904 1 1\n").is_err());
+    assert!(VoxelGrid::new([usize::MAX, 2, 1], Vec::new()).is_err());
+  }
+  #[test]
+  fn trilinear_sampling_between_cell_centers() {
+    let grid = VoxelGrid::new([2, 2, 2], vec![0.0, 1.0, 0.0, 1.0, 2.0, 3.0, 2.0, 3.0]).unwrap();
+    assert!((grid.sample([0.5, 0.5, 0.5]) - 1.5).abs() < 1e-6);
+    assert!((grid.sample([0.25, 0.25, 0.25]) - 0.0).abs() < 1e-6);
+    assert!((grid.sample([1.0, 0.0, 1.0]) - 3.0).abs() < 1e-6);
+    assert!((grid.sample([0.5, 0.0, 0.25]) - 0.5).abs() < 1e-6);
+  }
+}